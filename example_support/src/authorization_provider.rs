@@ -1,4 +1,7 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
 
 use rand::{distributions::Alphanumeric, thread_rng, Rng};
 use raos::{
@@ -8,9 +11,16 @@ use raos::{
 };
 use tokio::sync::Mutex;
 
+struct IssuedCode {
+    grant: Grant<u32>,
+    used: bool,
+    valid_until: Instant,
+}
+
 #[derive(Default)]
 pub struct ExampleAuthorizationProvider {
-    codes: Mutex<HashMap<String, Grant<u32>>>,
+    // Issued codes by code
+    codes: Mutex<HashMap<String, IssuedCode>>,
 }
 
 #[async_trait]
@@ -36,7 +46,12 @@ impl AuthorizationProvider for ExampleAuthorizationProvider {
         let random_string: String =
             thread_rng().sample_iter(&Alphanumeric).take(50).map(char::from).collect();
 
-        self.codes.lock().await.insert(random_string.clone(), grant);
+        let valid_until = Instant::now() + Duration::from_secs(600);
+
+        let mut codes = self.codes.lock().await;
+        // Expired codes can no longer be exchanged, so there is no replay left to detect
+        codes.retain(|_, code| code.valid_until > Instant::now());
+        codes.insert(random_string.clone(), IssuedCode { grant, used: false, valid_until });
         Ok(random_string)
     }
 
@@ -44,7 +59,22 @@ impl AuthorizationProvider for ExampleAuthorizationProvider {
         &self,
         code: String,
     ) -> Result<Option<Grant<Self::OwnerId>>, Self::Error> {
-        let grant = self.codes.lock().await.remove(&code);
+        // Used codes are kept until they expire, so a replayed code can be detected
+        let grant = self
+            .codes
+            .lock()
+            .await
+            .get(&code)
+            .filter(|code| code.valid_until > Instant::now())
+            .map(|code| code.grant.clone());
         Ok(grant)
     }
+
+    async fn mark_code_used(&self, code: &str) -> Result<bool, Self::Error> {
+        let mut codes = self.codes.lock().await;
+        let Some(code) = codes.get_mut(code) else {
+            return Ok(false);
+        };
+        Ok(!std::mem::replace(&mut code.used, true))
+    }
 }
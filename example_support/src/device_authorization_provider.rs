@@ -0,0 +1,111 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use rand::{distributions::Alphanumeric, seq::SliceRandom, thread_rng, Rng};
+use raos::{
+    async_trait,
+    device::{
+        DeviceAuthorization, DeviceAuthorizationDecision, DeviceAuthorizationProvider,
+        DeviceCodeStatus, DeviceCodes, DeviceGrant,
+    },
+};
+use tokio::sync::Mutex;
+
+struct PendingDeviceAuthorization {
+    authorization: DeviceAuthorization,
+    user_code: String,
+    decision: Option<DeviceAuthorizationDecision<u32>>,
+    valid_until: Instant,
+}
+
+#[derive(Default)]
+pub struct ExampleDeviceAuthorizationProvider {
+    // Pending authorizations by device code
+    pending: Mutex<HashMap<String, PendingDeviceAuthorization>>,
+}
+
+#[async_trait]
+impl DeviceAuthorizationProvider for ExampleDeviceAuthorizationProvider {
+    type OwnerId = u32;
+    type Error = ();
+
+    async fn generate_device_codes(
+        &self,
+        authorization: DeviceAuthorization,
+    ) -> Result<DeviceCodes, Self::Error> {
+        const USER_CODE_CHARSET: &[u8] = b"BCDFGHJKLMNPQRSTVWXZ";
+
+        let device_code: String =
+            thread_rng().sample_iter(&Alphanumeric).take(50).map(char::from).collect();
+        let user_code: String =
+            (0..8).map(|_| *USER_CODE_CHARSET.choose(&mut thread_rng()).unwrap() as char).collect();
+        let valid_until = Instant::now() + Duration::from_secs(600);
+
+        self.pending.lock().await.insert(
+            device_code.clone(),
+            PendingDeviceAuthorization {
+                authorization,
+                user_code: user_code.clone(),
+                decision: None,
+                valid_until,
+            },
+        );
+        Ok(DeviceCodes { device_code, user_code, valid_until })
+    }
+
+    async fn get_device_authorization(
+        &self,
+        user_code: &str,
+    ) -> Result<Option<DeviceAuthorization>, Self::Error> {
+        let pending = self.pending.lock().await;
+        Ok(pending
+            .values()
+            .find(|p| p.user_code == user_code && p.decision.is_none())
+            .filter(|p| p.valid_until > Instant::now())
+            .map(|p| p.authorization.clone()))
+    }
+
+    async fn complete_device_authorization(
+        &self,
+        user_code: &str,
+        decision: DeviceAuthorizationDecision<Self::OwnerId>,
+    ) -> Result<(), Self::Error> {
+        let mut pending = self.pending.lock().await;
+        if let Some(p) = pending.values_mut().find(|p| p.user_code == user_code) {
+            p.decision = Some(decision);
+        }
+        Ok(())
+    }
+
+    async fn poll_device_code(
+        &self,
+        device_code: String,
+    ) -> Result<DeviceCodeStatus<Self::OwnerId>, Self::Error> {
+        let mut pending = self.pending.lock().await;
+        let Some(p) = pending.get(&device_code) else {
+            return Ok(DeviceCodeStatus::Invalid);
+        };
+        if p.valid_until <= Instant::now() {
+            pending.remove(&device_code);
+            return Ok(DeviceCodeStatus::Expired);
+        }
+        if p.decision.is_none() {
+            return Ok(DeviceCodeStatus::Pending);
+        }
+
+        // The decision has been made, so the device code can only be used once
+        let p = pending.remove(&device_code).unwrap();
+        Ok(match p.decision.unwrap() {
+            DeviceAuthorizationDecision::Approved(resource_owner) => {
+                DeviceCodeStatus::Approved(DeviceGrant {
+                    client_id: p.authorization.client_id,
+                    resource_owner,
+                    scope: p.authorization.scope,
+                })
+            }
+            DeviceAuthorizationDecision::Denied => DeviceCodeStatus::Denied,
+        })
+    }
+}
@@ -1,7 +1,11 @@
 mod authorization_provider;
 mod client_provider;
+mod device_authorization_provider;
+mod pushed_authorization_provider;
 mod token_provider;
 
 pub use authorization_provider::*;
 pub use client_provider::*;
+pub use device_authorization_provider::*;
+pub use pushed_authorization_provider::*;
 pub use token_provider::*;
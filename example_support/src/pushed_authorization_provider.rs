@@ -0,0 +1,48 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use raos::{
+    async_trait,
+    authorize::AuthorizationRequest,
+    par::{PushedAuthorizationProvider, PushedRequestReference},
+};
+use tokio::sync::Mutex;
+
+#[derive(Default)]
+pub struct ExamplePushedAuthorizationProvider {
+    // Pushed requests by reference
+    requests: Mutex<HashMap<String, (AuthorizationRequest, Instant)>>,
+}
+
+#[async_trait]
+impl PushedAuthorizationProvider for ExamplePushedAuthorizationProvider {
+    type Error = ();
+
+    async fn store_authorization_request(
+        &self,
+        request: AuthorizationRequest,
+    ) -> Result<PushedRequestReference, Self::Error> {
+        let reference: String =
+            thread_rng().sample_iter(&Alphanumeric).take(50).map(char::from).collect();
+        let valid_until = Instant::now() + Duration::from_secs(60);
+
+        self.requests.lock().await.insert(reference.clone(), (request, valid_until));
+        Ok(PushedRequestReference { reference, valid_until })
+    }
+
+    async fn take_authorization_request(
+        &self,
+        reference: &str,
+    ) -> Result<Option<AuthorizationRequest>, Self::Error> {
+        Ok(self
+            .requests
+            .lock()
+            .await
+            .remove(reference)
+            .filter(|(_, valid_until)| *valid_until > Instant::now())
+            .map(|(request, _)| request))
+    }
+}
@@ -5,7 +5,10 @@ use rand::{distributions::Alphanumeric, thread_rng, Rng};
 use raos::{
     async_trait,
     common::model::Client,
-    token::{GrantType, RefreshGrant, Token, TokenProvider},
+    token::{
+        GrantType, IssuedToken, RefreshGrant, Token, TokenConfirmation, TokenProvider,
+        TokenTypeHint,
+    },
 };
 
 pub struct DumbTokenProvider;
@@ -19,6 +22,8 @@ impl TokenProvider for DumbTokenProvider {
         &self,
         _client: &Client,
         _grant: GrantType<Self::OwnerId>,
+        _confirmation: TokenConfirmation,
+        _audience: &[String],
     ) -> Result<Token, Self::Error> {
         let random_string: String = thread_rng()
             .sample_iter(&Alphanumeric)
@@ -38,4 +43,38 @@ impl TokenProvider for DumbTokenProvider {
     ) -> Result<Option<RefreshGrant<Self::OwnerId>>, Self::Error> {
         Ok(None)
     }
+
+    async fn mark_refresh_token_used(&self, _refresh_token: &str) -> Result<bool, Self::Error> {
+        // No refresh tokens are issued, so they can never be used
+        Ok(true)
+    }
+
+    async fn revoke_refresh_token_family(&self, _family_id: &str) -> Result<(), Self::Error> {
+        // Tokens are not stored, so there is nothing to revoke
+        Ok(())
+    }
+
+    async fn revoke_code_tokens(&self, _code_id: &str) -> Result<(), Self::Error> {
+        // Tokens are not stored, so there is nothing to revoke
+        Ok(())
+    }
+
+    async fn lookup_token(
+        &self,
+        _token: String,
+        _token_type_hint: Option<TokenTypeHint>,
+    ) -> Result<Option<IssuedToken<Self::OwnerId>>, Self::Error> {
+        // Tokens are not stored, so they can never be looked up
+        Ok(None)
+    }
+
+    async fn revoke_token(
+        &self,
+        _client: &Client,
+        _token: String,
+        _token_type_hint: Option<TokenTypeHint>,
+    ) -> Result<(), Self::Error> {
+        // Tokens are not stored, so there is nothing to revoke
+        Ok(())
+    }
 }
@@ -0,0 +1,121 @@
+pub use provider::*;
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Deserialize;
+
+use crate::{
+    common::{
+        auth::Audience,
+        frontend::{OAuthError, OAuthValidationError},
+        jose::CompactJws,
+        model::Client,
+    },
+    manager::OAuthManager,
+    token::GrantType,
+};
+
+mod provider;
+#[cfg(test)]
+mod test;
+
+/// The grant type used by clients to exchange a JWT assertion for an access token, as described in RFC 7523 section 2.1.
+pub const JWT_BEARER_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:jwt-bearer";
+
+/// The claims of an authorization grant assertion, as described in RFC 7523 section 3.
+#[derive(Deserialize)]
+struct GrantAssertionClaims {
+    iss: String,
+    sub: String,
+    aud: Audience,
+    exp: u64,
+    nbf: Option<u64>,
+}
+
+impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
+    /// Validate a JWT bearer grant, verifying the assertion against the keys of its issuer
+    /// and mapping its subject to a resource owner through the [JwtBearerProvider].
+    ///
+    /// # Parameters
+    /// - `client` - The client that presented the assertion.
+    /// - `assertion` - The JWT assertion sent in the token request.
+    /// - `scope` - The scope requested by the client, the default scopes of the client if omitted.
+    ///
+    /// # Errors
+    /// This function will return an [OAuthError] if the JWT bearer grant is not enabled, if the assertion is invalid,
+    /// or if the client is not allowed any of the requested scopes.
+    pub(crate) async fn validate_jwt_bearer(
+        &self,
+        client: &Client,
+        assertion: String,
+        scope: Option<Vec<String>>,
+    ) -> Result<GrantType<U>, OAuthError<E>> {
+        let Some(jwt_bearer_provider) = &self.jwt_bearer_provider else {
+            return Err(OAuthValidationError::InvalidGrantType {
+                requested: JWT_BEARER_GRANT_TYPE.to_string(),
+            }
+            .into());
+        };
+        let invalid = |reason| OAuthValidationError::InvalidAssertion(reason).into();
+
+        let Some(jws) = CompactJws::parse(&assertion) else {
+            return Err(invalid("the assertion is not a valid JWS"));
+        };
+        let Some(claims) = jws.claims::<GrantAssertionClaims>() else {
+            return Err(invalid("the assertion is missing required claims"));
+        };
+
+        // The JWT MUST be digitally signed or have a Message Authentication Code applied by the issuer.
+        let Some(keys) = jwt_bearer_provider
+            .get_issuer_keys(&claims.iss)
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?
+        else {
+            return Err(invalid("the issuer is not trusted"));
+        };
+        let Some(jws_verifier) = &self.jws_verifier else {
+            return Err(invalid("no JWS verifier is configured"));
+        };
+        let signature_valid = keys
+            .iter()
+            .filter(|key| jws.header.kid.is_none() || key.kid == jws.header.kid)
+            .any(|key| jws.verify(jws_verifier.as_ref(), key));
+        if !signature_valid {
+            return Err(invalid("the signature is invalid"));
+        }
+
+        if !claims.aud.contains_any(&self.assertion_audiences()?) {
+            return Err(invalid("the audience does not identify this authorization server"));
+        }
+
+        // The authorization server MUST reject any JWT with an expiration time that has passed.
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        if claims.exp <= now {
+            return Err(invalid("the assertion has expired"));
+        }
+        if claims.nbf.is_some_and(|nbf| nbf > now) {
+            return Err(invalid("the assertion is not yet valid"));
+        }
+
+        // For the authorization grant, the subject typically identifies an authorized accessor
+        // for which the access token is being requested.
+        let Some(resource_owner) = jwt_bearer_provider
+            .resolve_subject(client, &claims.iss, &claims.sub)
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?
+        else {
+            return Err(invalid("the subject is unknown"));
+        };
+
+        let scope = self
+            .client_provider
+            .allow_client_scopes(client, scope.unwrap_or_else(|| client.default_scopes.clone()))
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?;
+        if scope.is_empty() {
+            return Err(OAuthValidationError::NoScopesProvided.into());
+        }
+
+        Ok(GrantType::JwtBearer { resource_owner, scope, issuer: claims.iss })
+    }
+}
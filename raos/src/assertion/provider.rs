@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+
+use crate::common::{jose::Jwk, model::Client};
+
+/// JWT bearer provider trait.
+/// This is an optional trait that can be implemented by the end user, to enable the JWT bearer authorization grant
+/// as described in RFC 7523 section 2.1.
+///
+/// This trait is used to look up the keys of the issuers that are trusted to sign assertions,
+/// and to map the subject of an assertion to a resource owner.
+/// Verifying the signatures of assertions requires a [JwsVerifier](crate::common::jose::JwsVerifier) to be configured.
+#[async_trait]
+pub trait JwtBearerProvider: 'static + Send + Sync {
+    /// This is the type of the owner id that is used to identify the resource owner.
+    /// This type will need to match the OwnerId used in [AuthorizationProvider](crate::authorize::AuthorizationProvider).
+    type OwnerId;
+    /// This is the error type that can be returned by the JWT bearer provider implementing this trait.
+    /// This type will need to match the Error used in [ClientProvider](crate::common::model::ClientProvider).
+    type Error;
+
+    /// Get the public keys of a trusted issuer, used to verify the signature of an assertion.
+    ///
+    /// # Implementation notes
+    /// Only issuers that are trusted to make statements about resource owners should be returned,
+    /// as any assertion signed by these keys will result in an access token.
+    ///
+    /// # Arguments
+    /// * `issuer` - The `iss` claim of the assertion, which has not been verified yet.
+    ///
+    /// # Returns
+    /// An [Option] containing the keys of the issuer, or [None] if the issuer is not trusted.
+    ///
+    /// # Errors
+    /// If the provider fails to look up the keys, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn get_issuer_keys(&self, issuer: &str) -> Result<Option<Vec<Jwk>>, Self::Error>;
+
+    /// Map the subject of a verified assertion to a resource owner.
+    ///
+    /// # Implementation notes
+    /// This is also the place to check whether the client is allowed to act for the resource owner,
+    /// for example by checking that the client belongs to the partner that issued the assertion.
+    ///
+    /// # Arguments
+    /// * `client` - The client that presented the assertion.
+    /// * `issuer` - The issuer that signed the assertion.
+    /// * `subject` - The `sub` claim of the assertion, as identified by the issuer.
+    ///
+    /// # Returns
+    /// An [Option] containing the resource owner, or [None] if the subject is unknown or the client may not act for it.
+    ///
+    /// # Errors
+    /// If the provider fails to resolve the subject, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn resolve_subject(
+        &self,
+        client: &Client,
+        issuer: &str,
+        subject: &str,
+    ) -> Result<Option<Self::OwnerId>, Self::Error>;
+}
@@ -0,0 +1,205 @@
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use mockall::predicate::{always, eq};
+use serde_json::{json, Value};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use crate::{
+    common::{
+        frontend::{FrontendRequest, OAuthError, OAuthValidationError, PublicOAuthError},
+        jose::Jwk,
+    },
+    manager::OAuthManager,
+    test::{mock::request_from_raw_http, TestEnvironment, DEFAULT_TOKEN},
+    token::{GrantType, RequestedGrantType, Token, TokenRequest},
+};
+
+static ISSUER: &str = "https://partner.example.com";
+static SUBJECT: &str = "partner_user";
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
+
+fn assertion_claims() -> Value {
+    json!({
+        "iss": ISSUER,
+        "sub": SUBJECT,
+        "aud": "https://example.com/token",
+        "exp": now() + 60,
+    })
+}
+
+/// Create an assertion signed with the signature "signature", which the mock verifier accepts.
+fn assertion(claims: Value) -> String {
+    let encode = |value: Value| BASE64_URL_SAFE_NO_PAD.encode(value.to_string());
+    format!(
+        "{}.{}.{}",
+        encode(json!({ "alg": "ES256", "kid": "partner" })),
+        encode(claims),
+        BASE64_URL_SAFE_NO_PAD.encode("signature")
+    )
+}
+
+fn jwt_bearer_request(assertion: String) -> TokenRequest {
+    TokenRequest {
+        client_secret: None,
+        grant_type: RequestedGrantType::JwtBearer { assertion },
+        redirect_uri: None,
+        scope: Some(vec!["read".to_string()]),
+        ..Default::default()
+    }
+}
+
+/// A test environment where the partner issuer is trusted, and its subject maps to the default resource owner.
+fn jwt_bearer_environment() -> TestEnvironment {
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.jwt_bearer_provider.expect_get_issuer_keys().with(eq(ISSUER)).returning(|_| {
+        let key: Jwk = serde_json::from_value(
+            json!({ "kty": "EC", "kid": "partner", "crv": "P-256", "x": "x", "y": "y" }),
+        )
+        .unwrap();
+        Ok(Some(vec![key]))
+    });
+    test.jwt_bearer_provider.expect_get_issuer_keys().returning(|_| Ok(None));
+    test.jws_verifier.expect_verify().returning(|alg, key, _, signature| {
+        alg == "ES256" && key.kid.as_deref() == Some("partner") && signature == b"signature"
+    });
+    test.jwt_bearer_provider
+        .expect_resolve_subject()
+        .with(always(), eq(ISSUER), eq(SUBJECT))
+        .returning(|_, _, _| Ok(Some(1)));
+    test.jwt_bearer_provider.expect_resolve_subject().returning(|_, _, _| Ok(None));
+    test
+}
+
+fn build(test: TestEnvironment) -> OAuthManager<u32, (), ()> {
+    let mut manager = test.build();
+    manager.config.authorization_server_identifier = Some("https://example.com".to_string());
+    manager
+}
+
+async fn assert_invalid_assertion(claims: Value, reason: &'static str) {
+    // Arrange
+    let mut test = jwt_bearer_environment();
+    test.token_provider.expect_token().never();
+    let manager = build(test);
+
+    // Act
+    let result = manager.handle_token(jwt_bearer_request(assertion(claims))).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    let error = result.unwrap_err();
+    assert_eq!(OAuthError::ValidationFailed(OAuthValidationError::InvalidAssertion(reason)), error);
+    assert_eq!(PublicOAuthError::InvalidGrant, PublicOAuthError::from(error));
+}
+
+#[test]
+fn test_jwt_bearer_request_requires_assertion() {
+    // Arrange
+    let request = request_from_raw_http(
+        r#"
+        POST /token HTTP/1.1
+        Content-Type: application/x-www-form-urlencoded
+
+        grant_type=urn%3Aietf%3Aparams%3Aoauth%3Agrant-type%3Ajwt-bearer&client_id=client
+    "#,
+    );
+
+    // Act
+    let result = TokenRequest::try_from(&request as &dyn FrontendRequest);
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(OAuthValidationError::MissingRequiredParameter("assertion"), result.unwrap_err());
+}
+
+#[tokio::test]
+async fn test_jwt_bearer_issues_token_for_mapped_subject() {
+    // Arrange
+    let mut test = jwt_bearer_environment();
+    test.token_provider
+        .expect_token()
+        .with(
+            always(),
+            eq(GrantType::JwtBearer {
+                resource_owner: 1,
+                scope: vec!["read".to_string()],
+                issuer: ISSUER.to_string(),
+            }),
+            always(),
+            always(),
+        )
+        .times(1)
+        .returning(|_, _, _, _| {
+            Ok(Token {
+                token: DEFAULT_TOKEN.to_string(),
+                refresh_token: None,
+                valid_until: Instant::now(),
+            })
+        });
+    let manager = build(test);
+
+    // Act
+    let result = manager.handle_token(jwt_bearer_request(assertion(assertion_claims()))).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    assert_eq!(Some("read".to_string()), result.unwrap().scope);
+}
+
+#[tokio::test]
+async fn test_jwt_bearer_rejects_untrusted_issuer() {
+    let mut claims = assertion_claims();
+    claims["iss"] = json!("https://attacker.example.com");
+    assert_invalid_assertion(claims, "the issuer is not trusted").await;
+}
+
+#[tokio::test]
+async fn test_jwt_bearer_rejects_other_audience() {
+    // The JWT MUST contain an "aud" (audience) claim containing a value that identifies the authorization server as an intended audience.
+    let mut claims = assertion_claims();
+    claims["aud"] = json!("https://other.example.com");
+    assert_invalid_assertion(claims, "the audience does not identify this authorization server")
+        .await;
+}
+
+#[tokio::test]
+async fn test_jwt_bearer_rejects_expired_assertion() {
+    // The authorization server MUST reject any JWT with an expiration time that has passed.
+    let mut claims = assertion_claims();
+    claims["exp"] = json!(now() - 1);
+    assert_invalid_assertion(claims, "the assertion has expired").await;
+}
+
+#[tokio::test]
+async fn test_jwt_bearer_rejects_unknown_subject() {
+    let mut claims = assertion_claims();
+    claims["sub"] = json!("unknown_user");
+    assert_invalid_assertion(claims, "the subject is unknown").await;
+}
+
+#[tokio::test]
+async fn test_jwt_bearer_rejects_invalid_signature() {
+    // Arrange
+    let mut test = jwt_bearer_environment();
+    test.token_provider.expect_token().never();
+    let manager = build(test);
+    let forged = format!(
+        "{}.{}",
+        assertion(assertion_claims()).rsplit_once('.').unwrap().0,
+        BASE64_URL_SAFE_NO_PAD.encode("forged")
+    );
+
+    // Act
+    let result = manager.handle_token(jwt_bearer_request(forged)).await;
+
+    // Assert
+    assert_eq!(
+        Err(OAuthError::ValidationFailed(OAuthValidationError::InvalidAssertion(
+            "the signature is invalid"
+        ))),
+        result.map(|_| ())
+    );
+}
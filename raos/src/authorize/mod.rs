@@ -5,11 +5,12 @@ pub use validate::*;
 
 use crate::{
     common::{
-        frontend::{FrontendRequest, OAuthError},
-        model::Grant,
+        frontend::{FrontendRequest, OAuthError, OAuthValidationError},
+        model::{Client, Grant, Prompt, OPENID_SCOPE},
     },
     manager::OAuthManager,
 };
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
 use std::mem::take;
 
 mod provider;
@@ -95,7 +96,11 @@ impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
     ///     code_challenge: CodeChallenge::Plain {code_challenge: "CODE_CHALLENGE".to_string()},
     ///     redirect_uri: Some("https://example.com".to_string()),
     ///     scope: Some("SCOPE".to_string()),
+    ///     resource: Vec::new(),
     ///     state: Some("STATE".to_string()),
+    ///     dpop_jkt: None,
+    ///     request_uri: None,
+    ///     openid: Default::default(),
     /// };
     ///
     /// # tokio_test::block_on(async {
@@ -137,39 +142,32 @@ impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
         validated: &mut ValidatedAuthorizationRequest,
         mut extras: Option<Ex>,
     ) -> Result<String, OAuthError<E>> {
+        // With prompt=none, the resource owner may not be asked to authenticate or consent
+        let openid = validated.scopes.iter().any(|scope| scope == OPENID_SCOPE);
+        let allow_interaction = !(openid && validated.openid.prompt.contains(&Prompt::None));
+
         // Authorize the grant, by checking in with the authorization provider
-        let authorization_result = self
-            .authorization_provider
-            .authorize_grant(&validated.client, &validated.scopes, &mut extras)
-            .await
-            .map_err(OAuthError::ProviderImplementationError)?;
+        let owner_id = self
+            .authorize_owner(&validated.client, &validated.scopes, allow_interaction, &mut extras)
+            .await?;
 
-        let owner_id = match authorization_result {
-            // If we are authorized, continue normally
-            GrantAuthorizationResult::Authorized(owner_id) => owner_id,
-            // If we require authentication, we need to return a response to the client.
-            // We ask the authorization provider to make a response for us, which we then return.
-            GrantAuthorizationResult::RequireAuthentication => {
-                let response = self
-                    .authorization_provider
-                    .handle_required_authentication(&mut extras)
-                    .await
-                    .map_err(OAuthError::ProviderImplementationError)?;
-                return Err(OAuthError::RequiresResourceOwnerInteraction(response));
+        // OpenID Connect requests need to know when the resource owner authenticated, which may be too long ago
+        let mut auth_time = None;
+        if openid {
+            auth_time = self
+                .authorization_provider
+                .authentication_time(&owner_id, &mut extras)
+                .await
+                .map_err(OAuthError::ProviderImplementationError)?;
+            if let Some(max_age) = validated.openid.max_age {
+                let recent = auth_time.is_some_and(|auth_time| {
+                    auth_time.elapsed().map_or(true, |elapsed| elapsed.as_secs() <= max_age)
+                });
+                if !recent {
+                    return Err(self.require_authentication(allow_interaction, &mut extras).await);
+                }
             }
-            // If we require scope consent, we need to return a response to the client.
-            // We ask the authorization provider to make a response for us, which we then return.
-            GrantAuthorizationResult::RequireScopeConsent(scope) => {
-                let response = self
-                    .authorization_provider
-                    .handle_missing_scope_consent(scope, &mut extras)
-                    .await
-                    .map_err(OAuthError::ProviderImplementationError)?;
-                return Err(OAuthError::RequiresResourceOwnerInteraction(response));
-            }
-            // If we are unauthorized, return an access denied error
-            GrantAuthorizationResult::Unauthorized => return Err(OAuthError::AccessDenied),
-        };
+        }
 
         // Create a grant from the validated request and the owner id from the granted request
         let grant = Grant {
@@ -179,7 +177,12 @@ impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
             // The remaining values are taken from the validated request
             client_id: take(&mut validated.client.client_id),
             scope: take(&mut validated.scopes),
+            resource: take(&mut validated.resource),
             code_challenge: validated.code_challenge.take(),
+            code_id: thread_rng().sample_iter(&Alphanumeric).take(32).map(char::from).collect(),
+            dpop_jkt: validated.dpop_jkt.take(),
+            openid: take(&mut validated.openid),
+            auth_time,
         };
 
         // After validation, exchange our grant for an authorization code that can later be exchanged
@@ -192,4 +195,63 @@ impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
 
         Ok(code)
     }
+
+    /// Ask the authorization provider to authorize a grant for the given client and scopes,
+    /// returning the resource owner that authorized it.
+    /// If interaction is not allowed, requests that need authentication or consent fail instead.
+    pub(crate) async fn authorize_owner(
+        &self,
+        client: &Client,
+        scopes: &[String],
+        allow_interaction: bool,
+        extras: &mut Option<Ex>,
+    ) -> Result<U, OAuthError<E>> {
+        let authorization_result = self
+            .authorization_provider
+            .authorize_grant(client, scopes, extras)
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?;
+
+        match authorization_result {
+            // If we are authorized, continue normally
+            GrantAuthorizationResult::Authorized(owner_id) => Ok(owner_id),
+            // If we require authentication, we need to return a response to the client.
+            GrantAuthorizationResult::RequireAuthentication => {
+                Err(self.require_authentication(allow_interaction, extras).await)
+            }
+            // If we require scope consent, but may not ask for it, the client is told consent is required
+            GrantAuthorizationResult::RequireScopeConsent(_) if !allow_interaction => {
+                Err(OAuthValidationError::ConsentRequired.into())
+            }
+            // If we require scope consent, we need to return a response to the client.
+            // We ask the authorization provider to make a response for us, which we then return.
+            GrantAuthorizationResult::RequireScopeConsent(scope) => {
+                let response = self
+                    .authorization_provider
+                    .handle_missing_scope_consent(scope, extras)
+                    .await
+                    .map_err(OAuthError::ProviderImplementationError)?;
+                Err(OAuthError::RequiresResourceOwnerInteraction(response))
+            }
+            // If we are unauthorized, return an access denied error
+            GrantAuthorizationResult::Unauthorized => Err(OAuthError::AccessDenied),
+        }
+    }
+
+    /// Build the error returned when the resource owner needs to authenticate.
+    /// We ask the authorization provider to make a response for us, unless interaction is not allowed.
+    async fn require_authentication(
+        &self,
+        allow_interaction: bool,
+        extras: &mut Option<Ex>,
+    ) -> OAuthError<E> {
+        if !allow_interaction {
+            return OAuthValidationError::LoginRequired.into();
+        }
+
+        match self.authorization_provider.handle_required_authentication(extras).await {
+            Ok(response) => OAuthError::RequiresResourceOwnerInteraction(response),
+            Err(e) => OAuthError::ProviderImplementationError(e),
+        }
+    }
 }
@@ -3,6 +3,7 @@ use crate::common::{
     model::{Client, Grant},
 };
 use async_trait::async_trait;
+use std::time::SystemTime;
 
 /// Authorization provider trait
 /// This is one of the traits that has to be implemented by the end user, for the oauth manager to work.
@@ -75,6 +76,8 @@ pub trait AuthorizationProvider: 'static + Send + Sync {
     ///
     /// # Implementation notes
     /// This function should return the grant that was previously authorized by [authorize_grant](AuthorizationProvider::generate_code_for_grant).
+    /// Codes that were already exchanged MUST still be returned until they expire, so that a replayed code can be
+    /// detected through [mark_code_used](AuthorizationProvider::mark_code_used).
     ///
     /// # Arguments
     /// * `code` - The authorization code to exchange for a grant.
@@ -92,6 +95,51 @@ pub trait AuthorizationProvider: 'static + Send + Sync {
         code: String,
     ) -> Result<Option<Grant<Self::OwnerId>>, Self::Error>;
 
+    /// Mark an authorization code as used, so it can only be exchanged for a token once.
+    ///
+    /// # Implementation notes
+    /// Checking and marking the code MUST happen atomically, so that two concurrent requests cannot both use the same code.
+    /// When this function returns `false`, the library will revoke all tokens issued from the code through
+    /// [TokenProvider::revoke_code_tokens](crate::token::TokenProvider::revoke_code_tokens).
+    ///
+    /// # Arguments
+    /// * `code` - The authorization code that is being exchanged.
+    ///
+    /// # Returns
+    /// `true` if this is the first use of the code, or `false` if the code was already used.
+    ///
+    /// # Errors
+    /// If the authorization provider fails to mark the code, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError].
+    async fn mark_code_used(&self, code: &str) -> Result<bool, Self::Error>;
+
+    /// Get the time the resource owner last actively authenticated.
+    ///
+    /// # Implementation notes
+    /// This is only called for OpenID Connect authentication requests, after the grant was authorized.
+    /// The time is used to enforce the `max_age` parameter, and is included in the ID token as the `auth_time` claim.
+    ///
+    /// # Arguments
+    /// * `owner_id` - The resource owner that authorized the grant.
+    /// * `extras` - An optional parameter that can be passed down from the top-level functions. This can contain things like request information.
+    ///
+    /// # Returns
+    /// The time the resource owner authenticated, or None if it is unknown.
+    ///
+    /// # Errors
+    /// If the authorization provider fails to get the authentication time, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError].
+    ///
+    /// # Default implementation
+    /// The default implementation of this function returns None, so requests with a `max_age` always require authentication.
+    async fn authentication_time(
+        &self,
+        _owner_id: &Self::OwnerId,
+        _extras: &mut Option<Self::Extras>,
+    ) -> Result<Option<SystemTime>, Self::Error> {
+        Ok(None)
+    }
+
     /// Handle a required authentication.
     /// This function should return a response that can be sent to the client to prompt the resource owner to authenticate.
     /// This is used when the resource owner needs to authenticate before the grant can be authorized.
@@ -1,12 +1,12 @@
 use crate::common::{
     frontend::{FrontendRequest, FrontendRequestMethod, OAuthValidationError},
-    model::CodeChallenge,
+    model::{CodeChallenge, OpenIdParameters},
     syntax::{ValidateSyntax, CLIENT_ID_SYNTAX, STATE_SYNTAX},
     util::NoneIfEmpty,
 };
 
 /// The response type expected in an authorization request.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ResponseType {
     /// The client is requesting an authorization code.
     Code,
@@ -15,7 +15,7 @@ pub enum ResponseType {
 /// A parsed authorization request from a client.
 /// This struct contains all the information needed to authorize a client's request.
 /// This is produced by parsing a [FrontendRequest] from a client.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct AuthorizationRequest {
     /// The response type expected in the request.
     pub response_type: ResponseType,
@@ -27,8 +27,17 @@ pub struct AuthorizationRequest {
     pub redirect_uri: Option<String>,
     /// The scope of the request, space separated
     pub scope: Option<String>,
+    /// The resources the client wants to access, as described in RFC 8707.
+    pub resource: Vec<String>,
     /// The state of the request to be sent back to the client in the response.
     pub state: Option<String>,
+    /// The JWK thumbprint of the DPoP key the authorization code should be bound to.
+    pub dpop_jkt: Option<String>,
+    /// A reference to a pushed authorization request, as returned by the pushed authorization request endpoint.
+    /// If set, the other parameters are ignored and taken from the pushed request instead.
+    pub request_uri: Option<String>,
+    /// The OpenID Connect parameters of the request, like the `nonce` and `prompt`.
+    pub openid: OpenIdParameters,
 }
 
 impl TryFrom<&dyn FrontendRequest> for AuthorizationRequest {
@@ -55,21 +64,60 @@ impl TryFrom<&dyn FrontendRequest> for AuthorizationRequest {
                 }
             })
         };
-
-        // Get the response type, client ID, and code challenge method from the request
-        let response_type = match param("response_type") {
-            Some(str) => str.try_into()?,
-            None => return Err(OAuthValidationError::MissingRequiredParameter("response_type")),
+        let param_values = |key| {
+            let values = request.query_param_values(key);
+            if values.is_empty() && matches!(request.request_method(), FrontendRequestMethod::POST)
+            {
+                request.body_param_values(key)
+            } else {
+                values
+            }
         };
+
         let Some(client_id) = param("client_id") else {
             return Err(OAuthValidationError::MissingRequiredParameter("client_id"));
         };
         client_id.validate_syntax("client_id", &CLIENT_ID_SYNTAX)?;
+
+        // A pushed authorization request only needs the client id, the rest is resolved during validation
+        if let Some(request_uri) = param("request_uri") {
+            return Ok(Self {
+                response_type: ResponseType::Code,
+                client_id,
+                code_challenge: CodeChallenge::None,
+                redirect_uri: None,
+                scope: None,
+                resource: Vec::new(),
+                state: None,
+                dpop_jkt: None,
+                request_uri: Some(request_uri),
+                openid: OpenIdParameters::default(),
+            });
+        }
+
+        Self::from_params(client_id, param, param_values)
+    }
+}
+
+impl AuthorizationRequest {
+    /// Parse the authorization parameters other than the client id, using the given parameter lookups
+    /// for single and repeated parameters.
+    pub(crate) fn from_params(
+        client_id: String,
+        param: impl Fn(&'static str) -> Option<String>,
+        param_values: impl Fn(&'static str) -> Vec<String>,
+    ) -> Result<Self, OAuthValidationError> {
+        // Get the response type and code challenge method from the request
+        let response_type = match param("response_type") {
+            Some(str) => str.try_into()?,
+            None => return Err(OAuthValidationError::MissingRequiredParameter("response_type")),
+        };
         let code_challenge =
             (param("code_challenge"), param("code_challenge_method")).try_into()?;
 
         let state = param("state");
         state.validate_syntax("state", &STATE_SYNTAX)?;
+        let openid = OpenIdParameters::from_params(&param)?;
 
         // Return the authorization request
         Ok(Self {
@@ -79,10 +127,24 @@ impl TryFrom<&dyn FrontendRequest> for AuthorizationRequest {
             state,
             redirect_uri: param("redirect_uri"),
             scope: param("scope"),
+            // We should treat empty values as if they were omitted from the request
+            resource: param_values("resource").into_iter().filter(|r| !r.is_empty()).collect(),
+            dpop_jkt: param("dpop_jkt"),
+            request_uri: None,
+            openid,
         })
     }
 }
 
+impl ResponseType {
+    /// The value of the `response_type` parameter this response type is requested with.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResponseType::Code => "code",
+        }
+    }
+}
+
 impl TryFrom<String> for ResponseType {
     type Error = OAuthValidationError;
 
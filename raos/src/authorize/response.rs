@@ -32,7 +32,7 @@ impl FrontendResponseExt for AuthorizationResponse {
     fn into_frontend_response(self) -> FrontendResponse {
         // Serialize the struct into a query string
         let Ok(url_params) = serde_urlencoded::to_string(&self) else {
-            return FrontendResponse::Error { error: PublicOAuthError::ServerError.into() };
+            return PublicOAuthError::ServerError.into();
         };
 
         // Append the query string to the redirect URI
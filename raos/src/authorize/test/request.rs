@@ -145,3 +145,25 @@ fn test_authorization_request_with_invalid_or_missing_response_type() {
         result_invalid.unwrap_err()
     );
 }
+
+#[test]
+fn test_authorization_request_accepts_multiple_resources() {
+    // The client MAY indicate multiple protected resources by including multiple resource parameters in the request.
+
+    // Arrange
+    let request = request_from_raw_http(
+        r#"
+            GET /authorize?client_id=1234&response_type=code&resource=https%3A%2F%2Fone.example.com&resource=https%3A%2F%2Ftwo.example.com HTTP/1.1
+        "#,
+    );
+
+    // Act
+    let result = AuthorizationRequest::try_from(&request as &dyn FrontendRequest);
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    assert_eq!(
+        vec!["https://one.example.com".to_string(), "https://two.example.com".to_string()],
+        result.unwrap().resource
+    );
+}
@@ -1,7 +1,7 @@
 use crate::{
     authorize::AuthorizationRequest,
     common::{
-        frontend::{OAuthError, OAuthValidationError},
+        frontend::{OAuthError, OAuthValidationError, PublicOAuthError},
         model::{Client, CodeChallenge},
     },
     test::{TestEnvironment, DEFAULT_CLIENT_ID, DEFAULT_CLIENT_SECRET, DEFAULT_REDIRECT_URI},
@@ -306,3 +306,139 @@ async fn test_code_challenge_required_for_public_clients() {
         result.unwrap_err()
     );
 }
+
+#[tokio::test]
+async fn test_response_type_must_be_registered_for_client() {
+    // The client is not authorized to request an authorization code using this method: unauthorized_client.
+
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.register_client(
+        Client { response_types: Vec::new(), ..Default::default() },
+        DEFAULT_CLIENT_SECRET.to_string(),
+    );
+    let manager = test.build();
+
+    // Act
+    let result = manager.validate_authorization_request(AuthorizationRequest::default()).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::ClientNotAllowedToUseResponseType {
+            requested: "code"
+        }),
+        result.unwrap_err()
+    );
+}
+
+#[tokio::test]
+async fn test_default_scopes_used_without_scope() {
+    // If the client omits the scope parameter when requesting authorization,
+    // the authorization server MUST either process the request using a pre-defined default value or fail the request.
+
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.register_client(
+        Client { default_scopes: vec!["default".to_string()], ..Default::default() },
+        DEFAULT_CLIENT_SECRET.to_string(),
+    );
+    let manager = test.build();
+
+    let request = AuthorizationRequest { scope: None, ..Default::default() };
+
+    // Act
+    let result = manager.validate_authorization_request(request).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    assert_eq!(vec!["default".to_string()], result.unwrap().scopes);
+}
+
+#[tokio::test]
+async fn test_loopback_redirect_uri_keeps_requested_port() {
+    // Native apps that are able to open a port on the loopback network interface without needing special permissions
+    // can use the loopback interface to receive the OAuth redirect.
+
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.register_client(
+        Client {
+            redirect_uris: vec!["http://127.0.0.1/callback".to_string()],
+            ..Default::default()
+        },
+        DEFAULT_CLIENT_SECRET.to_string(),
+    );
+    let manager = test.build();
+
+    let request = AuthorizationRequest {
+        redirect_uri: Some("http://127.0.0.1:51004/callback".to_string()),
+        ..Default::default()
+    };
+
+    // Act
+    let result = manager.validate_authorization_request(request).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    assert_eq!("http://127.0.0.1:51004/callback", result.unwrap().redirect_uri.to_string());
+}
+
+#[tokio::test]
+async fn test_resource_must_be_absolute_uri_without_fragment() {
+    // Its value MUST be an absolute URI, as specified by Section 4.3 of RFC3986, which MAY include a query component,
+    // but MUST NOT include a fragment component.
+
+    for resource in ["/api", "https://api.example.com#fragment"] {
+        // Arrange
+        let mut test = TestEnvironment::new();
+        test.default_client();
+        test.client_provider.expect_allow_client_resources().never();
+        let manager = test.build();
+
+        let request =
+            AuthorizationRequest { resource: vec![resource.to_string()], ..Default::default() };
+
+        // Act
+        let result = manager.validate_authorization_request(request).await;
+
+        // Assert
+        assert!(result.is_err(), "result is not Err, result is {:?}", result);
+        assert_eq!(PublicOAuthError::InvalidTarget, PublicOAuthError::from(result.unwrap_err()));
+    }
+}
+
+#[tokio::test]
+async fn test_resource_must_be_allowed_for_client() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.client_provider
+        .expect_allow_client_resources()
+        .returning(|_, resources| Ok(resources == ["https://api.example.com"]));
+    let manager = test.build();
+
+    let allowed = AuthorizationRequest {
+        resource: vec!["https://api.example.com".to_string()],
+        ..Default::default()
+    };
+    let disallowed = AuthorizationRequest {
+        resource: vec!["https://admin.example.com".to_string()],
+        ..Default::default()
+    };
+
+    // Act
+    let allowed = manager.validate_authorization_request(allowed).await;
+    let disallowed = manager.validate_authorization_request(disallowed).await;
+
+    // Assert
+    assert!(allowed.is_ok(), "result is not Ok, result is {:?}", allowed);
+    assert_eq!(vec!["https://api.example.com".to_string()], allowed.unwrap().resource);
+    assert!(disallowed.is_err(), "result is not Err, result is {:?}", disallowed);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::InvalidTarget(
+            "not allowed for the client"
+        )),
+        disallowed.unwrap_err()
+    );
+}
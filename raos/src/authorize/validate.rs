@@ -2,7 +2,7 @@ use url::Url;
 
 use crate::common::frontend::{OAuthError, OAuthValidationError};
 use crate::common::model::Client;
-use crate::common::model::CodeChallenge;
+use crate::common::model::{CodeChallenge, OpenIdParameters};
 use crate::{
     authorize::{AuthorizationRequest, ResponseType},
     manager::OAuthManager,
@@ -23,11 +23,17 @@ pub struct ValidatedAuthorizationRequest {
     pub redirect_uri: Url,
     /// The scopes requested by the client, after being filtered by the [ClientProvider](crate::common::ClientProvider).
     pub scopes: Vec<String>,
+    /// The resources the client wants to access, after being checked by the [ClientProvider](crate::common::ClientProvider).
+    pub resource: Vec<String>,
     /// The state of the request to be sent back to the client in the response.
     pub state: Option<String>,
+    /// The JWK thumbprint of the DPoP key the authorization code should be bound to.
+    pub dpop_jkt: Option<String>,
+    /// The OpenID Connect parameters of the request, like the `nonce` and `prompt`.
+    pub openid: OpenIdParameters,
 }
 
-impl<U: 'static, E: 'static, Ex> OAuthManager<U, E, Ex> {
+impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
     /// Validate an incoming authorization request from a client.
     /// This function will validate the incoming request, and then return a [ValidatedAuthorizationRequest]
     /// that contains the information needed to authorize the request.
@@ -57,7 +63,11 @@ impl<U: 'static, E: 'static, Ex> OAuthManager<U, E, Ex> {
     ///     code_challenge: CodeChallenge::Plain { code_challenge: "CODE_CHALLENGE".to_string() },
     ///     redirect_uri: Some("https://example.com".to_string()),
     ///     scope: Some("SCOPE".to_string()),
+    ///     resource: Vec::new(),
     ///     state: Some("STATE".to_string()),
+    ///     dpop_jkt: None,
+    ///     request_uri: None,
+    ///     openid: Default::default(),
     /// };
     ///
     /// # tokio_test::block_on(async {
@@ -68,6 +78,15 @@ impl<U: 'static, E: 'static, Ex> OAuthManager<U, E, Ex> {
     pub async fn validate_authorization_request(
         &self,
         req: AuthorizationRequest,
+    ) -> Result<ValidatedAuthorizationRequest, OAuthError<E>> {
+        let req = self.resolve_pushed_authorization_request(req).await?;
+        self.validate_authorization_parameters(req).await
+    }
+
+    /// Validate the authorization parameters, without resolving or requiring a pushed authorization request.
+    pub(crate) async fn validate_authorization_parameters(
+        &self,
+        req: AuthorizationRequest,
     ) -> Result<ValidatedAuthorizationRequest, OAuthError<E>> {
         let Some(client) = self
             .client_provider
@@ -80,6 +99,12 @@ impl<U: 'static, E: 'static, Ex> OAuthManager<U, E, Ex> {
         if !client.is_valid() {
             return Err(OAuthValidationError::InvalidClient.into());
         }
+        if !client.has_response_type(req.response_type.as_str()) {
+            return Err(OAuthValidationError::ClientNotAllowedToUseResponseType {
+                requested: req.response_type.as_str(),
+            }
+            .into());
+        }
 
         if matches!(req.code_challenge, CodeChallenge::None)
             && self.config.require_code_challenge.require_code_challenge(&client)
@@ -92,12 +117,9 @@ impl<U: 'static, E: 'static, Ex> OAuthManager<U, E, Ex> {
             return Err(OAuthValidationError::CodeChallengeRequired.into());
         }
 
+        let requested = req.redirect_uri.is_some();
         let redirect_uri = if let Some(redirect_uri) = req.redirect_uri {
-            if client.has_redirect_uri(&redirect_uri) {
-                redirect_uri
-            } else {
-                return Err(OAuthValidationError::UnknownRedirectUri.into());
-            }
+            redirect_uri
         } else if client.redirect_uris.len() == 1 {
             client
                 .redirect_uris
@@ -112,11 +134,15 @@ impl<U: 'static, E: 'static, Ex> OAuthManager<U, E, Ex> {
         if redirect_uri.fragment().is_some() {
             return Err(OAuthValidationError::InvalidRedirectUri.into());
         }
+        // The requested redirect uri is used as is, as loopback redirect uris may use a different port than registered
+        if requested && !client.has_redirect_uri(redirect_uri.as_str()) {
+            return Err(OAuthValidationError::UnknownRedirectUri.into());
+        }
 
         let scopes = if let Some(scope) = req.scope {
             scope.split(' ').map(str::to_string).collect()
         } else {
-            Vec::new()
+            client.default_scopes.clone()
         };
         let scopes = self
             .client_provider
@@ -126,14 +152,18 @@ impl<U: 'static, E: 'static, Ex> OAuthManager<U, E, Ex> {
         if scopes.is_empty() {
             return Err(OAuthValidationError::NoScopesProvided.into());
         }
+        self.validate_resources(&client, &req.resource).await?;
 
         Ok(ValidatedAuthorizationRequest {
             client,
             redirect_uri,
             scopes,
+            resource: req.resource,
             response_type: req.response_type,
             code_challenge: req.code_challenge,
             state: req.state,
+            dpop_jkt: req.dpop_jkt,
+            openid: req.openid,
         })
     }
 }
@@ -1,36 +1,92 @@
+use std::{collections::HashMap, time::Duration};
+
 use crate::{
+    assertion::JwtBearerProvider,
     authorize::AuthorizationProvider,
-    common::model::ClientProvider,
-    manager::{CodeChallengeRequirement, OAuthConfig, OAuthManager},
-    token::TokenProvider,
+    common::{jose::JwsVerifier, model::ClientProvider},
+    device::DeviceAuthorizationProvider,
+    dpop::DPoPProvider,
+    exchange::TokenExchangeProvider,
+    manager::{
+        CodeChallengeRequirement, OAuthConfig, OAuthManager, PushedAuthorizationRequirement,
+    },
+    metadata::Endpoint,
+    par::PushedAuthorizationProvider,
+    registration::ClientRegistrationProvider,
+    token::{GrantTypeHandler, TokenProvider},
 };
+#[cfg(feature = "oidc")]
+use crate::{jwt::SigningKeySource, oidc::UserInfoProvider};
 
 /// The OAuthManagerBuilder is used to build an [OAuthManager].
 /// This builder is used to configure the OAuthManager with the necessary providers,
 /// and to set the configuration options.
-pub struct OAuthManagerBuilder<C, A, T> {
+///
+/// The `O` and `E` type parameters are the owner id and error types shared by all providers,
+/// these are inferred from the configured providers and do not need to be specified.
+pub struct OAuthManagerBuilder<C, A, T, O, E> {
     client_provider: Option<C>,
     authorization_provider: Option<A>,
     token_provider: Option<T>,
+    optional_providers: OptionalProviders<O, E>,
     config: OAuthConfig,
 }
 
-impl OAuthManagerBuilder<NeedsClientProvider, NeedsAuthorizationProvider, NeedsTokenProvider> {
+/// The providers that are not required to build an [OAuthManager], but enable additional flows.
+struct OptionalProviders<O, E> {
+    device_authorization_provider:
+        Option<Box<dyn DeviceAuthorizationProvider<OwnerId = O, Error = E>>>,
+    pushed_authorization_provider: Option<Box<dyn PushedAuthorizationProvider<Error = E>>>,
+    dpop_provider: Option<Box<dyn DPoPProvider<Error = E>>>,
+    jws_verifier: Option<Box<dyn JwsVerifier>>,
+    client_registration_provider: Option<Box<dyn ClientRegistrationProvider<Error = E>>>,
+    token_exchange_provider: Option<Box<dyn TokenExchangeProvider<OwnerId = O, Error = E>>>,
+    jwt_bearer_provider: Option<Box<dyn JwtBearerProvider<OwnerId = O, Error = E>>>,
+    grant_type_handlers: HashMap<String, Box<dyn GrantTypeHandler<OwnerId = O, Error = E>>>,
+    #[cfg(feature = "oidc")]
+    user_info_provider: Option<Box<dyn UserInfoProvider<OwnerId = O, Error = E>>>,
+    #[cfg(feature = "oidc")]
+    signing_key_source: Option<Box<dyn SigningKeySource<Error = E>>>,
+}
+
+impl<O, E> Default for OptionalProviders<O, E> {
+    fn default() -> Self {
+        Self {
+            device_authorization_provider: None,
+            pushed_authorization_provider: None,
+            dpop_provider: None,
+            jws_verifier: None,
+            client_registration_provider: None,
+            token_exchange_provider: None,
+            jwt_bearer_provider: None,
+            grant_type_handlers: HashMap::new(),
+            #[cfg(feature = "oidc")]
+            user_info_provider: None,
+            #[cfg(feature = "oidc")]
+            signing_key_source: None,
+        }
+    }
+}
+
+impl<O, E>
+    OAuthManagerBuilder<NeedsClientProvider, NeedsAuthorizationProvider, NeedsTokenProvider, O, E>
+{
     pub(crate) fn new() -> Self {
         Self {
             client_provider: None,
             authorization_provider: None,
             token_provider: None,
+            optional_providers: OptionalProviders::default(),
             config: OAuthConfig::default(),
         }
     }
 }
 
-impl<A, T> OAuthManagerBuilder<NeedsClientProvider, A, T> {
+impl<A, T, O, E> OAuthManagerBuilder<NeedsClientProvider, A, T, O, E> {
     /// Set the client provider for the OAuthManager.
     /// The client provider is used to get information about clients that are making requests.
     /// See [ClientProvider] for more information.
-    pub fn client_provider<C>(self, client_provider: C) -> OAuthManagerBuilder<C, A, T>
+    pub fn client_provider<C>(self, client_provider: C) -> OAuthManagerBuilder<C, A, T, O, E>
     where
         C: ClientProvider,
     {
@@ -38,19 +94,20 @@ impl<A, T> OAuthManagerBuilder<NeedsClientProvider, A, T> {
             client_provider: Some(client_provider),
             authorization_provider: self.authorization_provider,
             token_provider: self.token_provider,
+            optional_providers: self.optional_providers,
             config: self.config,
         }
     }
 }
 
-impl<C, T> OAuthManagerBuilder<C, NeedsAuthorizationProvider, T> {
+impl<C, T, O, E> OAuthManagerBuilder<C, NeedsAuthorizationProvider, T, O, E> {
     /// Set the authorization provider for the OAuthManager.
     /// The authorization provider is used to authorize grants and exchange codes for grants.
     /// See [AuthorizationProvider] for more information.
     pub fn authorization_provider<A>(
         self,
         authorization_provider: A,
-    ) -> OAuthManagerBuilder<C, A, T>
+    ) -> OAuthManagerBuilder<C, A, T, O, E>
     where
         A: AuthorizationProvider,
     {
@@ -58,16 +115,17 @@ impl<C, T> OAuthManagerBuilder<C, NeedsAuthorizationProvider, T> {
             authorization_provider: Some(authorization_provider),
             client_provider: self.client_provider,
             token_provider: self.token_provider,
+            optional_providers: self.optional_providers,
             config: self.config,
         }
     }
 }
 
-impl<C, A> OAuthManagerBuilder<C, A, NeedsTokenProvider> {
+impl<C, A, O, E> OAuthManagerBuilder<C, A, NeedsTokenProvider, O, E> {
     /// Set the token provider for the OAuthManager.
     /// The token provider is used to generate and validate tokens and refresh tokens.
     /// See [TokenProvider] for more information.
-    pub fn token_provider<T>(self, token_provider: T) -> OAuthManagerBuilder<C, A, T>
+    pub fn token_provider<T>(self, token_provider: T) -> OAuthManagerBuilder<C, A, T, O, E>
     where
         T: TokenProvider,
     {
@@ -75,12 +133,126 @@ impl<C, A> OAuthManagerBuilder<C, A, NeedsTokenProvider> {
             token_provider: Some(token_provider),
             client_provider: self.client_provider,
             authorization_provider: self.authorization_provider,
+            optional_providers: self.optional_providers,
             config: self.config,
         }
     }
 }
 
-impl<C, A, T> OAuthManagerBuilder<C, A, T> {
+impl<C, A, T, O, E> OAuthManagerBuilder<C, A, T, O, E> {
+    /// Set the device authorization provider for the OAuthManager.
+    /// The device authorization provider is used to store and poll device codes,
+    /// and enables the device authorization grant.
+    /// See [DeviceAuthorizationProvider] for more information.
+    pub fn device_authorization_provider<D>(mut self, device_authorization_provider: D) -> Self
+    where
+        D: DeviceAuthorizationProvider<OwnerId = O, Error = E>,
+    {
+        self.optional_providers.device_authorization_provider =
+            Some(Box::new(device_authorization_provider));
+        self
+    }
+
+    /// Set the pushed authorization provider for the OAuthManager.
+    /// The pushed authorization provider is used to store authorization requests pushed by clients,
+    /// and enables the pushed authorization request endpoint.
+    /// See [PushedAuthorizationProvider] for more information.
+    pub fn pushed_authorization_provider<P>(mut self, pushed_authorization_provider: P) -> Self
+    where
+        P: PushedAuthorizationProvider<Error = E>,
+    {
+        self.optional_providers.pushed_authorization_provider =
+            Some(Box::new(pushed_authorization_provider));
+        self
+    }
+
+    /// Set the DPoP provider for the OAuthManager.
+    /// The DPoP provider is used to prevent DPoP proofs from being replayed, and enables DPoP sender-constrained tokens.
+    /// Verifying proofs also requires a [JwsVerifier] to be set through [jws_verifier](Self::jws_verifier).
+    /// See [DPoPProvider] for more information.
+    pub fn dpop_provider<D>(mut self, dpop_provider: D) -> Self
+    where
+        D: DPoPProvider<Error = E>,
+    {
+        self.optional_providers.dpop_provider = Some(Box::new(dpop_provider));
+        self
+    }
+
+    /// Set the JWS verifier for the OAuthManager.
+    /// The JWS verifier is used to check the signatures of JWTs presented by clients, like DPoP proofs.
+    /// See [JwsVerifier] for more information.
+    pub fn jws_verifier<V>(mut self, jws_verifier: V) -> Self
+    where
+        V: JwsVerifier,
+    {
+        self.optional_providers.jws_verifier = Some(Box::new(jws_verifier));
+        self
+    }
+
+    /// Set the client registration provider for the OAuthManager.
+    /// The client registration provider is used to store dynamically registered clients,
+    /// and enables the client registration endpoint.
+    /// See [ClientRegistrationProvider] for more information.
+    pub fn client_registration_provider<R>(mut self, client_registration_provider: R) -> Self
+    where
+        R: ClientRegistrationProvider<Error = E>,
+    {
+        self.optional_providers.client_registration_provider =
+            Some(Box::new(client_registration_provider));
+        self
+    }
+
+    /// Set the token exchange provider for the OAuthManager.
+    /// The token exchange provider is used to validate the tokens presented by clients and authorize the exchange,
+    /// and enables the token exchange grant.
+    /// See [TokenExchangeProvider] for more information.
+    pub fn token_exchange_provider<X>(mut self, token_exchange_provider: X) -> Self
+    where
+        X: TokenExchangeProvider<OwnerId = O, Error = E>,
+    {
+        self.optional_providers.token_exchange_provider = Some(Box::new(token_exchange_provider));
+        self
+    }
+
+    /// Set the JWT bearer provider for the OAuthManager.
+    /// The JWT bearer provider is used to look up the keys of trusted assertion issuers and map their subjects
+    /// to resource owners, and enables the JWT bearer authorization grant.
+    /// This requires a [JwsVerifier] to be configured through [jws_verifier](OAuthManagerBuilder::jws_verifier).
+    /// See [JwtBearerProvider] for more information.
+    pub fn jwt_bearer_provider<J>(mut self, jwt_bearer_provider: J) -> Self
+    where
+        J: JwtBearerProvider<OwnerId = O, Error = E>,
+    {
+        self.optional_providers.jwt_bearer_provider = Some(Box::new(jwt_bearer_provider));
+        self
+    }
+
+    /// Register a handler for a custom grant type, which is requested with the given grant type URI.
+    /// Registering a handler for a grant type that already has one replaces the previous handler.
+    /// See [GrantTypeHandler] for more information.
+    pub fn grant_type_handler<H>(mut self, grant_type: impl Into<String>, handler: H) -> Self
+    where
+        H: GrantTypeHandler<OwnerId = O, Error = E>,
+    {
+        self.optional_providers.grant_type_handlers.insert(grant_type.into(), Box::new(handler));
+        self
+    }
+
+    /// Enable OpenID Connect, so that an ID token is issued when the `openid` scope is granted.
+    /// The user info provider provides the claims about the resource owner, and the signing key source signs the ID tokens.
+    /// A [KeyManager](crate::jwt::KeyManager) can be used as signing key source, to rotate the signing keys.
+    /// See [UserInfoProvider] and [SigningKeySource] for more information.
+    #[cfg(feature = "oidc")]
+    pub fn openid_connect<P, K>(mut self, user_info_provider: P, signing_key_source: K) -> Self
+    where
+        P: UserInfoProvider<OwnerId = O, Error = E>,
+        K: SigningKeySource<Error = E>,
+    {
+        self.optional_providers.user_info_provider = Some(Box::new(user_info_provider));
+        self.optional_providers.signing_key_source = Some(Box::new(signing_key_source));
+        self
+    }
+
     /// Calling disallow_plain_code_challenge will disallow the use of plain code challenges.
     /// If this function is called, the code challenge must be a S256 challenge.
     /// This is a security measure to prevent code injection attacks.
@@ -89,14 +261,52 @@ impl<C, A, T> OAuthManagerBuilder<C, A, T> {
         self
     }
 
+    /// Calling enable_mutual_tls will enable mutual TLS client authentication and certificate-bound access tokens, as described in RFC 8705.
+    /// The frontend has to provide the certificate of the client through [FrontendRequest::peer_certificate](crate::common::frontend::FrontendRequest::peer_certificate).
+    /// Clients authenticate using the registration returned by [ClientProvider::get_tls_client_authentication].
+    pub fn enable_mutual_tls(mut self) -> Self {
+        self.config.mutual_tls = true;
+        self
+    }
+
     /// Set the authorization server identifier.
-    /// This is used to identify the authorization server in the authorization response.
-    /// This is an optional setting.
+    /// This is used to identify the authorization server in the authorization response,
+    /// and as the issuer in the [authorization server metadata](OAuthManager::authorization_server_metadata).
+    /// This is an optional setting, but required to serve the metadata document.
     pub fn set_authorization_server_identifier(mut self, identifier: String) -> Self {
         self.config.authorization_server_identifier = Some(identifier);
         self
     }
 
+    /// Set the url an endpoint is served on, as advertised in the authorization server metadata.
+    /// By default, endpoints are assumed to be served on their [default path](Endpoint::default_path)
+    /// relative to the authorization server identifier.
+    pub fn set_endpoint(mut self, endpoint: Endpoint, url: String) -> Self {
+        self.config.endpoints.insert(endpoint, url);
+        self
+    }
+
+    /// Set the minimum amount of time devices should wait between polling the token endpoint.
+    /// By default, this is 5 seconds.
+    pub fn set_device_code_interval(mut self, interval: Duration) -> Self {
+        self.config.device_code_interval = interval;
+        self
+    }
+
+    /// Set how far the issue time of a DPoP proof may differ from the current time before it is rejected.
+    /// By default, this is 60 seconds.
+    pub fn set_dpop_proof_lifetime(mut self, lifetime: Duration) -> Self {
+        self.config.dpop_proof_lifetime = lifetime;
+        self
+    }
+
+    /// Set how long ID tokens are valid after they are issued.
+    /// By default, this is 1 hour.
+    pub fn set_id_token_lifetime(mut self, lifetime: Duration) -> Self {
+        self.config.id_token_lifetime = lifetime;
+        self
+    }
+
     /// Set the code challenge requirement to always require a code challenge.
     /// By default, it is only required for public clients.
     pub fn code_challenge_always_required(mut self) -> Self {
@@ -104,6 +314,26 @@ impl<C, A, T> OAuthManagerBuilder<C, A, T> {
         self
     }
 
+    /// Require all clients to use pushed authorization requests.
+    /// Authorization requests without a `request_uri` will be rejected.
+    /// By default, pushed authorization requests are optional.
+    pub fn pushed_authorization_always_required(mut self) -> Self {
+        self.config.require_pushed_authorization = PushedAuthorizationRequirement::Always;
+        self
+    }
+
+    /// Require the given clients to use pushed authorization requests.
+    /// Authorization requests from these clients without a `request_uri` will be rejected.
+    /// By default, pushed authorization requests are optional.
+    pub fn pushed_authorization_required_for_clients(
+        mut self,
+        client_ids: impl IntoIterator<Item = String>,
+    ) -> Self {
+        self.config.require_pushed_authorization =
+            PushedAuthorizationRequirement::ForClients(client_ids.into_iter().collect());
+        self
+    }
+
     /// Set the code challenge requirement to never require a code challenge.
     /// This is intended for use-cases where the implementer takes responsibility for client authentication, like with a secured network or trusted source.
     /// By default, it is only required for public clients.
@@ -113,7 +343,7 @@ impl<C, A, T> OAuthManagerBuilder<C, A, T> {
     }
 }
 
-impl<C, A, T, O, E, Ex> OAuthManagerBuilder<C, A, T>
+impl<C, A, T, O, E, Ex> OAuthManagerBuilder<C, A, T, O, E>
 where
     C: ClientProvider<Error = E>,
     A: AuthorizationProvider<OwnerId = O, Error = E, Extras = Ex>,
@@ -126,6 +356,18 @@ where
             client_provider: Box::new(self.client_provider.unwrap()),
             authorization_provider: Box::new(self.authorization_provider.unwrap()),
             token_provider: Box::new(self.token_provider.unwrap()),
+            device_authorization_provider: self.optional_providers.device_authorization_provider,
+            pushed_authorization_provider: self.optional_providers.pushed_authorization_provider,
+            dpop_provider: self.optional_providers.dpop_provider,
+            jws_verifier: self.optional_providers.jws_verifier,
+            client_registration_provider: self.optional_providers.client_registration_provider,
+            token_exchange_provider: self.optional_providers.token_exchange_provider,
+            jwt_bearer_provider: self.optional_providers.jwt_bearer_provider,
+            grant_type_handlers: self.optional_providers.grant_type_handlers,
+            #[cfg(feature = "oidc")]
+            user_info_provider: self.optional_providers.user_info_provider,
+            #[cfg(feature = "oidc")]
+            signing_key_source: self.optional_providers.signing_key_source,
             config: self.config,
         }
     }
@@ -0,0 +1,455 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    common::{
+        frontend::{FrontendRequest, OAuthError, OAuthValidationError},
+        jose::{hmac::verify_hmac, CompactJws},
+        model::{Client, ClientAssertionKeys, TlsClientAuthentication, TokenEndpointAuthMethod},
+        syntax::{ValidateSyntax, CLIENT_ID_SYNTAX},
+        util::NoneIfEmpty,
+        x509::ClientCertificate,
+    },
+    manager::OAuthManager,
+    metadata::Endpoint,
+};
+
+/// The client assertion type used to authenticate with a JWT, as described in RFC 7523.
+pub(crate) const JWT_BEARER_CLIENT_ASSERTION_TYPE: &str =
+    "urn:ietf:params:oauth:client-assertion-type:jwt-bearer";
+
+/// The credentials a client sent along with a request.
+pub(crate) struct ClientCredentials {
+    pub(crate) client_id: String,
+    pub(crate) client_secret: Option<String>,
+    pub(crate) client_secret_basic: bool,
+    pub(crate) client_assertion: Option<String>,
+    pub(crate) client_certificate: Option<Vec<u8>>,
+}
+
+/// Read the client credentials from a request, either through HTTP Basic authentication, the request body or a client assertion.
+/// Returns the client id and the client secret or assertion if one was provided, along with the TLS client certificate.
+pub(crate) fn client_credentials_from_request(
+    request: &dyn FrontendRequest,
+) -> Result<ClientCredentials, OAuthValidationError> {
+    // We should treat empty values as if they were omitted from the request
+    let body_param = |key| request.body_param(key).none_if_empty();
+
+    let header_credentials = get_credentials_from_header(request)?;
+    let client_assertion = get_client_assertion_from_body(request)?;
+    if client_assertion.is_some()
+        && (header_credentials.is_some() || body_param("client_secret").is_some())
+    {
+        return Err(OAuthValidationError::MultipleClientAuthenticationMethods);
+    }
+
+    let client_secret_basic = header_credentials.is_some();
+    let (mut client_id, mut client_secret) = header_credentials.unzip();
+    if let Some(body_client_id) = body_param("client_id") {
+        if let Some(header_client_id) = client_id {
+            if body_client_id != header_client_id {
+                return Err(OAuthValidationError::InvalidBasicAuthentication(Box::new(
+                    OAuthValidationError::MismatchedClientCredentials,
+                )));
+            }
+        }
+
+        client_id = Some(body_client_id);
+    };
+    if let Some(body_client_secret) = body_param("client_secret") {
+        if client_secret.is_none() {
+            client_secret = Some(body_client_secret);
+        }
+    };
+
+    // The client_id is optional when using a client assertion, as the subject of the assertion identifies the client
+    if let Some((_, subject)) = &client_assertion {
+        if let Some(client_id) = &client_id {
+            if client_id != subject {
+                return Err(OAuthValidationError::MismatchedClientCredentials);
+            }
+        }
+        client_id = Some(subject.clone());
+    }
+
+    let Some(client_id) = client_id else {
+        return Err(OAuthValidationError::MissingRequiredParameter("client_id"));
+    };
+    client_id.validate_syntax("client_id", &CLIENT_ID_SYNTAX)?;
+
+    Ok(ClientCredentials {
+        client_id,
+        client_secret,
+        client_secret_basic,
+        client_assertion: client_assertion.map(|(assertion, _)| assertion),
+        client_certificate: request.peer_certificate(),
+    })
+}
+
+/// Read the client assertion from the request body, along with its unverified subject.
+fn get_client_assertion_from_body(
+    request: &dyn FrontendRequest,
+) -> Result<Option<(String, String)>, OAuthValidationError> {
+    static MASKED: &str = "<masked>";
+
+    let body_param = |key| request.body_param(key).none_if_empty();
+
+    let client_assertion_type = body_param("client_assertion_type");
+    let client_assertion = body_param("client_assertion");
+    let (client_assertion_type, client_assertion) = match (client_assertion_type, client_assertion)
+    {
+        (None, None) => return Ok(None),
+        (Some(client_assertion_type), Some(client_assertion)) => {
+            (client_assertion_type, client_assertion)
+        }
+        (None, Some(_)) => {
+            return Err(OAuthValidationError::MissingRequiredParameter("client_assertion_type"))
+        }
+        (Some(_), None) => {
+            return Err(OAuthValidationError::MissingRequiredParameter("client_assertion"))
+        }
+    };
+    if client_assertion_type != JWT_BEARER_CLIENT_ASSERTION_TYPE {
+        return Err(OAuthValidationError::InvalidParameterValue(
+            "client_assertion_type",
+            client_assertion_type,
+        ));
+    }
+
+    #[derive(Deserialize)]
+    struct Subject {
+        sub: String,
+    }
+    let Some(Subject { sub }) = CompactJws::parse(&client_assertion).and_then(|jws| jws.claims())
+    else {
+        return Err(OAuthValidationError::InvalidParameterValue(
+            "client_assertion",
+            MASKED.to_string(),
+        ));
+    };
+
+    Ok(Some((client_assertion, sub)))
+}
+
+fn get_credentials_from_header(
+    request: &dyn FrontendRequest,
+) -> Result<Option<(String, String)>, OAuthValidationError> {
+    static MASKED: &str = "<masked>";
+
+    if let Some(authorization_header) = request.header_param("authorization") {
+        // Parse the authorization header
+        let parts: Vec<&str> = authorization_header.split_whitespace().collect();
+        if parts.len() != 2 {
+            return Err(OAuthValidationError::InvalidParameterValue(
+                "authorization",
+                MASKED.to_string(),
+            ));
+        }
+        if !parts[0].eq_ignore_ascii_case("Basic") {
+            return Err(OAuthValidationError::InvalidParameterValue(
+                "authorization",
+                MASKED.to_string(),
+            ));
+        }
+        let decoded = BASE64_STANDARD.decode(parts[1]).map_err(|_| {
+            OAuthValidationError::InvalidParameterValue("authorization", MASKED.to_string())
+        })?;
+        let decoded_str = std::str::from_utf8(&decoded).map_err(|_| {
+            OAuthValidationError::InvalidParameterValue("authorization", MASKED.to_string())
+        })?;
+        let parts: Vec<&str> = decoded_str.split(':').collect();
+        if parts.len() != 2 {
+            return Err(OAuthValidationError::InvalidParameterValue(
+                "authorization",
+                MASKED.to_string(),
+            ));
+        }
+        return Ok(Some((parts[0].to_string(), parts[1].to_string())));
+    }
+
+    Ok(None)
+}
+
+/// Read the bearer access token from the `Authorization` header of a request, as described in RFC 6750 section 2.1.
+/// Returns [None] if the header is missing or does not use the `Bearer` scheme.
+pub(crate) fn bearer_token_from_request(request: &dyn FrontendRequest) -> Option<String> {
+    let authorization_header = request.header_param("authorization")?;
+    let parts: Vec<&str> = authorization_header.split_whitespace().collect();
+    if parts.len() != 2 || !parts[0].eq_ignore_ascii_case("Bearer") {
+        return None;
+    }
+    Some(parts[1].to_string())
+}
+
+/// Read the access token from the `Authorization` header of a request to a protected resource.
+/// Besides the `Bearer` scheme, DPoP-bound access tokens are sent using the `DPoP` scheme as described in RFC 9449 section 7.1.
+/// Returns [None] if the header is missing or does not use either scheme.
+pub(crate) fn access_token_from_request(request: &dyn FrontendRequest) -> Option<String> {
+    let authorization_header = request.header_param("authorization")?;
+    let parts: Vec<&str> = authorization_header.split_whitespace().collect();
+    if parts.len() != 2
+        || !(parts[0].eq_ignore_ascii_case("Bearer") || parts[0].eq_ignore_ascii_case("DPoP"))
+    {
+        return None;
+    }
+    Some(parts[1].to_string())
+}
+
+/// The claims of a client assertion, as described in RFC 7523 section 3.
+#[derive(Deserialize)]
+struct ClientAssertionClaims {
+    iss: String,
+    sub: String,
+    aud: Audience,
+    exp: u64,
+    nbf: Option<u64>,
+    jti: String,
+}
+
+/// The audience of a JWT, which can either be a single value or an array.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum Audience {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Audience {
+    pub(crate) fn contains_any(&self, values: &[String]) -> bool {
+        match self {
+            Audience::One(aud) => values.contains(aud),
+            Audience::Many(auds) => auds.iter().any(|aud| values.contains(aud)),
+        }
+    }
+}
+
+impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
+    /// Look up a client and verify its secret, assertion or TLS client certificate, if one was provided.
+    /// Missing credentials are rejected for confidential clients, or when `require_secret` is set.
+    /// The client must authenticate using the method it is registered for, where `client_secret_basic`
+    /// tells whether the client secret was sent using HTTP Basic authentication or in the request body.
+    pub(crate) async fn authenticate_client(
+        &self,
+        client_id: &str,
+        client_secret: Option<String>,
+        client_secret_basic: bool,
+        client_assertion: Option<String>,
+        client_certificate: Option<&[u8]>,
+        require_secret: bool,
+    ) -> Result<Client, OAuthError<E>> {
+        // Clients that attempted to authenticate using HTTP Basic authentication are sent a challenge
+        let invalid = |error| {
+            if client_secret_basic {
+                OAuthValidationError::InvalidBasicAuthentication(Box::new(error)).into()
+            } else {
+                error.into()
+            }
+        };
+
+        let Some(client) = self
+            .client_provider
+            .get_client_by_id(client_id)
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?
+        else {
+            return Err(invalid(OAuthValidationError::ClientDoesNotExist));
+        };
+        if !client.is_valid() {
+            return Err(invalid(OAuthValidationError::InvalidClient));
+        }
+
+        let method = if let Some(client_assertion) = client_assertion {
+            self.verify_client_assertion(&client, &client_assertion).await?
+        } else if let Some(client_secret) = client_secret {
+            let secret_valid = self
+                .client_provider
+                .verify_client_secret(&client, &client_secret)
+                .await
+                .map_err(OAuthError::ProviderImplementationError)?;
+            if !secret_valid {
+                return Err(invalid(OAuthValidationError::InvalidClientSecret));
+            }
+            if client_secret_basic {
+                TokenEndpointAuthMethod::ClientSecretBasic
+            } else {
+                TokenEndpointAuthMethod::ClientSecretPost
+            }
+        } else if let Some(tls_client_authentication) =
+            self.tls_client_authentication(&client).await?
+        {
+            let method = match tls_client_authentication {
+                TlsClientAuthentication::SelfSigned(_) => {
+                    TokenEndpointAuthMethod::SelfSignedTlsClientAuth
+                }
+                _ => TokenEndpointAuthMethod::TlsClientAuth,
+            };
+            self.verify_client_certificate(client_certificate, tls_client_authentication)?;
+            method
+        } else if client.confidential || require_secret {
+            return Err(OAuthValidationError::MissingRequiredParameter("client_secret").into());
+        } else {
+            return Ok(client);
+        };
+
+        if method != client.token_endpoint_auth_method {
+            return Err(OAuthValidationError::ClientNotAllowedToUseAuthenticationMethod {
+                used: method.as_str(),
+            }
+            .into());
+        }
+
+        Ok(client)
+    }
+
+    /// Verify a client assertion as described in RFC 7523 section 3,
+    /// using the keys returned by [ClientProvider::get_client_assertion_keys](crate::common::model::ClientProvider::get_client_assertion_keys).
+    /// Returns the authentication method the assertion was signed for.
+    async fn verify_client_assertion(
+        &self,
+        client: &Client,
+        client_assertion: &str,
+    ) -> Result<TokenEndpointAuthMethod, OAuthError<E>> {
+        let invalid = |reason| OAuthValidationError::InvalidClientAssertion(reason).into();
+
+        let Some(jws) = CompactJws::parse(client_assertion) else {
+            return Err(invalid("the assertion is not a valid JWS"));
+        };
+
+        // The JWT MUST be digitally signed or have a Message Authentication Code applied by the issuer.
+        let keys = self
+            .client_provider
+            .get_client_assertion_keys(client)
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?;
+        let (method, signature_valid) = match keys {
+            None => return Err(invalid("the client does not authenticate using assertions")),
+            Some(ClientAssertionKeys::Secret(secret)) => (
+                TokenEndpointAuthMethod::ClientSecretJwt,
+                verify_hmac(
+                    &jws.header.alg,
+                    secret.as_bytes(),
+                    jws.signing_input.as_bytes(),
+                    &jws.signature,
+                ),
+            ),
+            Some(ClientAssertionKeys::Jwks(keys)) => {
+                let Some(jws_verifier) = &self.jws_verifier else {
+                    return Err(invalid("no JWS verifier is configured"));
+                };
+                let signature_valid = keys
+                    .iter()
+                    .filter(|key| jws.header.kid.is_none() || key.kid == jws.header.kid)
+                    .any(|key| jws.verify(jws_verifier.as_ref(), key));
+                (TokenEndpointAuthMethod::PrivateKeyJwt, signature_valid)
+            }
+        };
+        if !signature_valid {
+            return Err(invalid("the signature is invalid"));
+        }
+
+        let Some(claims) = jws.claims::<ClientAssertionClaims>() else {
+            return Err(invalid("the assertion is missing required claims"));
+        };
+
+        // For client authentication, the subject MUST be the "client_id" of the OAuth client.
+        if claims.iss != client.client_id || claims.sub != client.client_id {
+            return Err(invalid("the issuer and subject must be the client id"));
+        }
+
+        if !claims.aud.contains_any(&self.assertion_audiences()?) {
+            return Err(invalid("the audience does not identify this authorization server"));
+        }
+
+        // The authorization server MUST reject any JWT with an expiration time that has passed.
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        if claims.exp <= now {
+            return Err(invalid("the assertion has expired"));
+        }
+        if claims.nbf.is_some_and(|nbf| nbf > now) {
+            return Err(invalid("the assertion is not yet valid"));
+        }
+
+        // The authorization server MAY ensure that JWTs are not replayed by maintaining the set of used "jti" values
+        let valid_until = UNIX_EPOCH + Duration::from_secs(claims.exp);
+        let first_use = self
+            .client_provider
+            .register_client_assertion_id(client, &claims.jti, valid_until)
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?;
+        if !first_use {
+            return Err(invalid("the assertion was already used"));
+        }
+
+        Ok(method)
+    }
+
+    /// The values that identify this authorization server as the intended audience of an assertion.
+    pub(crate) fn assertion_audiences(&self) -> Result<Vec<String>, OAuthError<E>> {
+        // The JWT MUST contain an "aud" (audience) claim containing a value that identifies the authorization server as an intended audience.
+        // The token endpoint URL of the authorization server MAY be used as a value for an "aud" element.
+        let Some(issuer) = self.config.authorization_server_identifier.clone() else {
+            return Err(OAuthValidationError::NoAuthorizationServerIdentifier.into());
+        };
+        let mut audiences = vec![issuer];
+        audiences.extend(self.config.endpoint_url(Endpoint::Token));
+        Ok(audiences)
+    }
+
+    /// Get the mutual TLS registration of a client, if mutual TLS is enabled.
+    async fn tls_client_authentication(
+        &self,
+        client: &Client,
+    ) -> Result<Option<TlsClientAuthentication>, OAuthError<E>> {
+        if !self.config.mutual_tls {
+            return Ok(None);
+        }
+
+        self.client_provider
+            .get_tls_client_authentication(client)
+            .await
+            .map_err(OAuthError::ProviderImplementationError)
+    }
+
+    /// Verify the TLS client certificate against the registration of the client, as described in RFC 8705 section 2.
+    fn verify_client_certificate(
+        &self,
+        client_certificate: Option<&[u8]>,
+        tls_client_authentication: TlsClientAuthentication,
+    ) -> Result<(), OAuthError<E>> {
+        let invalid = |reason| OAuthValidationError::InvalidClientCertificate(reason).into();
+
+        let Some(client_certificate) = client_certificate else {
+            return Err(invalid("no certificate was presented"));
+        };
+        let Some(certificate) = ClientCertificate::parse(client_certificate) else {
+            return Err(invalid("the certificate could not be parsed"));
+        };
+
+        // Exactly one of the subject or subject alternative name values is used to match the certificate
+        let matches = match tls_client_authentication {
+            TlsClientAuthentication::SubjectDn(subject_dn) => certificate.subject_dn == subject_dn,
+            TlsClientAuthentication::SanDns(dns) => {
+                certificate.san_dns.iter().any(|san| san.eq_ignore_ascii_case(&dns))
+            }
+            TlsClientAuthentication::SanUri(uri) => certificate.san_uri.contains(&uri),
+            TlsClientAuthentication::SanIp(ip) => certificate.san_ip.contains(&ip),
+            TlsClientAuthentication::SanEmail(email) => certificate.san_email.contains(&email),
+            // The certificate must match one of the certificates the client registered in its JSON Web Key Set
+            TlsClientAuthentication::SelfSigned(keys) => keys.iter().any(|key| {
+                key.other
+                    .get("x5c")
+                    .and_then(|x5c| x5c.get(0))
+                    .and_then(|x5c| x5c.as_str())
+                    .and_then(|x5c| BASE64_STANDARD.decode(x5c).ok())
+                    .is_some_and(|der| der == client_certificate)
+            }),
+        };
+        if !matches {
+            return Err(invalid("the certificate does not match the registration of the client"));
+        }
+
+        Ok(())
+    }
+}
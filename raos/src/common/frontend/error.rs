@@ -37,16 +37,43 @@ pub enum OAuthValidationError {
     /// The client credentials provided in the request and header mismatch.
     #[error("The client credentials provided in the request and header mismatch")]
     MismatchedClientCredentials,
+    /// The client used more than one authentication method in the request.
+    #[error("The client used more than one authentication method")]
+    MultipleClientAuthenticationMethods,
+    /// The client assertion is invalid, expired, replayed or not signed by a key of the client.
+    #[error("Invalid client assertion: {0}")]
+    InvalidClientAssertion(&'static str),
+    /// The TLS client certificate is missing, or does not match the mutual TLS registration of the client.
+    #[error("Invalid client certificate: {0}")]
+    InvalidClientCertificate(&'static str),
     /// The client returned from the provider is invalid.
     #[error("The client returned from the provider is invalid.")]
     InvalidClient,
+    /// The client attempted to authenticate using HTTP Basic authentication, which failed with the contained error.
+    #[error("HTTP Basic authentication failed: {0}")]
+    InvalidBasicAuthentication(Box<OAuthValidationError>),
     /// The authenticated client is not authorized to use this authorization grant type.
     #[error("The authenticated client is not authorized to use this authorization grant type: {requested}"
     )]
     ClientNotAllowedToUseGrantType {
         /// The requested grant type
+        requested: String,
+    },
+    /// The client is not registered for the response type it requested.
+    #[error("The client is not authorized to use this response type: {requested}")]
+    ClientNotAllowedToUseResponseType {
+        /// The requested response type
         requested: &'static str,
     },
+    /// The client authenticated using a method other than the one it is registered for.
+    #[error("The client is not authorized to use this authentication method: {used}")]
+    ClientNotAllowedToUseAuthenticationMethod {
+        /// The authentication method the client used
+        used: &'static str,
+    },
+    /// The authenticated client is not allowed to introspect tokens, as it is not a confidential client.
+    #[error("The authenticated client is not allowed to introspect tokens")]
+    ClientNotAllowedToIntrospect,
     /// The client secret provided by the request is incorrect.
     #[error("The client secret is incorrect.")]
     InvalidClientSecret,
@@ -75,15 +102,108 @@ pub enum OAuthValidationError {
     /// Authorization code was not issued to this client
     #[error("Authorization code was not issued to this client")]
     AuthorizationCodeClientMismatch,
+    /// An authorization code was used more than once, the tokens issued from it have been revoked.
+    #[error("Authorization code was already used")]
+    AuthorizationCodeReplayed,
     /// Invalid refresh token.
     #[error("Invalid refresh token")]
     InvalidRefreshToken,
     /// Refresh token was not issued to this client
     #[error("Refresh token was not issued to this client")]
     RefreshTokenClientMismatch,
+    /// A refresh token was used more than once, its family has been revoked.
+    #[error("Refresh token was already used")]
+    RefreshTokenReplayed,
     /// Invalid code verifier.
     #[error("Invalid code verifier")]
     InvalidCodeVerifier,
+    /// The user code entered by the resource owner is unknown, expired or already used.
+    #[error("Invalid user code")]
+    InvalidUserCode,
+    /// The resource owner has not yet completed the device authorization.
+    #[error("The device authorization is still pending")]
+    AuthorizationPending,
+    /// The device is polling the token endpoint too quickly.
+    #[error("The device is polling too quickly")]
+    SlowDown,
+    /// The device code has expired.
+    #[error("The device code has expired")]
+    ExpiredDeviceCode,
+    /// Invalid device code.
+    #[error("Invalid device code")]
+    InvalidDeviceCode,
+    /// Device code was not issued to this client
+    #[error("Device code was not issued to this client")]
+    DeviceCodeClientMismatch,
+    /// No pushed authorization provider was configured, so pushed authorization requests are not supported.
+    #[error("Pushed authorization requests are not supported")]
+    PushedAuthorizationNotSupported,
+    /// The client is required to use pushed authorization requests, but did not include a request_uri.
+    #[error("A pushed authorization request is required for this client")]
+    PushedAuthorizationRequired,
+    /// The request_uri is unknown, expired, already used or was pushed by another client.
+    #[error("Invalid request uri")]
+    InvalidRequestUri,
+    /// The DPoP proof sent along with the request is invalid, expired or replayed.
+    #[error("Invalid DPoP proof: {0}")]
+    InvalidDPoPProof(&'static str),
+    /// The DPoP proof did not contain the nonce the server expects, the client has to retry with the given nonce.
+    #[error("The DPoP proof must use nonce {0}")]
+    UseDPoPNonce(String),
+    /// The authorization code or refresh token is bound to another DPoP key than the one that signed the proof.
+    #[error("The grant is bound to another DPoP key")]
+    DPoPKeyMismatch,
+    /// The resource owner needs to authenticate, but the client requested that no user interface is displayed.
+    #[error("The resource owner needs to authenticate")]
+    LoginRequired,
+    /// The resource owner needs to consent, but the client requested that no user interface is displayed.
+    #[error("The resource owner needs to consent")]
+    ConsentRequired,
+    /// No authorization server identifier was configured, which is required for this request.
+    #[error("No authorization server identifier was configured")]
+    NoAuthorizationServerIdentifier,
+    /// No user info provider was configured, so OpenID Connect requests are not supported.
+    #[error("OpenID Connect is not supported")]
+    OpenIdConnectNotSupported,
+    /// The request to a protected resource does not include an access token.
+    #[error("Missing access token")]
+    MissingAccessToken,
+    /// The bearer access token is unknown, expired or revoked.
+    #[error("Invalid access token")]
+    InvalidAccessToken,
+    /// No client registration provider was configured, so dynamic client registration is not supported.
+    #[error("Dynamic client registration is not supported")]
+    ClientRegistrationNotSupported,
+    /// The metadata of a client registration request is invalid, or not supported by the authorization server.
+    #[error("Invalid client metadata: {0}")]
+    InvalidClientMetadata(&'static str),
+    /// A redirect uri of a client registration request is missing, could not be parsed, or contained a #fragment.
+    #[error("Invalid redirect uri in client metadata")]
+    InvalidClientRedirectUri,
+    /// The access token was not granted the scope required for this request, containing the space separated required scope.
+    #[error("The access token does not have the required scope: {0}")]
+    InsufficientScope(String),
+    /// The access token was sent in both the `Authorization` header and the request body.
+    #[error("The access token was sent using more than one method")]
+    MultipleAccessTokens,
+    /// The subject token of a token exchange request is unknown, expired or of an unsupported type.
+    #[error("Invalid subject token")]
+    InvalidSubjectToken,
+    /// The actor token of a token exchange request is unknown, expired or of an unsupported type.
+    #[error("Invalid actor token")]
+    InvalidActorToken,
+    /// The client is not allowed to exchange the subject token for the requested token.
+    #[error("The client is not allowed to perform this token exchange")]
+    ClientNotAllowedToExchangeToken,
+    /// The assertion of a JWT bearer grant is invalid, expired, not signed by a trusted issuer, or its subject is unknown.
+    #[error("Invalid assertion: {0}")]
+    InvalidAssertion(&'static str),
+    /// The grant type handler of a custom grant type rejected the grant.
+    #[error("The custom grant was rejected by its grant type handler")]
+    InvalidExtensionGrant,
+    /// A requested resource is not an absolute uri without a fragment, or the client may not target it.
+    #[error("Invalid target: {0}")]
+    InvalidTarget(&'static str),
 }
 
 /// The error type used to return from all OAuth functions, which splits into validation and provider errors.
@@ -116,13 +236,13 @@ impl<E> FrontendResponseExt for OAuthError<E> {
         }
 
         let error: PublicOAuthError = self.into();
-        FrontendResponse::Error { error: error.into() }
+        error.into()
     }
 }
 
 /// The public OAuth error types that can be returned to the client.
 /// These are the errors that are safe to show to the client, and do not expose any internal information.
-#[derive(Error, Debug, PartialEq, Serialize)]
+#[derive(Error, Debug, Clone, PartialEq, Serialize)]
 pub enum PublicOAuthError {
     /// The resource owner or authorization server denied the request.
     #[error("access_denied")]
@@ -133,15 +253,62 @@ pub enum PublicOAuthError {
     /// The requested scope is invalid, unknown, malformed, or exceeds the scope granted by the resource owner.
     #[error("invalid_scope")]
     InvalidScope,
+    /// The requested resource is invalid, missing, unknown, or malformed.
+    #[error("invalid_target")]
+    InvalidTarget,
     /// Client authentication failed (e.g., unknown client, no client authentication included, or unsupported authentication method).
     #[error("invalid_client")]
-    InvalidClient,
+    InvalidClient {
+        /// Whether the client attempted to authenticate using HTTP Basic authentication,
+        /// in which case the response is a 401 with a `WWW-Authenticate` challenge instead of a 400.
+        basic_authentication: bool,
+    },
     /// The requested grant type is not supported by the authorization server.
     #[error("unsupported_grant_type")]
     UnsupportedGrantType,
     /// The authenticated client is not authorized to use this authorization grant type.
     #[error("unauthorized_client")]
     UnauthorizedClient,
+    /// The provided authorization grant or refresh token is invalid, expired, revoked, or was issued to another client.
+    #[error("invalid_grant")]
+    InvalidGrant,
+    /// The authorization request is still pending as the end user hasn't yet completed the user-interaction steps.
+    #[error("authorization_pending")]
+    AuthorizationPending,
+    /// The authorization request is still pending and polling should continue, but the interval MUST be increased by 5 seconds.
+    #[error("slow_down")]
+    SlowDown,
+    /// The device code has expired, and the device authorization session has concluded.
+    #[error("expired_token")]
+    ExpiredToken,
+    /// The DPoP proof sent along with the request is invalid.
+    #[error("invalid_dpop_proof")]
+    InvalidDPoPProof,
+    /// The DPoP proof must include the server-provided nonce, which is sent along in the `DPoP-Nonce` header.
+    #[error("use_dpop_nonce")]
+    UseDPoPNonce(String),
+    /// The authorization server requires the resource owner to authenticate, which cannot be done without displaying a user interface.
+    #[error("login_required")]
+    LoginRequired,
+    /// The authorization server requires the resource owner to consent, which cannot be done without displaying a user interface.
+    #[error("consent_required")]
+    ConsentRequired,
+    /// The value of one or more redirection URIs is invalid.
+    #[error("invalid_redirect_uri")]
+    InvalidRedirectUri,
+    /// The value of one of the client metadata fields is invalid and the server has rejected this request.
+    #[error("invalid_client_metadata")]
+    InvalidClientMetadata,
+    /// The request to a protected resource lacks any authentication information.
+    /// This is answered with a challenge that does not include an error code, as described in RFC 6750 section 3.1.
+    #[error("invalid_request")]
+    MissingToken,
+    /// The access token provided is expired, revoked, malformed, or invalid for other reasons.
+    #[error("invalid_token")]
+    InvalidToken,
+    /// The request requires higher privileges than provided by the access token, containing the scope that is required.
+    #[error("insufficient_scope")]
+    InsufficientScope(String),
     /// The authorization server encountered an unexpected condition that prevented it from fulfilling the request.
     #[error("server_error")]
     ServerError,
@@ -155,17 +322,80 @@ impl<E> From<OAuthError<E>> for PublicOAuthError {
                 OAuthValidationError::ClientDoesNotExist
                 | OAuthValidationError::MismatchedClientCredentials
                 | OAuthValidationError::InvalidClient
-                | OAuthValidationError::InvalidClientSecret,
-            ) => Self::InvalidClient,
+                | OAuthValidationError::InvalidClientSecret
+                | OAuthValidationError::InvalidClientAssertion(_)
+                | OAuthValidationError::InvalidClientCertificate(_),
+            ) => Self::InvalidClient { basic_authentication: false },
+            OAuthError::ValidationFailed(OAuthValidationError::InvalidBasicAuthentication(_)) => {
+                Self::InvalidClient { basic_authentication: true }
+            }
             OAuthError::ValidationFailed(
                 OAuthValidationError::ScopeNotConsented | OAuthValidationError::NoScopesProvided,
             ) => Self::InvalidScope,
             OAuthError::ValidationFailed(
-                OAuthValidationError::ClientNotAllowedToUseGrantType { .. },
+                OAuthValidationError::ClientNotAllowedToUseGrantType { .. }
+                | OAuthValidationError::ClientNotAllowedToUseResponseType { .. }
+                | OAuthValidationError::ClientNotAllowedToUseAuthenticationMethod { .. }
+                | OAuthValidationError::ClientNotAllowedToIntrospect
+                | OAuthValidationError::ClientNotAllowedToExchangeToken,
             ) => Self::UnauthorizedClient,
             OAuthError::ValidationFailed(OAuthValidationError::InvalidGrantType { .. }) => {
                 Self::UnsupportedGrantType
             }
+            OAuthError::ValidationFailed(OAuthValidationError::NoAuthorizationServerIdentifier) => {
+                Self::ServerError
+            }
+            OAuthError::ValidationFailed(OAuthValidationError::AuthorizationPending) => {
+                Self::AuthorizationPending
+            }
+            OAuthError::ValidationFailed(OAuthValidationError::SlowDown) => Self::SlowDown,
+            OAuthError::ValidationFailed(OAuthValidationError::ExpiredDeviceCode) => {
+                Self::ExpiredToken
+            }
+            OAuthError::ValidationFailed(
+                OAuthValidationError::InvalidAuthorizationCode
+                | OAuthValidationError::AuthorizationCodeClientMismatch
+                | OAuthValidationError::InvalidCodeVerifier
+                | OAuthValidationError::InvalidRefreshToken
+                | OAuthValidationError::RefreshTokenClientMismatch
+                | OAuthValidationError::InvalidDeviceCode
+                | OAuthValidationError::DeviceCodeClientMismatch
+                | OAuthValidationError::RefreshTokenReplayed
+                | OAuthValidationError::AuthorizationCodeReplayed
+                | OAuthValidationError::DPoPKeyMismatch
+                | OAuthValidationError::InvalidAssertion(_)
+                | OAuthValidationError::InvalidExtensionGrant,
+            ) => Self::InvalidGrant,
+            OAuthError::ValidationFailed(OAuthValidationError::InvalidDPoPProof(_)) => {
+                Self::InvalidDPoPProof
+            }
+            OAuthError::ValidationFailed(OAuthValidationError::UseDPoPNonce(nonce)) => {
+                Self::UseDPoPNonce(nonce)
+            }
+            OAuthError::ValidationFailed(OAuthValidationError::LoginRequired) => {
+                Self::LoginRequired
+            }
+            OAuthError::ValidationFailed(OAuthValidationError::ConsentRequired) => {
+                Self::ConsentRequired
+            }
+            OAuthError::ValidationFailed(OAuthValidationError::InvalidClientMetadata(_)) => {
+                Self::InvalidClientMetadata
+            }
+            OAuthError::ValidationFailed(OAuthValidationError::InvalidClientRedirectUri) => {
+                Self::InvalidRedirectUri
+            }
+            OAuthError::ValidationFailed(OAuthValidationError::MissingAccessToken) => {
+                Self::MissingToken
+            }
+            OAuthError::ValidationFailed(OAuthValidationError::InvalidAccessToken) => {
+                Self::InvalidToken
+            }
+            OAuthError::ValidationFailed(OAuthValidationError::InsufficientScope(scope)) => {
+                Self::InsufficientScope(scope)
+            }
+            OAuthError::ValidationFailed(OAuthValidationError::InvalidTarget(_)) => {
+                Self::InvalidTarget
+            }
             OAuthError::ValidationFailed(_) => Self::InvalidRequest,
             OAuthError::ProviderImplementationError(_) => Self::ServerError,
             OAuthError::RequiresResourceOwnerInteraction(_) => {
@@ -177,6 +407,42 @@ impl<E> From<OAuthError<E>> for PublicOAuthError {
 }
 
 impl PublicOAuthError {
+    /// Get the HTTP status code that should be used when returning this error.
+    /// Failed HTTP Basic client authentication and missing or invalid access tokens result in a 401,
+    /// insufficient scope in a 403, server errors in a 500, and all other errors in a 400.
+    /// Every 401 is sent along with a `WWW-Authenticate` challenge from [headers](Self::headers).
+    pub fn status_code(&self) -> u16 {
+        match self {
+            Self::InvalidClient { basic_authentication: true }
+            | Self::MissingToken
+            | Self::InvalidToken => 401,
+            Self::InsufficientScope(_) => 403,
+            Self::ServerError => 500,
+            _ => 400,
+        }
+    }
+
+    /// Get the additional headers that should be sent when returning this error.
+    /// Failed HTTP Basic client authentication is answered with a `Basic` challenge in the `WWW-Authenticate` header,
+    /// errors about the bearer access token are described in the `WWW-Authenticate` header as in RFC 6750 section 3,
+    /// and a required DPoP nonce is sent in the `DPoP-Nonce` header.
+    pub fn headers(&self) -> Vec<(&'static str, String)> {
+        match self {
+            Self::InvalidClient { basic_authentication: true } => {
+                vec![("WWW-Authenticate", r#"Basic realm="OAuth""#.to_string())]
+            }
+            Self::MissingToken => vec![("WWW-Authenticate", "Bearer".to_string())],
+            Self::InvalidToken => {
+                vec![("WWW-Authenticate", format!(r#"Bearer error="{self}""#))]
+            }
+            Self::InsufficientScope(scope) => {
+                vec![("WWW-Authenticate", format!(r#"Bearer error="{self}", scope="{scope}""#))]
+            }
+            Self::UseDPoPNonce(nonce) => vec![("DPoP-Nonce", nonce.clone())],
+            _ => Vec::new(),
+        }
+    }
+
     /// Get a human-readable description of the error.
     /// This is used to generate the error_description field in the OAuth response.
     /// This is a static string, and does not contain any internal information.
@@ -186,9 +452,23 @@ impl PublicOAuthError {
             Self::AccessDenied => "The resource owner or authorization server denied the request.",
             Self::InvalidRequest => "The request is missing a required parameter, includes an invalid parameter value, includes a parameter more than once, or is otherwise malformed.",
             Self::InvalidScope => "The requested scope is invalid, unknown, malformed, or exceeds the scope granted by the resource owner.",
-            Self::InvalidClient => "Client authentication failed (e.g., unknown client, no client authentication included, or unsupported authentication method).",
+            Self::InvalidTarget => "The requested resource is invalid, missing, unknown, or malformed.",
+            Self::InvalidClient { .. } => "Client authentication failed (e.g., unknown client, no client authentication included, or unsupported authentication method).",
             Self::UnsupportedGrantType => "The authorization grant type is not supported by the authorization server.",
             Self::UnauthorizedClient => "The authenticated client is not authorized to use this authorization grant type.",
+            Self::InvalidGrant => "The provided authorization grant or refresh token is invalid, expired, revoked, or was issued to another client.",
+            Self::AuthorizationPending => "The authorization request is still pending as the end user hasn't yet completed the user-interaction steps.",
+            Self::SlowDown => "The authorization request is still pending and polling should continue, but the interval MUST be increased by 5 seconds.",
+            Self::ExpiredToken => "The device code has expired, and the device authorization session has concluded.",
+            Self::InvalidDPoPProof => "The DPoP proof sent along with the request is invalid.",
+            Self::UseDPoPNonce(_) => "The authorization server requires a nonce in the DPoP proof, as provided in the DPoP-Nonce header.",
+            Self::LoginRequired => "The authorization server requires the resource owner to authenticate, which cannot be done without displaying a user interface.",
+            Self::ConsentRequired => "The authorization server requires the resource owner to consent, which cannot be done without displaying a user interface.",
+            Self::InvalidRedirectUri => "The value of one or more redirection URIs is invalid.",
+            Self::InvalidClientMetadata => "The value of one of the client metadata fields is invalid and the server has rejected this request.",
+            Self::MissingToken => "The request does not include an access token.",
+            Self::InvalidToken => "The access token provided is expired, revoked, malformed, or invalid for other reasons.",
+            Self::InsufficientScope(_) => "The request requires higher privileges than provided by the access token.",
             Self::ServerError => "The authorization server encountered an unexpected condition that prevented it from fulfilling the request.",
         }
     }
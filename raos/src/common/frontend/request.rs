@@ -1,4 +1,6 @@
-/// The FrontendRequestMethod enum describes the two supported request methods.
+use std::collections::HashMap;
+
+/// The FrontendRequestMethod enum describes the supported request methods.
 /// For other methods, the OtherUnsupported variant is used.
 #[derive(Clone, Debug, PartialEq)]
 pub enum FrontendRequestMethod {
@@ -6,10 +8,27 @@ pub enum FrontendRequestMethod {
     GET,
     /// HTTP POST
     POST,
-    /// Other request methods, e.g. PUT, DELETE, etc., used for logging and error purposes.
+    /// HTTP PUT, used by the client configuration endpoint.
+    PUT,
+    /// HTTP DELETE, used by the client configuration endpoint.
+    DELETE,
+    /// Other request methods, e.g. PATCH, HEAD, etc., used for logging and error purposes.
     OtherUnsupported(String),
 }
 
+impl FrontendRequestMethod {
+    /// The name of the request method, like `GET`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::GET => "GET",
+            Self::POST => "POST",
+            Self::PUT => "PUT",
+            Self::DELETE => "DELETE",
+            Self::OtherUnsupported(method) => method,
+        }
+    }
+}
+
 /// Frontend request wrapper to be implemented by server framework wrappers
 /// to provide a unified interface for handling requests.
 pub trait FrontendRequest {
@@ -24,4 +43,36 @@ pub trait FrontendRequest {
 
     /// Returns value of the specified body parameter, likely from application/x-www-form-urlencoded encoding.
     fn body_param(&self, key: &str) -> Option<String>;
+
+    /// Returns all values of the specified query parameter, for parameters that may be repeated like `resource`.
+    /// Frontends that keep a single value per parameter can leave this unimplemented, so only that value is used.
+    fn query_param_values(&self, key: &str) -> Vec<String> {
+        self.query_param(key).into_iter().collect()
+    }
+
+    /// Returns all values of the specified body parameter, for parameters that may be repeated like `resource`.
+    /// Frontends that keep a single value per parameter can leave this unimplemented, so only that value is used.
+    fn body_param_values(&self, key: &str) -> Vec<String> {
+        self.body_param(key).into_iter().collect()
+    }
+
+    /// Returns all body parameters of the request, likely from application/x-www-form-urlencoded encoding.
+    /// This is only used to pass the request to custom grant type handlers.
+    /// Frontends that do not support custom grant types can leave this unimplemented.
+    fn body_params(&self) -> HashMap<String, String> {
+        HashMap::new()
+    }
+
+    /// Returns the body of the request parsed as JSON, if it has the `application/json` content type.
+    /// This is only used by endpoints that receive JSON, like client registration.
+    /// Frontends that do not serve those endpoints can leave this unimplemented.
+    fn body_json(&self) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// Returns the DER encoded certificate the client presented during the TLS handshake, if mutual TLS is used.
+    /// Frontends that do not terminate TLS themselves can leave this unimplemented.
+    fn peer_certificate(&self) -> Option<Vec<u8>> {
+        None
+    }
 }
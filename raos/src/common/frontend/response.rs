@@ -1,9 +1,9 @@
 use url::Url;
 
-use crate::common::frontend::{OAuthError, PublicOAuthErrorBody};
+use crate::common::frontend::{OAuthError, PublicOAuthError, PublicOAuthErrorBody};
 
 /// The FrontendResponse enum is used to send responses back to the client.
-/// This enum is used to send JSON responses, error responses, and redirects.
+/// This enum is used to send JSON responses, JWT responses, error responses, and redirects.
 #[derive(Debug, PartialEq)]
 pub enum FrontendResponse {
     /// A successful JSON response.
@@ -11,8 +11,26 @@ pub enum FrontendResponse {
         /// The JSON data to send back to the client.
         json: serde_json::Value,
     },
+    /// A successful JSON response that created a resource, sent with a 201 status code.
+    Created {
+        /// The JSON data to send back to the client.
+        json: serde_json::Value,
+    },
+    /// A successful response containing a signed JWT, sent with the `application/jwt` content type.
+    Jwt {
+        /// The compact serialization of the JWT to send back to the client.
+        jwt: String,
+    },
+    /// A successful response without a body.
+    Empty,
+    /// A successful response without a body, sent with a 204 status code.
+    NoContent,
     /// An error response.
     Error {
+        /// The HTTP status code of the response.
+        status: u16,
+        /// Additional headers to send with the response, like `WWW-Authenticate`.
+        headers: Vec<(&'static str, String)>,
         /// The error to send back to the client.
         error: PublicOAuthErrorBody,
     },
@@ -23,6 +41,12 @@ pub enum FrontendResponse {
     },
 }
 
+impl From<PublicOAuthError> for FrontendResponse {
+    fn from(value: PublicOAuthError) -> Self {
+        Self::Error { status: value.status_code(), headers: value.headers(), error: value.into() }
+    }
+}
+
 /// The FrontendResponseExt trait is used to convert various response types like
 /// [AuthorizationResponse](crate::authorize::AuthorizationResponse) and [TokenResponse](crate::token::TokenResponse)
 /// into a [FrontendResponse].
@@ -0,0 +1,88 @@
+use std::collections::BTreeMap;
+
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::common::frontend::{FrontendResponse, FrontendResponseExt};
+
+/// A JSON Web Key, as described in RFC 7517.
+/// Only the public parameters of the supported key types are modelled, any other parameters are kept in `other`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Jwk {
+    /// The key type, like `EC`, `RSA`, `OKP` or `oct`.
+    pub kty: String,
+    /// The key id, used to select a key from a set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    /// The algorithm the key is intended to be used with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alg: Option<String>,
+    /// The intended use of the key, `sig` or `enc`.
+    #[serde(rename = "use", skip_serializing_if = "Option::is_none")]
+    pub key_use: Option<String>,
+    /// The curve of an `EC` or `OKP` key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+    /// The x coordinate of an `EC` key, or the public key of an `OKP` key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+    /// The y coordinate of an `EC` key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+    /// The modulus of an `RSA` key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+    /// The exponent of an `RSA` key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+    /// The key value of an `oct` (symmetric) key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub k: Option<String>,
+    /// Any other parameters of the key.
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Jwk {
+    /// Whether this key contains private or symmetric key material, which must never be sent along with a signature.
+    pub fn has_private_parameters(&self) -> bool {
+        self.k.is_some()
+            || ["d", "p", "q", "dp", "dq", "qi"].iter().any(|p| self.other.contains_key(*p))
+    }
+
+    /// Compute the JWK SHA-256 thumbprint of this key, as described in RFC 7638.
+    /// Returns [None] if the key type is unknown, or if a required parameter is missing.
+    pub fn thumbprint(&self) -> Option<String> {
+        // The thumbprint is computed over the required members only, in lexicographic order
+        let mut members = BTreeMap::new();
+        let required: &[(&str, &Option<String>)] = match self.kty.as_str() {
+            "EC" => &[("crv", &self.crv), ("x", &self.x), ("y", &self.y)],
+            "RSA" => &[("e", &self.e), ("n", &self.n)],
+            "OKP" => &[("crv", &self.crv), ("x", &self.x)],
+            "oct" => &[("k", &self.k)],
+            _ => return None,
+        };
+        for (name, value) in required {
+            members.insert(*name, value.as_deref()?);
+        }
+        members.insert("kty", self.kty.as_str());
+
+        let json = serde_json::to_vec(&members).ok()?;
+        Some(BASE64_URL_SAFE_NO_PAD.encode(Sha256::digest(json)))
+    }
+}
+
+/// A JSON Web Key Set, as described in RFC 7517 section 5.
+/// This is used for the keys clients register, and served on `/.well-known/jwks.json` for the keys of the authorization server.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct JsonWebKeySet {
+    /// The keys in the set.
+    pub keys: Vec<Jwk>,
+}
+
+impl FrontendResponseExt for JsonWebKeySet {
+    fn into_frontend_response(self) -> FrontendResponse {
+        FrontendResponse::Success { json: serde_json::to_value(self).unwrap() }
+    }
+}
@@ -0,0 +1,83 @@
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::common::jose::Jwk;
+
+/// Verifier for JSON Web Signatures, used to check the signatures of JWTs presented by clients.
+/// This is an optional trait that can be implemented by the end user using the cryptography library of their choice.
+///
+/// The library takes care of parsing the JWS and validating its claims, only the signature check is delegated.
+pub trait JwsVerifier: 'static + Send + Sync {
+    /// The JWS algorithms this verifier supports, like `ES256` or `RS256`.
+    /// These are advertised in the authorization server metadata, and signatures using other algorithms are rejected.
+    fn supported_algorithms(&self) -> Vec<&'static str>;
+
+    /// Verify a signature.
+    ///
+    /// # Implementation notes
+    /// The algorithm MUST be checked against the key, so that a key is never used with an algorithm it was not made for.
+    ///
+    /// # Arguments
+    /// * `alg` - The algorithm from the JWS header, one of [supported_algorithms](JwsVerifier::supported_algorithms).
+    /// * `key` - The public key that should have produced the signature.
+    /// * `signing_input` - The signed data, being the encoded header and payload separated by a period.
+    /// * `signature` - The decoded signature.
+    ///
+    /// # Returns
+    /// Whether the signature is valid for the given key and data.
+    fn verify(&self, alg: &str, key: &Jwk, signing_input: &[u8], signature: &[u8]) -> bool;
+}
+
+/// The header of a JSON Web Signature.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct JwsHeader {
+    pub(crate) alg: String,
+    pub(crate) typ: Option<String>,
+    pub(crate) kid: Option<String>,
+    pub(crate) jwk: Option<Jwk>,
+}
+
+/// A JSON Web Signature in compact serialization, as described in RFC 7515.
+/// The signature is not verified while parsing.
+#[derive(Debug)]
+pub(crate) struct CompactJws {
+    pub(crate) header: JwsHeader,
+    pub(crate) payload: Vec<u8>,
+    pub(crate) signing_input: String,
+    pub(crate) signature: Vec<u8>,
+}
+
+impl CompactJws {
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.split('.');
+        let (Some(header), Some(payload), Some(signature), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return None;
+        };
+
+        Some(Self {
+            header: serde_json::from_slice(&BASE64_URL_SAFE_NO_PAD.decode(header).ok()?).ok()?,
+            payload: BASE64_URL_SAFE_NO_PAD.decode(payload).ok()?,
+            signing_input: format!("{header}.{payload}"),
+            signature: BASE64_URL_SAFE_NO_PAD.decode(signature).ok()?,
+        })
+    }
+
+    /// Decode the payload as a JSON object.
+    pub(crate) fn claims<T: DeserializeOwned>(&self) -> Option<T> {
+        serde_json::from_slice(&self.payload).ok()
+    }
+
+    /// Verify the signature of this JWS with the given key, using the configured [JwsVerifier].
+    /// The `none` algorithm, and algorithms the verifier does not support, are always rejected.
+    pub(crate) fn verify(&self, verifier: &dyn JwsVerifier, key: &Jwk) -> bool {
+        verifier.supported_algorithms().contains(&self.header.alg.as_str())
+            && verifier.verify(
+                &self.header.alg,
+                key,
+                self.signing_input.as_bytes(),
+                &self.signature,
+            )
+    }
+}
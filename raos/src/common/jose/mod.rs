@@ -0,0 +1,6 @@
+pub use jwk::*;
+pub use jws::*;
+
+pub(crate) mod hmac;
+mod jwk;
+mod jws;
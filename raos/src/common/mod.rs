@@ -1,6 +1,11 @@
+pub(crate) mod auth;
 /// Common data structures and functions used to communicate with a frontend
 pub mod frontend;
+/// Common data structures and functions used to handle JSON Web Keys and Signatures
+pub mod jose;
 /// Common data structures and functions used across the OAuth spec
 pub mod model;
 pub(crate) mod syntax;
+pub(crate) mod target;
 pub(crate) mod util;
+pub(crate) mod x509;
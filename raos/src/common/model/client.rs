@@ -1,5 +1,11 @@
-use crate::common::syntax::CLIENT_ID_SYNTAX;
+use crate::common::{jose::Jwk, syntax::CLIENT_ID_SYNTAX};
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::{
+    net::IpAddr,
+    time::{Duration, SystemTime},
+};
+use url::{Host, Url};
 
 /// Represents a OAuth client.
 #[derive(Debug, Clone)]
@@ -12,11 +18,50 @@ pub struct Client {
     ///
     /// Confidential clients MUST authenticate with the authorization server, for example by using client credentials.
     pub confidential: bool,
+    /// The grant types the client is allowed to use at the token endpoint, like `authorization_code` or `refresh_token`.
+    /// Requests for any other grant type are rejected with `unauthorized_client`.
+    pub grant_types: Vec<String>,
+    /// The response types the client is allowed to use at the authorization endpoint, like `code`.
+    pub response_types: Vec<String>,
+    /// The method the client authenticates with. Credentials presented using any other method are rejected.
+    pub token_endpoint_auth_method: TokenEndpointAuthMethod,
+    /// The scopes that are requested when the client does not send a scope,
+    /// before they are passed to [ClientProvider::allow_client_scopes].
+    pub default_scopes: Vec<String>,
+    /// The lifetime of the access tokens issued to the client, to be used by the [TokenProvider](crate::token::TokenProvider)
+    /// instead of its default lifetime.
+    pub access_token_lifetime: Option<Duration>,
+    /// The lifetime of the refresh tokens issued to the client, to be used by the [TokenProvider](crate::token::TokenProvider)
+    /// instead of its default lifetime.
+    pub refresh_token_lifetime: Option<Duration>,
+    /// The metadata shown to the resource owner, for example on the consent page.
+    pub display: ClientDisplayMetadata,
+}
+
+/// The metadata of a client that is shown to the resource owner, as described in RFC 7591 section 2.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClientDisplayMetadata {
+    /// The human-readable name of the client.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_name: Option<String>,
+    /// The url of the home page of the client.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_uri: Option<String>,
+    /// The url of the logo of the client.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logo_uri: Option<String>,
+    /// The url of the policy that describes how the client uses the data of the resource owner.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy_uri: Option<String>,
+    /// The url of the terms of service of the client.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tos_uri: Option<String>,
 }
 
 impl Client {
     /// Checks if the client is valid.
-    /// A client is valid if it has a non-empty client id and at least one redirect uri.
+    /// A client is valid if it has a non-empty client id,
+    /// and at least one redirect uri when it is allowed to use the authorization code grant.
     pub fn is_valid(&self) -> bool {
         if self.client_id.trim().is_empty() {
             return false;
@@ -24,15 +69,137 @@ impl Client {
         if !CLIENT_ID_SYNTAX.is_match(&self.client_id) {
             return false;
         }
-        if self.redirect_uris.is_empty() {
+        if self.has_grant_type("authorization_code") && self.redirect_uris.is_empty() {
             return false;
         }
         true
     }
 
     /// Checks if the client has a specific redirect uri whitelisted.
+    /// The uris are compared after parsing, following the rules for native apps described in RFC 8252 section 7:
+    /// loopback redirect uris using an IP literal match on any port, and `localhost` is never allowed.
+    /// Private-use uri schemes like `com.example.app:/callback` are compared like any other uri.
     pub fn has_redirect_uri(&self, redirect_uri: &str) -> bool {
-        self.redirect_uris.contains(&redirect_uri.to_string())
+        let Ok(requested) = redirect_uri.parse::<Url>() else {
+            return false;
+        };
+        if is_localhost(&requested) {
+            return false;
+        }
+
+        self.redirect_uris.iter().filter_map(|registered| registered.parse::<Url>().ok()).any(
+            |registered| {
+                // The port is chosen by the native app at the time of the request, so it can not be registered
+                if is_loopback(&registered) && is_loopback(&requested) {
+                    let mut requested = requested.clone();
+                    if requested.set_port(registered.port()).is_err() {
+                        return false;
+                    }
+                    return requested == registered;
+                }
+                requested == registered
+            },
+        )
+    }
+
+    /// Checks if the client is allowed to use a grant type at the token endpoint.
+    pub fn has_grant_type(&self, grant_type: &str) -> bool {
+        self.grant_types.iter().any(|allowed| allowed == grant_type)
+    }
+
+    /// Checks if the client is allowed to use a response type at the authorization endpoint.
+    pub fn has_response_type(&self, response_type: &str) -> bool {
+        self.response_types.iter().any(|allowed| allowed == response_type)
+    }
+}
+
+/// Whether a redirect uri uses a loopback IP literal with the `http` scheme, like `http://127.0.0.1/callback` or `http://[::1]/callback`.
+fn is_loopback(url: &Url) -> bool {
+    url.scheme() == "http"
+        && match url.host() {
+            Some(Host::Ipv4(ip)) => ip.is_loopback(),
+            Some(Host::Ipv6(ip)) => ip.is_loopback(),
+            _ => false,
+        }
+}
+
+/// Whether a redirect uri uses the `localhost` host name, which is NOT RECOMMENDED for loopback redirects
+/// as it can be resolved to another interface than the loopback interface.
+pub(crate) fn is_localhost(url: &Url) -> bool {
+    matches!(url.host(), Some(Host::Domain(domain)) if domain.eq_ignore_ascii_case("localhost"))
+}
+
+/// The keys a client signs its JWT assertions with, used for the `private_key_jwt` and `client_secret_jwt`
+/// client authentication methods described in RFC 7523.
+#[derive(Debug, Clone)]
+pub enum ClientAssertionKeys {
+    /// The public keys of the client, as registered in its JSON Web Key Set.
+    /// Signatures are checked using the configured [JwsVerifier](crate::common::jose::JwsVerifier).
+    Jwks(Vec<Jwk>),
+    /// The shared secret of the client, used with the HMAC algorithms `HS256`, `HS384` and `HS512`.
+    Secret(String),
+}
+
+/// How a client authenticates using mutual TLS, as described in RFC 8705.
+///
+/// The frontend is responsible for the TLS handshake. For `tls_client_auth` it must validate the certificate chain
+/// against the trusted certificate authorities, while self-signed certificates must be accepted without validation.
+#[derive(Debug, Clone)]
+pub enum TlsClientAuthentication {
+    /// `tls_client_auth` using the expected subject distinguished name of the certificate,
+    /// in the string representation of RFC 4514, like `CN=client,O=Example,C=NL`.
+    SubjectDn(String),
+    /// `tls_client_auth` using a dNSName the certificate must contain as a subject alternative name.
+    SanDns(String),
+    /// `tls_client_auth` using a uniformResourceIdentifier the certificate must contain as a subject alternative name.
+    SanUri(String),
+    /// `tls_client_auth` using an iPAddress the certificate must contain as a subject alternative name.
+    SanIp(IpAddr),
+    /// `tls_client_auth` using an rfc822Name the certificate must contain as a subject alternative name.
+    SanEmail(String),
+    /// `self_signed_tls_client_auth` using the certificates registered by the client,
+    /// as the first certificate in the `x5c` parameter of its JSON Web Keys.
+    SelfSigned(Vec<Jwk>),
+}
+
+/// The method a client uses to authenticate at the token endpoint, as registered in its `token_endpoint_auth_method` metadata.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenEndpointAuthMethod {
+    /// The client is a public client, and does not authenticate.
+    None,
+    /// The client authenticates with its client secret, using HTTP Basic authentication.
+    #[default]
+    ClientSecretBasic,
+    /// The client authenticates with its client secret, sent in the request body.
+    ClientSecretPost,
+    /// The client authenticates with a JWT assertion signed with its client secret, as described in RFC 7523.
+    ClientSecretJwt,
+    /// The client authenticates with a JWT assertion signed with one of its private keys, as described in RFC 7523.
+    PrivateKeyJwt,
+    /// The client authenticates using mutual TLS with a certificate issued by a trusted certificate authority, as described in RFC 8705.
+    TlsClientAuth,
+    /// The client authenticates using mutual TLS with a self-signed certificate, as described in RFC 8705.
+    SelfSignedTlsClientAuth,
+}
+
+impl TokenEndpointAuthMethod {
+    /// Whether a client using this method authenticates using its client secret, which has to be issued to it.
+    pub fn uses_client_secret(&self) -> bool {
+        matches!(self, Self::ClientSecretBasic | Self::ClientSecretPost | Self::ClientSecretJwt)
+    }
+
+    /// The name of this method, as used in the `token_endpoint_auth_method` client metadata.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::ClientSecretBasic => "client_secret_basic",
+            Self::ClientSecretPost => "client_secret_post",
+            Self::ClientSecretJwt => "client_secret_jwt",
+            Self::PrivateKeyJwt => "private_key_jwt",
+            Self::TlsClientAuth => "tls_client_auth",
+            Self::SelfSignedTlsClientAuth => "self_signed_tls_client_auth",
+        }
     }
 }
 
@@ -88,6 +255,31 @@ pub trait ClientProvider<Extras = ()>: 'static + Send + Sync {
         requested_scopes: Vec<String>,
     ) -> Result<Vec<String>, Self::Error>;
 
+    /// Check if a client is allowed to target a set of resources, as described in RFC 8707.
+    ///
+    /// # Implementation notes
+    /// This is only called when the client requests at least one resource, the resources have already been
+    /// checked to be absolute uris without a fragment. The default implementation allows no resources,
+    /// so requests that include a `resource` parameter are rejected until this is implemented.
+    ///
+    /// # Arguments
+    /// * `client` - The client to check the resources for.
+    /// * `resources` - The resources requested by the client, which the issued tokens will be restricted to.
+    ///
+    /// # Returns
+    /// A [bool] indicating if the client is allowed to target all the requested resources.
+    ///
+    /// # Errors
+    /// If the client provider fails to check the resources, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::OAuthError::ProviderImplementationError).
+    async fn allow_client_resources(
+        &self,
+        _client: &Client,
+        _resources: &[String],
+    ) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+
     /// Verify a client secret.
     ///
     /// # Implementation notes
@@ -110,6 +302,97 @@ pub trait ClientProvider<Extras = ()>: 'static + Send + Sync {
         client: &Client,
         client_secret: &str,
     ) -> Result<bool, Self::Error>;
+
+    /// Get the keys a client uses to sign its JWT assertions, if the client authenticates using `private_key_jwt` or `client_secret_jwt`.
+    ///
+    /// # Implementation notes
+    /// The default implementation returns [None], which rejects any client assertion.
+    /// As `client_secret_jwt` requires the plain secret to verify the signature, prefer `private_key_jwt` for new clients.
+    ///
+    /// # Arguments
+    /// * `client` - The client to get the assertion keys for.
+    ///
+    /// # Returns
+    /// The [ClientAssertionKeys] of the client, or [None] if the client does not authenticate using assertions.
+    ///
+    /// # Errors
+    /// If the client provider fails to get the keys, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::OAuthError::ProviderImplementationError).
+    async fn get_client_assertion_keys(
+        &self,
+        _client: &Client,
+    ) -> Result<Option<ClientAssertionKeys>, Self::Error> {
+        Ok(None)
+    }
+
+    /// Get how a client authenticates using mutual TLS, if the client uses `tls_client_auth` or `self_signed_tls_client_auth`.
+    /// This is only used when mutual TLS is enabled through [enable_mutual_tls](crate::builder::OAuthManagerBuilder::enable_mutual_tls).
+    ///
+    /// # Implementation notes
+    /// The default implementation returns [None], which means the client does not authenticate using mutual TLS.
+    ///
+    /// # Arguments
+    /// * `client` - The client to get the mutual TLS registration for.
+    ///
+    /// # Returns
+    /// The [TlsClientAuthentication] of the client, or [None] if the client does not authenticate using mutual TLS.
+    ///
+    /// # Errors
+    /// If the client provider fails to get the registration, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::OAuthError::ProviderImplementationError).
+    async fn get_tls_client_authentication(
+        &self,
+        _client: &Client,
+    ) -> Result<Option<TlsClientAuthentication>, Self::Error> {
+        Ok(None)
+    }
+
+    /// Check whether the UserInfo response for a client should be a signed JWT instead of plain JSON,
+    /// as registered through the `userinfo_signed_response_alg` client metadata of OpenID Connect.
+    ///
+    /// # Implementation notes
+    /// The default implementation returns false, so the claims are returned as JSON.
+    /// The response is signed with the active key of the signing key source, regardless of the registered algorithm.
+    ///
+    /// # Arguments
+    /// * `client` - The client the access token was issued to.
+    ///
+    /// # Returns
+    /// True if the UserInfo response should be signed.
+    ///
+    /// # Errors
+    /// If the client provider fails to get the registration, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::OAuthError::ProviderImplementationError).
+    async fn user_info_signed_response(&self, _client: &Client) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+
+    /// Register the `jti` of a client assertion, so that the same assertion can not be used twice.
+    ///
+    /// # Implementation notes
+    /// This function must be atomic, and is required when [ClientProvider::get_client_assertion_keys] returns keys.
+    /// The default implementation treats every assertion as replayed.
+    /// The identifier only has to be remembered until `valid_until`, after which the assertion is expired anyway.
+    ///
+    /// # Arguments
+    /// * `client` - The client that issued the assertion.
+    /// * `jti` - The unique identifier of the assertion.
+    /// * `valid_until` - The expiry time of the assertion.
+    ///
+    /// # Returns
+    /// True if this identifier was not seen before for this client, false if the assertion is replayed.
+    ///
+    /// # Errors
+    /// If the client provider fails to register the identifier, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::OAuthError::ProviderImplementationError).
+    async fn register_client_assertion_id(
+        &self,
+        _client: &Client,
+        _jti: &str,
+        _valid_until: SystemTime,
+    ) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
 }
 
 #[cfg(test)]
@@ -122,6 +405,7 @@ mod test {
             client_id: "client_id".to_string(),
             redirect_uris: vec!["http://localhost".to_string()],
             confidential: false,
+            ..Default::default()
         };
 
         assert!(client.is_valid());
@@ -137,6 +421,7 @@ mod test {
                 "http://localhost:8080".to_string(),
             ],
             confidential: false,
+            ..Default::default()
         };
 
         assert!(client.is_valid());
@@ -148,6 +433,7 @@ mod test {
             client_id: "".to_string(),
             redirect_uris: vec!["http://localhost".to_string()],
             confidential: false,
+            ..Default::default()
         };
 
         assert!(!client.is_valid());
@@ -159,6 +445,7 @@ mod test {
             client_id: " \t\n".to_string(),
             redirect_uris: vec!["http://localhost".to_string()],
             confidential: false,
+            ..Default::default()
         };
 
         assert!(!client.is_valid());
@@ -170,8 +457,66 @@ mod test {
             client_id: "client_id".to_string(),
             redirect_uris: vec![],
             confidential: false,
+            grant_types: vec!["authorization_code".to_string()],
+            ..Default::default()
         };
 
         assert!(!client.is_valid());
     }
+
+    #[test]
+    fn test_client_credentials_client_without_redirect_uris_is_valid() {
+        let client = Client {
+            client_id: "client_id".to_string(),
+            redirect_uris: vec![],
+            confidential: true,
+            grant_types: vec!["client_credentials".to_string()],
+            ..Default::default()
+        };
+
+        assert!(client.is_valid());
+    }
+
+    fn client(redirect_uri: &str) -> Client {
+        Client { redirect_uris: vec![redirect_uri.to_string()], ..Default::default() }
+    }
+
+    #[test]
+    fn test_redirect_uris_are_compared_after_parsing() {
+        let client = client("https://example.com/return");
+
+        assert!(client.has_redirect_uri("HTTPS://Example.com:443/return"));
+        assert!(!client.has_redirect_uri("https://example.com:8443/return"));
+        assert!(!client.has_redirect_uri("https://example.com/return/other"));
+    }
+
+    #[test]
+    fn test_loopback_redirect_uri_allows_any_port() {
+        // The authorization server MUST allow any port to be specified at the time of the request for loopback IP redirect URIs.
+        let ipv4 = client("http://127.0.0.1/callback");
+        let ipv6 = client("http://[::1]/callback");
+
+        assert!(ipv4.has_redirect_uri("http://127.0.0.1:51004/callback"));
+        assert!(ipv6.has_redirect_uri("http://[::1]:51004/callback"));
+        assert!(!ipv4.has_redirect_uri("http://127.0.0.1:51004/other"));
+        assert!(!ipv4.has_redirect_uri("https://127.0.0.1:51004/callback"));
+    }
+
+    #[test]
+    fn test_localhost_redirect_uri_is_rejected() {
+        // While redirect URIs using the name localhost are allowed, using localhost is NOT RECOMMENDED.
+        let client = client("http://localhost/callback");
+
+        assert!(!client.has_redirect_uri("http://localhost/callback"));
+        assert!(!client.has_redirect_uri("http://localhost:51004/callback"));
+    }
+
+    #[test]
+    fn test_private_use_scheme_redirect_uri() {
+        let client = client("com.example.app:/callback");
+
+        assert!(client.has_redirect_uri("com.example.app:/callback"));
+        assert!(!client.has_redirect_uri("com.example.app:/other"));
+        assert!(!client.has_redirect_uri("com.example.evil:/callback"));
+    }
 }
@@ -1,7 +1,9 @@
+use std::time::SystemTime;
+
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-use crate::common::model::CodeChallenge;
+use crate::common::model::{CodeChallenge, OpenIdParameters};
 
 /// A grant is used to send information to and from the providers for the library to work.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -12,8 +14,23 @@ pub struct Grant<OwnerId> {
     pub client_id: String,
     /// The scopes requested by the client.
     pub scope: Vec<String>,
+    /// The resources requested by the client, as described in RFC 8707.
+    /// The tokens issued from this grant are restricted to these resources, unless the token request narrows them down.
+    #[serde(default)]
+    pub resource: Vec<String>,
     /// The selected redirect uri of the client.
     pub redirect_uri: Url,
     /// The code challenge used in PKCE.
     pub code_challenge: CodeChallenge,
+    /// A random identifier for the authorization code issued for this grant.
+    /// Unlike the code itself, this identifier can be stored alongside the tokens issued from the code.
+    pub code_id: String,
+    /// The JWK thumbprint of the DPoP key the authorization code is bound to, through the `dpop_jkt` parameter.
+    /// The code can then only be exchanged with a DPoP proof signed by the same key.
+    pub dpop_jkt: Option<String>,
+    /// The OpenID Connect parameters of the authorization request, used to issue an ID token.
+    pub openid: OpenIdParameters,
+    /// The time the resource owner last authenticated, as reported by
+    /// [AuthorizationProvider::authentication_time](crate::authorize::AuthorizationProvider::authentication_time).
+    pub auth_time: Option<SystemTime>,
 }
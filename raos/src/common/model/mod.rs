@@ -1,7 +1,9 @@
 mod challenge;
 mod client;
 mod grant;
+mod openid;
 
 pub use challenge::*;
 pub use client::*;
 pub use grant::*;
+pub use openid::*;
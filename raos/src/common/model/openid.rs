@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::frontend::OAuthValidationError;
+
+/// The scope value that turns an authorization request into an OpenID Connect authentication request.
+pub const OPENID_SCOPE: &str = "openid";
+
+/// The OpenID Connect parameters of an authorization request, as described in OpenID Connect Core 1.0 section 3.1.2.1.
+/// These are parsed from every authorization request, but only used when the `openid` scope is granted.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OpenIdParameters {
+    /// A value that is passed through unmodified to the ID token, used by the client to mitigate replay attacks.
+    pub nonce: Option<String>,
+    /// Whether the authorization server should prompt the resource owner for authentication or consent.
+    /// [Prompt::None] is handled by the library, the other values are left to the
+    /// [AuthorizationProvider](crate::authorize::AuthorizationProvider).
+    pub prompt: Vec<Prompt>,
+    /// The maximum time in seconds since the resource owner last actively authenticated.
+    /// If more time has passed, the resource owner is asked to authenticate again.
+    pub max_age: Option<u64>,
+    /// A hint about the login identifier the resource owner might use to log in.
+    pub login_hint: Option<String>,
+    /// The requested Authentication Context Class Reference values, in order of preference.
+    pub acr_values: Vec<String>,
+}
+
+/// A value of the `prompt` parameter of an OpenID Connect authentication request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Prompt {
+    /// The authorization server must not display any user interface,
+    /// an error is returned if the resource owner is not already authenticated and has not consented.
+    None,
+    /// The resource owner should be asked to authenticate again.
+    Login,
+    /// The resource owner should be asked for consent before returning to the client.
+    Consent,
+    /// The resource owner should be asked to select an account.
+    SelectAccount,
+}
+
+impl OpenIdParameters {
+    /// Parse the OpenID Connect parameters, using the given parameter lookup.
+    pub(crate) fn from_params(
+        param: impl Fn(&'static str) -> Option<String>,
+    ) -> Result<Self, OAuthValidationError> {
+        let prompt = match param("prompt") {
+            Some(prompt) => {
+                let values = prompt
+                    .split(' ')
+                    .map(|value| match value {
+                        "none" => Ok(Prompt::None),
+                        "login" => Ok(Prompt::Login),
+                        "consent" => Ok(Prompt::Consent),
+                        "select_account" => Ok(Prompt::SelectAccount),
+                        _ => Err(OAuthValidationError::InvalidParameterValue(
+                            "prompt",
+                            prompt.clone(),
+                        )),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                // If the none value is present with any other value, an error is returned
+                if values.len() > 1 && values.contains(&Prompt::None) {
+                    return Err(OAuthValidationError::InvalidParameterValue("prompt", prompt));
+                }
+                values
+            }
+            None => Vec::new(),
+        };
+
+        let max_age = match param("max_age") {
+            Some(max_age) => Some(max_age.parse().map_err(|_| {
+                OAuthValidationError::InvalidParameterValue("max_age", max_age.clone())
+            })?),
+            None => None,
+        };
+
+        Ok(Self {
+            nonce: param("nonce"),
+            prompt,
+            max_age,
+            login_hint: param("login_hint"),
+            acr_values: param("acr_values")
+                .map(|acr_values| acr_values.split(' ').map(str::to_string).collect())
+                .unwrap_or_default(),
+        })
+    }
+}
@@ -0,0 +1,42 @@
+use url::Url;
+
+use crate::{
+    common::{
+        frontend::{OAuthError, OAuthValidationError},
+        model::Client,
+    },
+    manager::OAuthManager,
+};
+
+impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
+    /// Validate the resources a client requested with the `resource` parameter, as described in RFC 8707 section 2.
+    /// Each resource must be an absolute uri without a fragment, and the client must be allowed to target all of them.
+    pub(crate) async fn validate_resources(
+        &self,
+        client: &Client,
+        resources: &[String],
+    ) -> Result<(), OAuthError<E>> {
+        if resources.is_empty() {
+            return Ok(());
+        }
+
+        for resource in resources {
+            let Ok(url) = resource.parse::<Url>() else {
+                return Err(OAuthValidationError::InvalidTarget("not an absolute uri").into());
+            };
+            if url.fragment().is_some() {
+                return Err(OAuthValidationError::InvalidTarget("includes a fragment").into());
+            }
+        }
+
+        let allowed = self
+            .client_provider
+            .allow_client_resources(client, resources)
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?;
+        if !allowed {
+            return Err(OAuthValidationError::InvalidTarget("not allowed for the client").into());
+        }
+        Ok(())
+    }
+}
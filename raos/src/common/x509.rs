@@ -0,0 +1,235 @@
+use std::net::IpAddr;
+
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use sha2::{Digest, Sha256};
+
+/// The object identifier of the subject alternative name extension, 2.5.29.17.
+const SUBJECT_ALT_NAME_OID: &[u8] = &[0x55, 0x1d, 0x11];
+
+/// The short names of the attribute types listed in RFC 4514 section 3.
+const ATTRIBUTE_TYPES: &[(&str, &str)] = &[
+    ("2.5.4.3", "CN"),
+    ("2.5.4.6", "C"),
+    ("2.5.4.7", "L"),
+    ("2.5.4.8", "ST"),
+    ("2.5.4.9", "STREET"),
+    ("2.5.4.10", "O"),
+    ("2.5.4.11", "OU"),
+    ("0.9.2342.19200300.100.1.1", "UID"),
+    ("0.9.2342.19200300.100.1.25", "DC"),
+];
+
+/// Compute the X.509 certificate SHA-256 thumbprint of a DER encoded certificate,
+/// as used in the `x5t#S256` confirmation method described in RFC 8705.
+pub(crate) fn certificate_thumbprint(der: &[u8]) -> String {
+    BASE64_URL_SAFE_NO_PAD.encode(Sha256::digest(der))
+}
+
+/// The parts of an X.509 certificate used to authenticate clients through mutual TLS, as described in RFC 8705.
+/// The certificate chain is not validated while parsing, this is left to the TLS implementation of the frontend.
+#[derive(Debug, Default)]
+pub(crate) struct ClientCertificate {
+    /// The subject distinguished name, in the string representation of RFC 4514.
+    pub(crate) subject_dn: String,
+    pub(crate) san_dns: Vec<String>,
+    pub(crate) san_uri: Vec<String>,
+    pub(crate) san_ip: Vec<IpAddr>,
+    pub(crate) san_email: Vec<String>,
+}
+
+impl ClientCertificate {
+    /// Parse a DER encoded certificate, returns [None] if it is malformed.
+    pub(crate) fn parse(der: &[u8]) -> Option<Self> {
+        let certificate = Der::new(der).expect(0x30)?;
+        let mut tbs_certificate = Der::new(Der::new(certificate).expect(0x30)?);
+
+        // Skip the optional version, the serial number, signature algorithm, issuer and validity
+        if tbs_certificate.peek_tag() == Some(0xa0) {
+            tbs_certificate.next()?;
+        }
+        tbs_certificate.expect(0x02)?;
+        tbs_certificate.expect(0x30)?;
+        tbs_certificate.expect(0x30)?;
+        tbs_certificate.expect(0x30)?;
+        let subject = tbs_certificate.expect(0x30)?;
+        tbs_certificate.expect(0x30)?;
+
+        let mut certificate =
+            ClientCertificate { subject_dn: format_name(subject)?, ..Default::default() };
+
+        // The extensions are the only explicitly tagged [3] field that can follow
+        while let Some((tag, extensions, _)) = tbs_certificate.next() {
+            if tag == 0xa3 {
+                certificate.read_extensions(extensions)?;
+            }
+        }
+
+        Some(certificate)
+    }
+
+    fn read_extensions(&mut self, extensions: &[u8]) -> Option<()> {
+        let mut extensions = Der::new(Der::new(extensions).expect(0x30)?);
+        while !extensions.is_empty() {
+            let mut extension = Der::new(extensions.expect(0x30)?);
+            let oid = extension.expect(0x06)?;
+            if extension.peek_tag() == Some(0x01) {
+                extension.next()?;
+            }
+            let value = extension.expect(0x04)?;
+            if oid == SUBJECT_ALT_NAME_OID {
+                self.read_subject_alt_names(value)?;
+            }
+        }
+        Some(())
+    }
+
+    fn read_subject_alt_names(&mut self, value: &[u8]) -> Option<()> {
+        let mut names = Der::new(Der::new(value).expect(0x30)?);
+        while let Some((tag, name, _)) = names.next() {
+            match tag {
+                0x81 => self.san_email.push(String::from_utf8(name.to_vec()).ok()?),
+                0x82 => self.san_dns.push(String::from_utf8(name.to_vec()).ok()?),
+                0x86 => self.san_uri.push(String::from_utf8(name.to_vec()).ok()?),
+                0x87 => self.san_ip.push(match name.len() {
+                    4 => IpAddr::from(<[u8; 4]>::try_from(name).ok()?),
+                    16 => IpAddr::from(<[u8; 16]>::try_from(name).ok()?),
+                    _ => return None,
+                }),
+                _ => {}
+            }
+        }
+        Some(())
+    }
+}
+
+/// Format a distinguished name using the string representation of RFC 4514.
+fn format_name(name: &[u8]) -> Option<String> {
+    let mut relative_names = Vec::new();
+    let mut name = Der::new(name);
+    while !name.is_empty() {
+        let mut attributes = Vec::new();
+        let mut relative_name = Der::new(name.expect(0x31)?);
+        while !relative_name.is_empty() {
+            let mut attribute = Der::new(relative_name.expect(0x30)?);
+            let attribute_type = format_oid(attribute.expect(0x06)?)?;
+            let attribute_type = ATTRIBUTE_TYPES
+                .iter()
+                .find(|(oid, _)| *oid == attribute_type)
+                .map_or(attribute_type.clone(), |(_, short_name)| short_name.to_string());
+            let (tag, value, raw) = attribute.next()?;
+            attributes
+                .push(format!("{attribute_type}={}", format_attribute_value(tag, value, raw)));
+        }
+        relative_names.push(attributes.join("+"));
+    }
+
+    // The relative distinguished names are output in reverse order, starting with the last element of the sequence
+    relative_names.reverse();
+    Some(relative_names.join(","))
+}
+
+fn format_attribute_value(tag: u8, value: &[u8], raw: &[u8]) -> String {
+    let string = match tag {
+        // UTF8String, PrintableString, TeletexString and IA5String
+        0x0c | 0x13 | 0x14 | 0x16 => String::from_utf8(value.to_vec()).ok(),
+        // BMPString
+        0x1e => String::from_utf16(
+            &value
+                .chunks(2)
+                .map(|c| u16::from_be_bytes([c[0], *c.get(1).unwrap_or(&0)]))
+                .collect::<Vec<_>>(),
+        )
+        .ok(),
+        _ => None,
+    };
+
+    // Values that are not strings are output as a number sign followed by the hexadecimal BER encoding
+    let Some(string) = string else {
+        return format!("#{}", raw.iter().map(|b| format!("{b:02x}")).collect::<String>());
+    };
+
+    let last = string.chars().count().saturating_sub(1);
+    let mut escaped = String::with_capacity(string.len());
+    for (i, c) in string.chars().enumerate() {
+        match c {
+            '"' | '+' | ',' | ';' | '<' | '>' | '\\' => escaped.push('\\'),
+            ' ' | '#' if i == 0 => escaped.push('\\'),
+            ' ' if i == last => escaped.push('\\'),
+            '\0' => {
+                escaped.push_str("\\00");
+                continue;
+            }
+            _ => {}
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn format_oid(oid: &[u8]) -> Option<String> {
+    let (first, rest) = oid.split_first()?;
+    // The first two arcs are combined into a single byte
+    let first_arc = (first / 40).min(2);
+    let mut arcs = vec![first_arc as u64, (first - first_arc * 40) as u64];
+    let mut arc = 0u64;
+    for byte in rest {
+        arc = arc.checked_mul(128)? | (byte & 0x7f) as u64;
+        if byte & 0x80 == 0 {
+            arcs.push(arc);
+            arc = 0;
+        }
+    }
+    Some(arcs.iter().map(u64::to_string).collect::<Vec<_>>().join("."))
+}
+
+/// A minimal reader for the DER encoding, which only supports the single byte tags used in certificates.
+struct Der<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Der<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn peek_tag(&self) -> Option<u8> {
+        self.data.first().copied()
+    }
+
+    /// Read the next element, returning its tag, its contents and the complete encoding.
+    fn next(&mut self) -> Option<(u8, &'a [u8], &'a [u8])> {
+        let tag = *self.data.first()?;
+        let first_length = *self.data.get(1)?;
+        let (length, header_length) = if first_length < 0x80 {
+            (first_length as usize, 2)
+        } else {
+            let length_bytes = (first_length & 0x7f) as usize;
+            if length_bytes == 0 || length_bytes > 4 {
+                return None;
+            }
+            let length = self
+                .data
+                .get(2..2 + length_bytes)?
+                .iter()
+                .fold(0usize, |length, byte| (length << 8) | *byte as usize);
+            (length, 2 + length_bytes)
+        };
+
+        let end = header_length.checked_add(length)?;
+        let raw = self.data.get(..end)?;
+        self.data = &self.data[end..];
+        Some((tag, &raw[header_length..], raw))
+    }
+
+    /// Read the next element, which must have the given tag, returning its contents.
+    fn expect(&mut self, tag: u8) -> Option<&'a [u8]> {
+        match self.next()? {
+            (actual, contents, _) if actual == tag => Some(contents),
+            _ => None,
+        }
+    }
+}
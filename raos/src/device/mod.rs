@@ -0,0 +1,261 @@
+pub use provider::*;
+pub use request::*;
+pub use response::*;
+
+use std::time::Instant;
+
+use url::Url;
+
+use crate::{
+    common::frontend::{FrontendRequest, OAuthError, OAuthValidationError},
+    manager::OAuthManager,
+    metadata::Endpoint,
+};
+
+mod provider;
+mod request;
+mod response;
+#[cfg(test)]
+mod test;
+
+/// The grant type used by devices to poll the token endpoint with their device code.
+pub const DEVICE_CODE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
+    /// Handle an incoming device authorization request from a device.
+    /// This function will parse the incoming request, validate it, and then generate a device code and user code,
+    /// returning a [DeviceAuthorizationResponse] that contains the information for the device to display.
+    ///
+    /// # Parameters
+    /// - `req` - The unparsed incoming request from the device, represented by a [FrontendRequest]
+    ///
+    /// # Returns
+    /// A [DeviceAuthorizationResponse] that can be used to build a response to the device, which in turn
+    /// implements the [FrontendResponse](crate::common::frontend::FrontendResponse) trait.
+    ///
+    /// # Errors
+    /// This function can return an [OAuthError] if the request is invalid, if no
+    /// [DeviceAuthorizationProvider] was configured, or if one of the providers returns an error.
+    ///
+    /// # Example
+    /// ```
+    /// # use raos::test::{
+    /// #    doctest::oauth_manager_from_application_state,
+    /// #    mock::request_from_raw_http
+    /// # };
+    ///
+    /// let manager = oauth_manager_from_application_state();
+    /// let req = request_from_raw_http(r#"
+    ///     POST /device_authorization HTTP/1.1
+    ///     Content-Type: application/x-www-form-urlencoded
+    ///
+    ///     client_id=CLIENT_ID&scope=SCOPE
+    /// "#);
+    ///
+    /// # tokio_test::block_on(async {
+    /// let result = manager.handle_device_authorization_request(req).await;
+    /// assert_eq!("USER_CODE", result.unwrap().user_code);
+    /// # });
+    /// ```
+    pub async fn handle_device_authorization_request(
+        &self,
+        req: impl FrontendRequest,
+    ) -> Result<DeviceAuthorizationResponse, OAuthError<E>> {
+        let request = DeviceAuthorizationRequest::try_from(&req as &dyn FrontendRequest)?;
+        self.handle_device_authorization(request).await
+    }
+
+    /// Handle an incoming device authorization request from a device.
+    /// This function will validate the request, and then generate a device code and user code through the
+    /// [DeviceAuthorizationProvider].
+    ///
+    /// # Parameters
+    /// - `req` - The parsed incoming request from the device, represented by a [DeviceAuthorizationRequest]
+    ///
+    /// # Returns
+    /// A [DeviceAuthorizationResponse] that can be used to build a response to the device, which in turn
+    /// implements the [FrontendResponse](crate::common::frontend::FrontendResponse) trait.
+    ///
+    /// # Errors
+    /// This function can return an [OAuthError] if the request is invalid, if no
+    /// [DeviceAuthorizationProvider] was configured, or if one of the providers returns an error.
+    pub async fn handle_device_authorization(
+        &self,
+        req: DeviceAuthorizationRequest,
+    ) -> Result<DeviceAuthorizationResponse, OAuthError<E>> {
+        let Some(device_authorization_provider) = &self.device_authorization_provider else {
+            return Err(OAuthValidationError::InvalidGrantType {
+                requested: DEVICE_CODE_GRANT_TYPE.to_string(),
+            }
+            .into());
+        };
+
+        let client = self
+            .authenticate_client(
+                &req.client_id,
+                req.client_secret,
+                req.client_secret_basic,
+                req.client_assertion,
+                req.client_certificate.as_deref(),
+                false,
+            )
+            .await?;
+        if !client.has_grant_type(DEVICE_CODE_GRANT_TYPE) {
+            return Err(OAuthValidationError::ClientNotAllowedToUseGrantType {
+                requested: DEVICE_CODE_GRANT_TYPE.to_string(),
+            }
+            .into());
+        }
+
+        let scopes = if let Some(scope) = req.scope {
+            scope.split(' ').map(str::to_string).collect()
+        } else {
+            client.default_scopes.clone()
+        };
+        let scopes = self
+            .client_provider
+            .allow_client_scopes(&client, scopes)
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?;
+        if scopes.is_empty() {
+            return Err(OAuthValidationError::NoScopesProvided.into());
+        }
+
+        let Some(verification_uri) = self.config.endpoint_url(Endpoint::DeviceVerification) else {
+            return Err(OAuthValidationError::NoAuthorizationServerIdentifier.into());
+        };
+
+        let codes = device_authorization_provider
+            .generate_device_codes(DeviceAuthorization {
+                client_id: client.client_id,
+                scope: scopes,
+            })
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?;
+
+        // Include the user code in the verification uri, so the user does not need to type it
+        let verification_uri_complete = Url::parse(&verification_uri).ok().map(|mut url| {
+            url.query_pairs_mut().append_pair("user_code", &codes.user_code);
+            url.to_string()
+        });
+
+        Ok(DeviceAuthorizationResponse {
+            device_code: codes.device_code,
+            user_code: codes.user_code,
+            verification_uri,
+            verification_uri_complete,
+            expires_in: codes.valid_until.saturating_duration_since(Instant::now()).as_secs(),
+            interval: self.config.device_code_interval.as_secs(),
+        })
+    }
+
+    /// Handle an incoming verification request from the resource owner, who entered the user code displayed by a device.
+    /// This function will parse the incoming request, and then authorize the pending device authorization through
+    /// [AuthorizationProvider::authorize_grant](crate::authorize::AuthorizationProvider::authorize_grant).
+    ///
+    /// # Parameters
+    /// - `req` - The unparsed incoming request from the resource owner, represented by a [FrontendRequest]
+    /// - `extras` - Extras passed down to the [AuthorizationProvider](crate::authorize::AuthorizationProvider), like session information
+    ///
+    /// # Returns
+    /// A [DeviceVerificationResponse] describing the authorized device.
+    ///
+    /// # Errors
+    /// This function can return an [OAuthError] if the user code is invalid, if the resource owner
+    /// needs to authenticate or consent, if the authorization was denied, or if one of the providers returns an error.
+    ///
+    /// # Example
+    /// ```
+    /// # use raos::test::{
+    /// #    doctest::oauth_manager_from_application_state,
+    /// #    mock::request_from_raw_http
+    /// # };
+    ///
+    /// let manager = oauth_manager_from_application_state();
+    /// let req = request_from_raw_http(r#"
+    ///     GET /device?user_code=USER_CODE HTTP/1.1
+    /// "#);
+    ///
+    /// # tokio_test::block_on(async {
+    /// let result = manager.handle_device_verification_request(req, None).await;
+    /// assert!(result.is_ok());
+    /// # });
+    /// ```
+    pub async fn handle_device_verification_request(
+        &self,
+        req: impl FrontendRequest,
+        extras: Option<Ex>,
+    ) -> Result<DeviceVerificationResponse, OAuthError<E>> {
+        let request = DeviceVerificationRequest::try_from(&req as &dyn FrontendRequest)?;
+        self.handle_device_verification(request, extras).await
+    }
+
+    /// Handle an incoming verification request from the resource owner, who entered the user code displayed by a device.
+    /// This function will authorize the pending device authorization through
+    /// [AuthorizationProvider::authorize_grant](crate::authorize::AuthorizationProvider::authorize_grant),
+    /// and store the decision through the [DeviceAuthorizationProvider].
+    ///
+    /// # Parameters
+    /// - `req` - The parsed incoming request from the resource owner, represented by a [DeviceVerificationRequest]
+    /// - `extras` - Extras passed down to the [AuthorizationProvider](crate::authorize::AuthorizationProvider), like session information
+    ///
+    /// # Returns
+    /// A [DeviceVerificationResponse] describing the authorized device.
+    ///
+    /// # Errors
+    /// This function can return an [OAuthError] if the user code is invalid, if the resource owner
+    /// needs to authenticate or consent, if the authorization was denied, or if one of the providers returns an error.
+    pub async fn handle_device_verification(
+        &self,
+        req: DeviceVerificationRequest,
+        mut extras: Option<Ex>,
+    ) -> Result<DeviceVerificationResponse, OAuthError<E>> {
+        let Some(device_authorization_provider) = &self.device_authorization_provider else {
+            return Err(OAuthValidationError::InvalidGrantType {
+                requested: DEVICE_CODE_GRANT_TYPE.to_string(),
+            }
+            .into());
+        };
+
+        let Some(authorization) = device_authorization_provider
+            .get_device_authorization(&req.user_code)
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?
+        else {
+            return Err(OAuthValidationError::InvalidUserCode.into());
+        };
+
+        let Some(client) = self
+            .client_provider
+            .get_client_by_id(&authorization.client_id)
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?
+        else {
+            return Err(OAuthValidationError::ClientDoesNotExist.into());
+        };
+
+        let decision =
+            match self.authorize_owner(&client, &authorization.scope, true, &mut extras).await {
+                Ok(owner_id) => DeviceAuthorizationDecision::Approved(owner_id),
+                // A denied authorization is stored, so the device stops polling
+                Err(OAuthError::AccessDenied) => DeviceAuthorizationDecision::Denied,
+                // Any other errors leave the authorization pending, like required authentication or consent
+                Err(e) => return Err(e),
+            };
+        let approved = matches!(decision, DeviceAuthorizationDecision::Approved(_));
+
+        device_authorization_provider
+            .complete_device_authorization(&req.user_code, decision)
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?;
+
+        if !approved {
+            return Err(OAuthError::AccessDenied);
+        }
+
+        Ok(DeviceVerificationResponse {
+            client_id: authorization.client_id,
+            scope: authorization.scope,
+        })
+    }
+}
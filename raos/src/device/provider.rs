@@ -0,0 +1,156 @@
+use std::time::Instant;
+
+use async_trait::async_trait;
+
+/// Device authorization provider trait.
+/// This is an optional trait that can be implemented by the end user, to enable the device authorization grant.
+///
+/// This trait is used to store device authorizations, resolve them by the code the user enters and poll them
+/// from the device.
+#[async_trait]
+pub trait DeviceAuthorizationProvider: 'static + Send + Sync {
+    /// This is the type of the owner id that is used to identify the resource owner.
+    /// This type will need to match the OwnerId used in [AuthorizationProvider](crate::authorize::AuthorizationProvider).
+    type OwnerId;
+    /// This is the error type that can be returned by the device authorization provider implementing this trait.
+    /// This type will need to match the Error used in [ClientProvider](crate::common::model::ClientProvider).
+    type Error;
+
+    /// Generate a device code and user code for a pending device authorization.
+    ///
+    /// # Implementation notes
+    /// The device code should be a fully random, high-entropy value, as it is used by the device to poll for a token.
+    /// The user code is entered by the resource owner on a secondary device, so it should be short and easy to type,
+    /// for example 8 characters from a limited set of consonants like `BCDFGHJKLMNPQRSTVWXZ`.
+    /// Both codes MUST expire, and should resolve back to the given authorization through
+    /// [get_device_authorization](DeviceAuthorizationProvider::get_device_authorization) and
+    /// [poll_device_code](DeviceAuthorizationProvider::poll_device_code).
+    ///
+    /// # Arguments
+    /// * `authorization` - The pending device authorization to store.
+    ///
+    /// # Returns
+    /// The [DeviceCodes] that are sent back to the device.
+    ///
+    /// # Errors
+    /// If the provider fails to store the device authorization, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn generate_device_codes(
+        &self,
+        authorization: DeviceAuthorization,
+    ) -> Result<DeviceCodes, Self::Error>;
+
+    /// Get a pending device authorization by the user code entered by the resource owner.
+    ///
+    /// # Arguments
+    /// * `user_code` - The user code entered by the resource owner.
+    ///
+    /// # Returns
+    /// An [Option] containing the [DeviceAuthorization] if the user code is known and still pending,
+    /// or [None] if the user code is invalid, expired or already used.
+    ///
+    /// # Errors
+    /// If the provider fails to look up the device authorization, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn get_device_authorization(
+        &self,
+        user_code: &str,
+    ) -> Result<Option<DeviceAuthorization>, Self::Error>;
+
+    /// Store the decision of the resource owner for a pending device authorization.
+    ///
+    /// # Implementation notes
+    /// After this function is called, the user code should no longer resolve through
+    /// [get_device_authorization](DeviceAuthorizationProvider::get_device_authorization),
+    /// and the next poll of the device code should return the outcome of the decision.
+    ///
+    /// # Arguments
+    /// * `user_code` - The user code entered by the resource owner.
+    /// * `decision` - Whether the resource owner approved or denied the authorization.
+    ///
+    /// # Errors
+    /// If the provider fails to store the decision, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn complete_device_authorization(
+        &self,
+        user_code: &str,
+        decision: DeviceAuthorizationDecision<Self::OwnerId>,
+    ) -> Result<(), Self::Error>;
+
+    /// Poll the status of a device code, on behalf of the device.
+    ///
+    /// # Implementation notes
+    /// Once a device code returns [Approved](DeviceCodeStatus::Approved), it MUST NOT be returned as approved again.
+    /// If the device polls faster than the configured interval, [SlowDown](DeviceCodeStatus::SlowDown) should be returned.
+    ///
+    /// # Arguments
+    /// * `device_code` - The device code sent by the device.
+    ///
+    /// # Returns
+    /// The current [DeviceCodeStatus] of the device code.
+    ///
+    /// # Errors
+    /// If the provider fails to look up the device code, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn poll_device_code(
+        &self,
+        device_code: String,
+    ) -> Result<DeviceCodeStatus<Self::OwnerId>, Self::Error>;
+}
+
+/// A device authorization that is waiting for the resource owner to approve it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceAuthorization {
+    /// The client id of the device that requested the authorization.
+    pub client_id: String,
+    /// The scopes requested by the device.
+    pub scope: Vec<String>,
+}
+
+/// The codes generated by the [DeviceAuthorizationProvider] for a device authorization.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeviceCodes {
+    /// The device code, used by the device to poll the token endpoint.
+    pub device_code: String,
+    /// The user code, entered by the resource owner at the verification uri.
+    pub user_code: String,
+    /// The instant at which both codes expire.
+    pub valid_until: Instant,
+}
+
+/// The decision of the resource owner on a device authorization.
+#[derive(Debug, PartialEq)]
+pub enum DeviceAuthorizationDecision<OwnerId> {
+    /// The resource owner approved the authorization.
+    Approved(OwnerId),
+    /// The resource owner, or the authorization provider, denied the authorization.
+    Denied,
+}
+
+/// The status of a device code when it is polled by the device.
+#[derive(Debug, PartialEq)]
+pub enum DeviceCodeStatus<OwnerId> {
+    /// The resource owner has not yet completed the authorization.
+    Pending,
+    /// The device is polling too quickly, and should increase its interval.
+    SlowDown,
+    /// The resource owner approved the authorization.
+    Approved(DeviceGrant<OwnerId>),
+    /// The resource owner denied the authorization.
+    Denied,
+    /// The device code has expired.
+    Expired,
+    /// The device code is unknown, or was already exchanged for a token.
+    Invalid,
+}
+
+/// An approved device authorization, ready to be exchanged for a token.
+#[derive(Debug, PartialEq)]
+pub struct DeviceGrant<OwnerId> {
+    /// The client id of the device that requested the authorization.
+    pub client_id: String,
+    /// The resource owner that approved the authorization.
+    pub resource_owner: OwnerId,
+    /// The scopes approved by the resource owner.
+    pub scope: Vec<String>,
+}
@@ -0,0 +1,89 @@
+use crate::common::{
+    auth::{client_credentials_from_request, ClientCredentials},
+    frontend::{FrontendRequest, FrontendRequestMethod, OAuthValidationError},
+    util::NoneIfEmpty,
+};
+
+/// A parsed device authorization request from a device, as described in RFC 8628.
+#[derive(Debug)]
+pub struct DeviceAuthorizationRequest {
+    /// The client ID of the device making the request.
+    pub client_id: String,
+    /// The client secret, for confidential clients.
+    pub client_secret: Option<String>,
+    /// Whether the client secret was sent using HTTP Basic authentication, instead of in the request body.
+    pub client_secret_basic: bool,
+    /// The client assertion, a JWT used to authenticate the client as described in RFC 7523.
+    pub client_assertion: Option<String>,
+    /// The DER encoded certificate the client presented for mutual TLS, as described in RFC 8705.
+    pub client_certificate: Option<Vec<u8>>,
+    /// The scope of the request, space separated.
+    pub scope: Option<String>,
+}
+
+impl TryFrom<&dyn FrontendRequest> for DeviceAuthorizationRequest {
+    type Error = OAuthValidationError;
+
+    fn try_from(request: &dyn FrontendRequest) -> Result<Self, Self::Error> {
+        if !matches!(request.request_method(), FrontendRequestMethod::POST) {
+            return Err(OAuthValidationError::InvalidRequestMethod {
+                expected: FrontendRequestMethod::POST,
+                actual: request.request_method(),
+            });
+        }
+
+        let ClientCredentials {
+            client_id,
+            client_secret,
+            client_secret_basic,
+            client_assertion,
+            client_certificate,
+        } = client_credentials_from_request(request)?;
+
+        Ok(Self {
+            client_id,
+            client_secret,
+            client_secret_basic,
+            client_assertion,
+            client_certificate,
+            scope: request.body_param("scope").none_if_empty(),
+        })
+    }
+}
+
+/// A parsed verification request from the resource owner, submitting the user code displayed by the device.
+#[derive(Debug)]
+pub struct DeviceVerificationRequest {
+    /// The user code entered by the resource owner.
+    pub user_code: String,
+}
+
+impl TryFrom<&dyn FrontendRequest> for DeviceVerificationRequest {
+    type Error = OAuthValidationError;
+
+    fn try_from(request: &dyn FrontendRequest) -> Result<Self, Self::Error> {
+        if !matches!(
+            request.request_method(),
+            FrontendRequestMethod::GET | FrontendRequestMethod::POST
+        ) {
+            return Err(OAuthValidationError::InvalidRequestMethod {
+                expected: FrontendRequestMethod::GET,
+                actual: request.request_method(),
+            });
+        }
+
+        // The user code can either be prefilled through verification_uri_complete, or submitted through a form
+        let user_code = request.query_param("user_code").none_if_empty().or_else(|| {
+            if let FrontendRequestMethod::POST = request.request_method() {
+                request.body_param("user_code").none_if_empty()
+            } else {
+                None
+            }
+        });
+        let Some(user_code) = user_code else {
+            return Err(OAuthValidationError::MissingRequiredParameter("user_code"));
+        };
+
+        Ok(Self { user_code })
+    }
+}
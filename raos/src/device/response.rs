@@ -0,0 +1,43 @@
+use serde::Serialize;
+
+use crate::common::frontend::{FrontendResponse, FrontendResponseExt};
+
+/// The response to a device authorization request.
+#[derive(Debug, Serialize)]
+pub struct DeviceAuthorizationResponse {
+    /// The device code, used by the device to poll the token endpoint.
+    pub device_code: String,
+    /// The user code, to be entered by the resource owner at the verification uri.
+    pub user_code: String,
+    /// The uri the resource owner should visit to enter the user code.
+    pub verification_uri: String,
+    /// The verification uri with the user code included, for devices that can display a QR code or link.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification_uri_complete: Option<String>,
+    /// The time in seconds until the device code and user code expire.
+    pub expires_in: u64,
+    /// The minimum amount of time in seconds the device should wait between polling requests.
+    pub interval: u64,
+}
+
+impl FrontendResponseExt for DeviceAuthorizationResponse {
+    fn into_frontend_response(self) -> FrontendResponse {
+        FrontendResponse::Success { json: serde_json::to_value(self).unwrap() }
+    }
+}
+
+/// The response to a successful device verification by the resource owner.
+/// After this response, the device will receive a token on its next poll.
+#[derive(Debug, Serialize)]
+pub struct DeviceVerificationResponse {
+    /// The client id of the device that was authorized.
+    pub client_id: String,
+    /// The scopes that were authorized for the device.
+    pub scope: Vec<String>,
+}
+
+impl FrontendResponseExt for DeviceVerificationResponse {
+    fn into_frontend_response(self) -> FrontendResponse {
+        FrontendResponse::Success { json: serde_json::to_value(self).unwrap() }
+    }
+}
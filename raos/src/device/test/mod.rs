@@ -0,0 +1,179 @@
+mod poll;
+
+use crate::{
+    authorize::GrantAuthorizationResult,
+    common::frontend::{OAuthError, OAuthValidationError},
+    device::{
+        DeviceAuthorization, DeviceAuthorizationDecision, DeviceAuthorizationRequest, DeviceCodes,
+        DeviceVerificationRequest, DEVICE_CODE_GRANT_TYPE,
+    },
+    test::{mock::request_from_raw_http, TestEnvironment, DEFAULT_CLIENT_ID},
+};
+use mockall::predicate::{always, eq};
+use std::time::{Duration, Instant};
+
+static USER_CODE: &str = "WDJB-MJHT";
+
+fn default_device_authorization() -> DeviceAuthorization {
+    DeviceAuthorization {
+        client_id: DEFAULT_CLIENT_ID.to_string(),
+        scope: vec!["scope".to_string()],
+    }
+}
+
+#[tokio::test]
+async fn test_device_authorization_full_flow() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.device_authorization_provider
+        .expect_generate_device_codes()
+        .with(eq(default_device_authorization()))
+        .returning(|_| {
+            Ok(DeviceCodes {
+                device_code: "device_code".to_string(),
+                user_code: USER_CODE.to_string(),
+                valid_until: Instant::now() + Duration::from_secs(600),
+            })
+        });
+    let mut manager = test.build();
+    manager.config.authorization_server_identifier = Some("https://example.com".to_string());
+
+    let request = request_from_raw_http(
+        r#"
+            POST /device_authorization HTTP/1.1
+            Content-Type: application/x-www-form-urlencoded
+
+            client_id=client&scope=scope
+        "#,
+    );
+
+    // Act
+    let result = manager.handle_device_authorization_request(request).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    let result = result.unwrap();
+    assert_eq!("device_code", result.device_code);
+    assert_eq!(USER_CODE, result.user_code);
+    assert_eq!("https://example.com/device", result.verification_uri);
+    assert_eq!(
+        Some("https://example.com/device?user_code=WDJB-MJHT".to_string()),
+        result.verification_uri_complete
+    );
+    assert_eq!(5, result.interval);
+}
+
+#[tokio::test]
+async fn test_device_authorization_requires_provider() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    let mut manager = test.build();
+    manager.device_authorization_provider = None;
+
+    let request = DeviceAuthorizationRequest {
+        client_id: DEFAULT_CLIENT_ID.to_string(),
+        client_secret: None,
+        client_secret_basic: false,
+        client_assertion: None,
+        client_certificate: None,
+        scope: Some("scope".to_string()),
+    };
+
+    // Act
+    let result = manager.handle_device_authorization(request).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::InvalidGrantType {
+            requested: DEVICE_CODE_GRANT_TYPE.to_string()
+        }),
+        result.unwrap_err()
+    );
+}
+
+#[tokio::test]
+async fn test_device_verification_approves_authorization() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.device_authorization_provider
+        .expect_get_device_authorization()
+        .with(eq(USER_CODE))
+        .returning(|_| Ok(Some(default_device_authorization())));
+    test.authorization_provider
+        .expect_authorize_grant()
+        .with(always(), eq(vec!["scope".to_string()]), always())
+        .returning(|_, _, _| Ok(GrantAuthorizationResult::Authorized(1)));
+    test.device_authorization_provider
+        .expect_complete_device_authorization()
+        .with(eq(USER_CODE), eq(DeviceAuthorizationDecision::Approved(1)))
+        .times(1)
+        .returning(|_, _| Ok(()));
+    let manager = test.build();
+
+    let request = request_from_raw_http(
+        r#"
+            GET /device?user_code=WDJB-MJHT HTTP/1.1
+        "#,
+    );
+
+    // Act
+    let result = manager.handle_device_verification_request(request, None).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    assert_eq!(DEFAULT_CLIENT_ID, result.unwrap().client_id);
+}
+
+#[tokio::test]
+async fn test_device_verification_stores_denial() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.device_authorization_provider
+        .expect_get_device_authorization()
+        .returning(|_| Ok(Some(default_device_authorization())));
+    test.authorization_provider
+        .expect_authorize_grant()
+        .returning(|_, _, _| Ok(GrantAuthorizationResult::Unauthorized));
+    test.device_authorization_provider
+        .expect_complete_device_authorization()
+        .with(eq(USER_CODE), eq(DeviceAuthorizationDecision::Denied))
+        .times(1)
+        .returning(|_, _| Ok(()));
+    let manager = test.build();
+
+    let request = DeviceVerificationRequest { user_code: USER_CODE.to_string() };
+
+    // Act
+    let result = manager.handle_device_verification(request, None).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(OAuthError::AccessDenied, result.unwrap_err());
+}
+
+#[tokio::test]
+async fn test_device_verification_rejects_unknown_user_code() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.device_authorization_provider.expect_get_device_authorization().returning(|_| Ok(None));
+    test.authorization_provider.expect_authorize_grant().never();
+    let manager = test.build();
+
+    let request = DeviceVerificationRequest { user_code: "unknown".to_string() };
+
+    // Act
+    let result = manager.handle_device_verification(request, None).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::InvalidUserCode),
+        result.unwrap_err()
+    );
+}
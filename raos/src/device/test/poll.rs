@@ -0,0 +1,136 @@
+use crate::{
+    common::frontend::{FrontendRequest, OAuthError, OAuthValidationError, PublicOAuthError},
+    device::{DeviceCodeStatus, DeviceGrant},
+    test::{mock::request_from_raw_http, TestEnvironment, DEFAULT_CLIENT_ID, DEFAULT_TOKEN},
+    token::{GrantType, RequestedGrantType, Token, TokenRequest},
+};
+use mockall::predicate::{always, eq};
+use std::time::Instant;
+
+fn device_code_request() -> TokenRequest {
+    TokenRequest {
+        client_secret: None,
+        grant_type: RequestedGrantType::DeviceCode { device_code: "device_code".to_string() },
+        redirect_uri: None,
+        scope: None,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_device_code_grant_type_is_parsed() {
+    // Arrange
+    let request = request_from_raw_http(
+        r#"
+            POST /token HTTP/1.1
+            Content-Type: application/x-www-form-urlencoded
+
+            grant_type=urn%3Aietf%3Aparams%3Aoauth%3Agrant-type%3Adevice_code&device_code=device_code&client_id=client
+        "#,
+    );
+
+    // Act
+    let result = TokenRequest::try_from(&request as &dyn FrontendRequest);
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    assert!(matches!(
+        result.unwrap().grant_type,
+        RequestedGrantType::DeviceCode { device_code } if device_code == "device_code"
+    ));
+}
+
+#[tokio::test]
+async fn test_approved_device_code_issues_token() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.device_authorization_provider
+        .expect_poll_device_code()
+        .with(eq("device_code".to_string()))
+        .returning(|_| {
+            Ok(DeviceCodeStatus::Approved(DeviceGrant {
+                client_id: DEFAULT_CLIENT_ID.to_string(),
+                resource_owner: 1,
+                scope: vec!["scope".to_string()],
+            }))
+        });
+    test.token_provider
+        .expect_token()
+        .with(
+            always(),
+            eq(GrantType::DeviceCode { resource_owner: 1, scope: vec!["scope".to_string()] }),
+            always(),
+            always(),
+        )
+        .returning(|_, _, _, _| {
+            Ok(Token {
+                token: DEFAULT_TOKEN.to_string(),
+                refresh_token: None,
+                valid_until: Instant::now(),
+            })
+        });
+    let manager = test.build();
+
+    // Act
+    let result = manager.handle_token(device_code_request()).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    assert_eq!(DEFAULT_TOKEN, result.unwrap().access_token);
+}
+
+#[tokio::test]
+async fn test_pending_device_code_errors() {
+    // The authorization_pending, slow_down and expired_token errors tell the device how to continue polling.
+
+    // Arrange
+    let cases = [
+        (DeviceCodeStatus::Pending, PublicOAuthError::AuthorizationPending),
+        (DeviceCodeStatus::SlowDown, PublicOAuthError::SlowDown),
+        (DeviceCodeStatus::Expired, PublicOAuthError::ExpiredToken),
+        (DeviceCodeStatus::Denied, PublicOAuthError::AccessDenied),
+        (DeviceCodeStatus::Invalid, PublicOAuthError::InvalidGrant),
+    ];
+
+    for (status, expected) in cases {
+        let mut test = TestEnvironment::new();
+        test.default_client();
+        test.device_authorization_provider.expect_poll_device_code().return_once(|_| Ok(status));
+        test.token_provider.expect_token().never();
+        let manager = test.build();
+
+        // Act
+        let result = manager.handle_token(device_code_request()).await;
+
+        // Assert
+        assert!(result.is_err(), "result is not Err, result is {:?}", result);
+        assert_eq!(expected, PublicOAuthError::from(result.unwrap_err()));
+    }
+}
+
+#[tokio::test]
+async fn test_device_code_belongs_to_requesting_client() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.device_authorization_provider.expect_poll_device_code().returning(|_| {
+        Ok(DeviceCodeStatus::Approved(DeviceGrant {
+            client_id: "other_client".to_string(),
+            resource_owner: 1,
+            scope: vec!["scope".to_string()],
+        }))
+    });
+    test.token_provider.expect_token().never();
+    let manager = test.build();
+
+    // Act
+    let result = manager.handle_token(device_code_request()).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::DeviceCodeClientMismatch),
+        result.unwrap_err()
+    );
+}
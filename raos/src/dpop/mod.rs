@@ -0,0 +1,112 @@
+pub use provider::*;
+
+use crate::{
+    common::{
+        frontend::{OAuthError, OAuthValidationError},
+        jose::CompactJws,
+    },
+    dpop::proof::{DPoPProofClaims, DPOP_PROOF_TYPE},
+    manager::OAuthManager,
+};
+
+mod proof;
+mod provider;
+#[cfg(test)]
+mod test;
+
+/// The token type of access tokens that are bound to a DPoP key.
+pub const DPOP_TOKEN_TYPE: &str = "DPoP";
+
+impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
+    /// Verify a DPoP proof, as described in RFC 9449 section 4.3.
+    /// This is done automatically by the token endpoint, but can also be used by resource servers
+    /// to verify the proof sent along with a DPoP-bound access token.
+    ///
+    /// # Parameters
+    /// - `proof` - The value of the `DPoP` header of the request
+    /// - `method` - The HTTP method of the request, like `POST`
+    /// - `uri` - The full uri of the request, the query and fragment are ignored
+    /// - `access_token` - The access token sent along with the proof, if any
+    ///
+    /// # Returns
+    /// The JWK SHA-256 thumbprint of the key that signed the proof, which can be compared to the `jkt`
+    /// the access token was bound to.
+    ///
+    /// # Errors
+    /// This function can return an [OAuthError] if the proof is invalid or replayed, if a different nonce is required,
+    /// if no [DPoPProvider] or [JwsVerifier](crate::common::jose::JwsVerifier) was configured,
+    /// or if the [DPoPProvider] returns an error.
+    pub async fn verify_dpop_proof(
+        &self,
+        proof: &str,
+        method: &str,
+        uri: &str,
+        access_token: Option<&str>,
+    ) -> Result<String, OAuthError<E>> {
+        let invalid = |reason| OAuthError::from(OAuthValidationError::InvalidDPoPProof(reason));
+        let (Some(dpop_provider), Some(jws_verifier)) = (&self.dpop_provider, &self.jws_verifier)
+        else {
+            return Err(invalid("DPoP is not supported"));
+        };
+
+        let Some(jws) = CompactJws::parse(proof) else {
+            return Err(invalid("the proof is not a well-formed JWT"));
+        };
+        if jws.header.typ.as_deref() != Some(DPOP_PROOF_TYPE) {
+            return Err(invalid("the proof has the wrong type"));
+        }
+
+        // The proof is signed with an asymmetric key, of which only the public part is embedded in the header
+        let Some(jwk) = &jws.header.jwk else {
+            return Err(invalid("the proof does not contain a public key"));
+        };
+        if jwk.has_private_parameters() {
+            return Err(invalid("the proof contains a private key"));
+        }
+        let Some(jkt) = jwk.thumbprint() else {
+            return Err(invalid("the public key of the proof is not supported"));
+        };
+        if !jws.verify(jws_verifier.as_ref(), jwk) {
+            return Err(invalid("the signature of the proof is invalid"));
+        }
+
+        let Some(claims) = jws.claims::<DPoPProofClaims>() else {
+            return Err(invalid("the proof is missing required claims"));
+        };
+        if claims.htm != method {
+            return Err(invalid("the proof was made for another request method"));
+        }
+        if !claims.matches_uri(uri) {
+            return Err(invalid("the proof was made for another uri"));
+        }
+        if !claims.issued_within(self.config.dpop_proof_lifetime) {
+            return Err(invalid("the proof has expired"));
+        }
+        if !claims.matches_access_token(access_token) {
+            return Err(invalid("the proof was made for another access token"));
+        }
+
+        let nonce_check = dpop_provider
+            .check_nonce(claims.nonce.clone())
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?;
+        if let DPoPNonceCheck::UseNonce(nonce) = nonce_check {
+            return Err(OAuthValidationError::UseDPoPNonce(nonce).into());
+        }
+
+        // Each proof may only be used once
+        let first_use = dpop_provider
+            .register_proof_id(
+                &jkt,
+                &claims.jti,
+                claims.valid_until(self.config.dpop_proof_lifetime),
+            )
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?;
+        if !first_use {
+            return Err(invalid("the proof was already used"));
+        }
+
+        Ok(jkt)
+    }
+}
@@ -0,0 +1,56 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use url::Url;
+
+/// The `typ` header value that DPoP proofs must use.
+pub(crate) const DPOP_PROOF_TYPE: &str = "dpop+jwt";
+
+/// The claims of a DPoP proof, as described in RFC 9449 section 4.2.
+#[derive(Debug, Deserialize)]
+pub(crate) struct DPoPProofClaims {
+    pub(crate) jti: String,
+    pub(crate) htm: String,
+    pub(crate) htu: String,
+    pub(crate) iat: u64,
+    pub(crate) ath: Option<String>,
+    pub(crate) nonce: Option<String>,
+}
+
+impl DPoPProofClaims {
+    /// The target uri of the proof must match the request uri, ignoring the query and fragment.
+    pub(crate) fn matches_uri(&self, uri: &str) -> bool {
+        let normalize = |uri: &str| {
+            Url::parse(uri).ok().map(|mut url| {
+                url.set_query(None);
+                url.set_fragment(None);
+                url
+            })
+        };
+        matches!((normalize(&self.htu), normalize(uri)), (Some(a), Some(b)) if a == b)
+    }
+
+    /// The proof must have been issued recently, allowing for the given clock skew in both directions.
+    pub(crate) fn issued_within(&self, lifetime: Duration) -> bool {
+        let Ok(now) = SystemTime::now().duration_since(UNIX_EPOCH) else {
+            return false;
+        };
+        now.as_secs().abs_diff(self.iat) <= lifetime.as_secs()
+    }
+
+    /// The time after which the proof is no longer accepted, used to expire its identifier from the replay store.
+    pub(crate) fn valid_until(&self, lifetime: Duration) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.iat) + lifetime
+    }
+
+    /// If the proof is sent along with an access token, it must contain the hash of that token.
+    pub(crate) fn matches_access_token(&self, access_token: Option<&str>) -> bool {
+        let Some(access_token) = access_token else {
+            return true;
+        };
+        let hash = BASE64_URL_SAFE_NO_PAD.encode(Sha256::digest(access_token.as_bytes()));
+        self.ath.as_deref() == Some(hash.as_str())
+    }
+}
@@ -0,0 +1,69 @@
+use std::time::SystemTime;
+
+use async_trait::async_trait;
+
+/// DPoP provider trait.
+/// This is an optional trait that can be implemented by the end user, to enable DPoP sender-constrained tokens.
+///
+/// This trait is used as the replay store for DPoP proofs, and optionally to hand out server-provided nonces.
+/// Verifying proofs also requires a [JwsVerifier](crate::common::jose::JwsVerifier) to be configured.
+#[async_trait]
+pub trait DPoPProvider: 'static + Send + Sync {
+    /// This is the error type that can be returned by the DPoP provider implementing this trait.
+    /// This type will need to match the Error used in [ClientProvider](crate::common::model::ClientProvider).
+    type Error;
+
+    /// Register the unique identifier (`jti`) of a DPoP proof, to prevent the proof from being replayed.
+    ///
+    /// # Implementation notes
+    /// The check and registration MUST happen atomically, so that two concurrent requests with the same proof
+    /// cannot both succeed. The identifier only needs to be kept until `valid_until`, after which the library
+    /// rejects the proof based on its issue time anyway.
+    ///
+    /// # Arguments
+    /// * `jkt` - The JWK thumbprint of the key that signed the proof, identifiers are only unique per key.
+    /// * `jti` - The unique identifier of the proof.
+    /// * `valid_until` - The time after which the proof would be rejected regardless.
+    ///
+    /// # Returns
+    /// `true` if this is the first time the identifier was seen for this key, `false` if the proof was replayed.
+    ///
+    /// # Errors
+    /// If the provider fails to register the identifier, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn register_proof_id(
+        &self,
+        jkt: &str,
+        jti: &str,
+        valid_until: SystemTime,
+    ) -> Result<bool, Self::Error>;
+
+    /// Check the nonce included in a DPoP proof.
+    ///
+    /// # Implementation notes
+    /// By default nonces are not used, and every proof is accepted.
+    /// Servers that want to limit the lifetime of proofs further can hand out nonces, which the client has to include
+    /// in its next proof. If the nonce is missing or no longer accepted, return the nonce the client should use instead.
+    ///
+    /// # Arguments
+    /// * `nonce` - The nonce included in the proof, if any.
+    ///
+    /// # Returns
+    /// A [DPoPNonceCheck] indicating if the nonce is acceptable.
+    ///
+    /// # Errors
+    /// If the provider fails to check the nonce, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn check_nonce(&self, _nonce: Option<String>) -> Result<DPoPNonceCheck, Self::Error> {
+        Ok(DPoPNonceCheck::Valid)
+    }
+}
+
+/// The result of checking the nonce of a DPoP proof through [DPoPProvider::check_nonce].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DPoPNonceCheck {
+    /// The nonce is acceptable.
+    Valid,
+    /// The nonce is missing or no longer accepted, the client has to retry with the given nonce.
+    UseNonce(String),
+}
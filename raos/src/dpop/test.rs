@@ -0,0 +1,484 @@
+use crate::{
+    authorize::AuthorizationRequest,
+    common::{
+        frontend::{FrontendRequest, OAuthError, OAuthValidationError, PublicOAuthError},
+        jose::Jwk,
+        model::Grant,
+    },
+    dpop::{DPoPNonceCheck, DPOP_TOKEN_TYPE},
+    manager::OAuthManager,
+    resource::BearerTokenValidator,
+    test::{
+        mock::request_from_raw_http, TestEnvironment, DEFAULT_AUTHORIZATION_CODE,
+        DEFAULT_REFRESH_TOKEN, DEFAULT_TOKEN,
+    },
+    token::{
+        GrantType, IssuedToken, RefreshGrant, RequestedGrantType, Token, TokenConfirmation,
+        TokenRequest, TokenTypeHint,
+    },
+};
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use mockall::predicate::{always, eq};
+use serde_json::{json, Value};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+static TOKEN_ENDPOINT: &str = "https://example.com/token";
+// The thumbprint of the public key used in the examples of RFC 9449
+static JKT: &str = "0ZcOCORZNYy-DWpqq30jZyJGHTN0d2HglBV3uiguA4I";
+
+fn public_key() -> Value {
+    json!({
+        "kty": "EC",
+        "x": "l8tFrhx-34tV3hRICRDY9zCkDlpBhF42UQUfWVAWBFs",
+        "y": "9VE4jf_Ok_o64zbTTlcuNJajHmt6v9TDVrU0CdvGRDA",
+        "crv": "P-256"
+    })
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
+
+fn proof_claims() -> Value {
+    json!({ "jti": "e1j3V_bKic8-LAEB", "htm": "POST", "htu": TOKEN_ENDPOINT, "iat": now() })
+}
+
+fn proof(header: Value, claims: Value) -> String {
+    let encode = |value: Value| BASE64_URL_SAFE_NO_PAD.encode(value.to_string());
+    format!("{}.{}.{}", encode(header), encode(claims), BASE64_URL_SAFE_NO_PAD.encode("signature"))
+}
+
+fn valid_proof() -> String {
+    proof(json!({ "typ": "dpop+jwt", "alg": "ES256", "jwk": public_key() }), proof_claims())
+}
+
+/// A test environment that accepts all proofs signed with the signature "signature".
+fn dpop_environment() -> TestEnvironment {
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.jws_verifier
+        .expect_verify()
+        .returning(|alg, _, _, signature| alg == "ES256" && signature == b"signature");
+    test.dpop_provider.expect_check_nonce().returning(|_| Ok(DPoPNonceCheck::Valid));
+    test
+}
+
+fn build(test: TestEnvironment) -> OAuthManager<u32, (), ()> {
+    let mut manager = test.build();
+    manager.config.authorization_server_identifier = Some("https://example.com".to_string());
+    manager
+}
+
+#[test]
+fn test_jwk_thumbprint() {
+    // Arrange
+    let rsa_key: Jwk = serde_json::from_value(json!({
+        "kty": "RSA",
+        "n": "0vx7agoebGcQSuuPiLJXZptN9nndrQmbXEps2aiAFbWhM78LhWx4cbbfAAtVT86zwu1RK7aPFFxuhDR1L6tSoc_BJECPebWKRXjBZCiFV4n3oknjhMstn64tZ_2W-5JsGY4Hc5n9yBXArwl93lqt7_RN5w6Cf0h4QyQ5v-65YGjQR0_FDW2QvzqY368QQMicAtaSqzs8KJZgnYb9c7d0zgdAZHzu6qMQvRL5hajrn1n91CbOpbISD08qNLyrdkt-bFTWhAI4vMQFh6WeZu0fM4lFd2NcRwr3XPksINHaQ-G_xBniIqbw0Ls1jF44-csFCur-kEgU8awapJzKnqDKgw",
+        "e": "AQAB",
+        "alg": "RS256",
+        "kid": "2011-04-29"
+    }))
+    .unwrap();
+    let ec_key: Jwk = serde_json::from_value(public_key()).unwrap();
+
+    // Act
+    let rsa_thumbprint = rsa_key.thumbprint();
+    let ec_thumbprint = ec_key.thumbprint();
+
+    // Assert
+    assert_eq!(Some("NzbLsXh8uDCcd-6MNwXF4W_7noWXFZAfHkxZsRGC9Xs".to_string()), rsa_thumbprint);
+    assert_eq!(Some(JKT.to_string()), ec_thumbprint);
+}
+
+#[test]
+fn test_dpop_header_is_parsed() {
+    // Arrange
+    let request = request_from_raw_http(
+        r#"
+            POST /token HTTP/1.1
+            Content-Type: application/x-www-form-urlencoded
+            dpop: proof
+
+            grant_type=authorization_code&code=code&code_verifier=verifier&client_id=client
+        "#,
+    );
+
+    // Act
+    let result = TokenRequest::try_from(&request as &dyn FrontendRequest);
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    assert_eq!(Some("proof".to_string()), result.unwrap().dpop_proof);
+}
+
+#[test]
+fn test_dpop_jkt_is_parsed() {
+    // Arrange
+    let request = request_from_raw_http(&format!(
+        r#"
+            GET /authorize?client_id=client&response_type=code&dpop_jkt={JKT} HTTP/1.1
+        "#
+    ));
+
+    // Act
+    let result = AuthorizationRequest::try_from(&request as &dyn FrontendRequest);
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    assert_eq!(Some(JKT.to_string()), result.unwrap().dpop_jkt);
+}
+
+#[tokio::test]
+async fn test_dpop_token_is_bound_to_key() {
+    // Arrange
+    let mut test = dpop_environment();
+    test.dpop_provider
+        .expect_register_proof_id()
+        .with(eq(JKT), eq("e1j3V_bKic8-LAEB"), always())
+        .times(1)
+        .returning(|_, _, _| Ok(true));
+    test.token_provider
+        .expect_token()
+        .with(
+            always(),
+            always(),
+            eq(TokenConfirmation { jkt: Some(JKT.to_string()), ..Default::default() }),
+            always(),
+        )
+        .times(1)
+        .returning(|_, _, _, _| {
+            Ok(Token {
+                token: DEFAULT_TOKEN.to_string(),
+                refresh_token: None,
+                valid_until: Instant::now(),
+            })
+        });
+    test.default_grant();
+    let manager = build(test);
+
+    let request = TokenRequest { dpop_proof: Some(valid_proof()), ..Default::default() };
+
+    // Act
+    let result = manager.handle_token(request).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    assert_eq!(DPOP_TOKEN_TYPE, result.unwrap().token_type);
+}
+
+#[tokio::test]
+async fn test_dpop_proof_rejects_mismatched_request() {
+    // The htm claim matches the HTTP method of the current request,
+    // and the htu claim matches the HTTP URI value for the HTTP request in which the JWT was received, ignoring any query and fragment parts.
+
+    // Arrange
+    let mut test = dpop_environment();
+    test.dpop_provider.expect_register_proof_id().never();
+    let manager = build(test);
+
+    let cases = [
+        ("htm", json!("GET")),
+        ("htu", json!("https://example.com/other")),
+        ("iat", json!(now() - 3600)),
+    ];
+
+    for (claim, value) in cases {
+        let mut claims = proof_claims();
+        claims[claim] = value;
+        let proof =
+            proof(json!({ "typ": "dpop+jwt", "alg": "ES256", "jwk": public_key() }), claims);
+
+        // Act
+        let result = manager.verify_dpop_proof(&proof, "POST", TOKEN_ENDPOINT, None).await;
+
+        // Assert
+        assert!(
+            matches!(
+                result,
+                Err(OAuthError::ValidationFailed(OAuthValidationError::InvalidDPoPProof(_)))
+            ),
+            "{claim} was not rejected, result is {:?}",
+            result
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_dpop_proof_rejects_invalid_header() {
+    // The typ JOSE Header Parameter has the value dpop+jwt, the alg JOSE Header Parameter indicates a registered asymmetric digital signature algorithm,
+    // is not none, is supported by the application, and is acceptable per local policy,
+    // and the jwk JOSE Header Parameter does not contain a private key.
+
+    // Arrange
+    let mut test = dpop_environment();
+    test.dpop_provider.expect_register_proof_id().never();
+    let manager = build(test);
+
+    let mut private_key = public_key();
+    private_key["d"] = json!("870MB6gfuTJ4HtUnUvYMyJpr5eUZNP4Bk43bVdj3eAE");
+    let headers = [
+        json!({ "typ": "JWT", "alg": "ES256", "jwk": public_key() }),
+        json!({ "typ": "dpop+jwt", "alg": "none", "jwk": public_key() }),
+        json!({ "typ": "dpop+jwt", "alg": "ES256" }),
+        json!({ "typ": "dpop+jwt", "alg": "ES256", "jwk": private_key }),
+    ];
+
+    for header in headers {
+        let proof = proof(header.clone(), proof_claims());
+
+        // Act
+        let result = manager.verify_dpop_proof(&proof, "POST", TOKEN_ENDPOINT, None).await;
+
+        // Assert
+        assert!(
+            matches!(
+                result,
+                Err(OAuthError::ValidationFailed(OAuthValidationError::InvalidDPoPProof(_)))
+            ),
+            "{header} was not rejected, result is {:?}",
+            result
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_dpop_proof_replay_is_rejected() {
+    // The jti value has not been used before in the context of the target URI (replay check).
+
+    // Arrange
+    let mut test = dpop_environment();
+    test.dpop_provider.expect_register_proof_id().returning(|_, _, _| Ok(false));
+    let manager = build(test);
+
+    // Act
+    let result = manager.verify_dpop_proof(&valid_proof(), "POST", TOKEN_ENDPOINT, None).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::InvalidDPoPProof(
+            "the proof was already used"
+        )),
+        result.unwrap_err()
+    );
+}
+
+#[tokio::test]
+async fn test_dpop_proof_access_token_hash() {
+    // When presenting an access token, the value of the ath claim equals the hash of that access token.
+
+    // Arrange
+    let mut test = dpop_environment();
+    test.dpop_provider.expect_register_proof_id().returning(|_, _, _| Ok(true));
+    let manager = build(test);
+
+    let mut claims = proof_claims();
+    claims["htm"] = json!("GET");
+    claims["htu"] = json!("https://resource.example.org/protectedresource");
+    claims["ath"] = json!("fUHyO2r2Z3DZ53EsNrWBb0xWXoaNy59IiKCAqksmQEo");
+    let proof = proof(json!({ "typ": "dpop+jwt", "alg": "ES256", "jwk": public_key() }), claims);
+    let uri = "https://resource.example.org/protectedresource?query";
+
+    // Act
+    let valid = manager
+        .verify_dpop_proof(&proof, "GET", uri, Some("Kz~8mXK1EalYznwH-LC-1fBAo.4Ljp~zsPE_NeO.gxU"))
+        .await;
+    let invalid = manager.verify_dpop_proof(&proof, "GET", uri, Some("other_token")).await;
+
+    // Assert
+    assert_eq!(Ok(JKT.to_string()), valid);
+    assert!(invalid.is_err(), "result is not Err, result is {:?}", invalid);
+}
+
+#[tokio::test]
+async fn test_dpop_nonce_is_required() {
+    // If the server requires a nonce, it responds with the use_dpop_nonce error and the nonce in the DPoP-Nonce header.
+
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.jws_verifier.expect_verify().returning(|_, _, _, _| true);
+    test.dpop_provider
+        .expect_check_nonce()
+        .withf(|nonce| nonce.is_none())
+        .returning(|_| Ok(DPoPNonceCheck::UseNonce("eyJ7S_zG.eyJH0-Z.HX4w-7v".to_string())));
+    test.dpop_provider.expect_register_proof_id().never();
+    let manager = build(test);
+
+    let request = TokenRequest { dpop_proof: Some(valid_proof()), ..Default::default() };
+
+    // Act
+    let result = manager.handle_token(request).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    let error = PublicOAuthError::from(result.unwrap_err());
+    assert_eq!(PublicOAuthError::UseDPoPNonce("eyJ7S_zG.eyJH0-Z.HX4w-7v".to_string()), error);
+    assert_eq!(vec![("DPoP-Nonce", "eyJ7S_zG.eyJH0-Z.HX4w-7v".to_string())], error.headers());
+}
+
+#[tokio::test]
+async fn test_dpop_bound_code_requires_same_key() {
+    // The authorization server MUST ensure that the same key is used in all subsequent access token requests
+    // using the authorization code, and reject the request if a different key is used.
+
+    // Arrange
+    let mut test = dpop_environment();
+    test.register_grant(
+        DEFAULT_AUTHORIZATION_CODE.to_string(),
+        Grant { dpop_jkt: Some("other_key".to_string()), ..Default::default() },
+    );
+    test.dpop_provider.expect_register_proof_id().returning(|_, _, _| Ok(true));
+    let manager = build(test);
+
+    let with_proof = TokenRequest { dpop_proof: Some(valid_proof()), ..Default::default() };
+    let without_proof = TokenRequest::default();
+
+    // Act
+    let with_proof = manager.handle_token(with_proof).await;
+    let without_proof = manager.handle_token(without_proof).await;
+
+    // Assert
+    for result in [with_proof, without_proof] {
+        assert!(result.is_err(), "result is not Err, result is {:?}", result);
+        assert_eq!(
+            OAuthError::ValidationFailed(OAuthValidationError::DPoPKeyMismatch),
+            result.unwrap_err()
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_dpop_bound_refresh_token_requires_same_key() {
+    // Refresh tokens issued to public clients are bound to the DPoP public key,
+    // so a refresh request has to be accompanied by a proof signed with the same key.
+
+    // Arrange
+    let mut test = dpop_environment();
+    test.token_provider.expect_exchange_refresh_token().returning(|_| {
+        Ok(Some(RefreshGrant {
+            client_id: "client".to_string(),
+            resource_owner: 1,
+            scope: vec!["scope".to_string()],
+            resource: Vec::new(),
+            family_id: "family".to_string(),
+            dpop_jkt: Some(JKT.to_string()),
+        }))
+    });
+    test.token_provider.expect_mark_refresh_token_used().never();
+    let manager = build(test);
+
+    let request = TokenRequest {
+        grant_type: RequestedGrantType::RefreshToken {
+            refresh_token: DEFAULT_REFRESH_TOKEN.to_string(),
+        },
+        client_secret: None,
+        ..Default::default()
+    };
+
+    // Act
+    let result = manager.handle_token(request).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::DPoPKeyMismatch),
+        result.unwrap_err()
+    );
+}
+
+/// The access token and uri of the protected resource used in the examples of RFC 9449.
+static ACCESS_TOKEN: &str = "Kz~8mXK1EalYznwH-LC-1fBAo.4Ljp~zsPE_NeO.gxU";
+static RESOURCE_URI: &str = "https://resource.example.org/protectedresource";
+
+/// A test environment where the access token of the examples is bound to the DPoP key.
+fn dpop_bound_resource_environment() -> TestEnvironment {
+    let mut test = dpop_environment();
+    test.dpop_provider.expect_register_proof_id().returning(|_, _, _| Ok(true));
+    test.token_provider.expect_lookup_token().returning(|_, _| {
+        Ok(Some(IssuedToken {
+            client_id: "client".to_string(),
+            grant: GrantType::ClientCredentials,
+            token_type: TokenTypeHint::AccessToken,
+            valid_until: Instant::now() + Duration::from_secs(60),
+            confirmation: TokenConfirmation { jkt: Some(JKT.to_string()), ..Default::default() },
+        }))
+    });
+    test
+}
+
+fn resource_proof() -> String {
+    let mut claims = proof_claims();
+    claims["htm"] = json!("GET");
+    claims["htu"] = json!(RESOURCE_URI);
+    claims["ath"] = json!("fUHyO2r2Z3DZ53EsNrWBb0xWXoaNy59IiKCAqksmQEo");
+    proof(json!({ "typ": "dpop+jwt", "alg": "ES256", "jwk": public_key() }), claims)
+}
+
+#[tokio::test]
+async fn test_dpop_bound_access_token_is_accepted_with_proof() {
+    // The resource server MUST check that the public key of the DPoP proof matches the public key
+    // to which the access token is bound.
+
+    // Arrange
+    let manager = build(dpop_bound_resource_environment());
+    let request = request_from_raw_http(&format!(
+        r#"
+        GET /protectedresource HTTP/1.1
+        authorization: DPoP {ACCESS_TOKEN}
+        dpop: {}
+    "#,
+        resource_proof()
+    ));
+    let validator = BearerTokenValidator::new().accept_dpop(RESOURCE_URI);
+
+    // Act
+    let result = manager.handle_protected_resource_request(request, &validator).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+}
+
+#[tokio::test]
+async fn test_dpop_bound_access_token_is_rejected_as_bearer_token() {
+    // Arrange
+    let manager = build(dpop_bound_resource_environment());
+    let request = request_from_raw_http(&format!(
+        r#"
+        GET /protectedresource HTTP/1.1
+        authorization: Bearer {ACCESS_TOKEN}
+    "#
+    ));
+    let validator = BearerTokenValidator::new().accept_dpop(RESOURCE_URI);
+
+    // Act
+    let result = manager.handle_protected_resource_request(request, &validator).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    let error = result.unwrap_err();
+    assert_eq!(OAuthError::ValidationFailed(OAuthValidationError::InvalidAccessToken), error);
+    assert_eq!(PublicOAuthError::InvalidToken, PublicOAuthError::from(error));
+}
+
+#[tokio::test]
+async fn test_dpop_bound_access_token_is_rejected_when_dpop_is_not_accepted() {
+    // Arrange
+    let manager = build(dpop_bound_resource_environment());
+    let request = request_from_raw_http(&format!(
+        r#"
+        GET /protectedresource HTTP/1.1
+        authorization: DPoP {ACCESS_TOKEN}
+        dpop: {}
+    "#,
+        resource_proof()
+    ));
+
+    // Act
+    let result =
+        manager.handle_protected_resource_request(request, &BearerTokenValidator::new()).await;
+
+    // Assert
+    assert_eq!(Err(OAuthError::ValidationFailed(OAuthValidationError::InvalidAccessToken)), result);
+}
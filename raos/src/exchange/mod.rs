@@ -0,0 +1,115 @@
+pub use provider::*;
+pub use request::*;
+
+use crate::{
+    common::{
+        frontend::{OAuthError, OAuthValidationError},
+        model::Client,
+    },
+    manager::OAuthManager,
+};
+
+mod provider;
+mod request;
+#[cfg(test)]
+mod test;
+
+/// The grant type used by clients to exchange a security token for another token, as described in RFC 8693.
+pub const TOKEN_EXCHANGE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:token-exchange";
+
+/// The token type identifier of an OAuth 2.0 access token, as described in RFC 8693 section 3.
+/// This is the token type that is issued when the client does not request a specific token type.
+pub const ACCESS_TOKEN_TYPE: &str = "urn:ietf:params:oauth:token-type:access_token";
+
+impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
+    /// Validate a token exchange request, resolving the subject and actor tokens through the [TokenExchangeProvider].
+    ///
+    /// # Parameters
+    /// - `client` - The authenticated client that requests the exchange.
+    /// - `params` - The token exchange parameters of the token request.
+    /// - `scope` - The scope requested by the client, which may not exceed the scope of the subject token.
+    ///
+    /// # Errors
+    /// This function will return an [OAuthError] if token exchange is not enabled, if the requested token type
+    /// is not an access token, if the subject or actor token is invalid, if the requested scope exceeds the scope of the subject token, or if the exchange is not authorized.
+    pub(crate) async fn validate_token_exchange(
+        &self,
+        client: &Client,
+        params: TokenExchangeParameters,
+        scope: Option<Vec<String>>,
+    ) -> Result<TokenExchangeGrant<U>, OAuthError<E>> {
+        let Some(token_exchange_provider) = &self.token_exchange_provider else {
+            return Err(OAuthValidationError::InvalidGrantType {
+                requested: TOKEN_EXCHANGE_GRANT_TYPE.to_string(),
+            }
+            .into());
+        };
+
+        // Only access tokens are issued, so any other requested token type can not be honored
+        let issued_token_type = match params.requested_token_type {
+            Some(requested_token_type) if requested_token_type != ACCESS_TOKEN_TYPE => {
+                return Err(OAuthValidationError::InvalidParameterValue(
+                    "requested_token_type",
+                    requested_token_type,
+                )
+                .into());
+            }
+            _ => ACCESS_TOKEN_TYPE.to_string(),
+        };
+
+        let Some(subject) = token_exchange_provider
+            .validate_subject_token(params.subject_token, params.subject_token_type)
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?
+        else {
+            return Err(OAuthValidationError::InvalidSubjectToken.into());
+        };
+
+        // The issued token can be downscoped, but never exceed the scope of the subject token
+        let scope = match scope {
+            Some(scope) => {
+                if scope.iter().any(|scope| !subject.scope.contains(scope)) {
+                    return Err(OAuthValidationError::ScopeNotConsented.into());
+                }
+                scope
+            }
+            None => subject.scope,
+        };
+
+        let actor = match (params.actor_token, params.actor_token_type) {
+            (Some(actor_token), Some(actor_token_type)) => {
+                let Some(mut actor) = token_exchange_provider
+                    .validate_actor_token(actor_token, actor_token_type)
+                    .await
+                    .map_err(OAuthError::ProviderImplementationError)?
+                else {
+                    return Err(OAuthValidationError::InvalidActorToken.into());
+                };
+
+                // The current actor is the outermost act claim, prior actors of the subject token are nested within it
+                actor.act = subject.actor.map(Box::new);
+                Some(actor)
+            }
+            _ => subject.actor,
+        };
+
+        let grant = TokenExchangeGrant {
+            resource_owner: subject.resource_owner,
+            scope,
+            audience: params.audience,
+            resource: params.resource,
+            issued_token_type,
+            actor,
+        };
+
+        let authorized = token_exchange_provider
+            .authorize_exchange(client, &grant)
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?;
+        if !authorized {
+            return Err(OAuthValidationError::ClientNotAllowedToExchangeToken.into());
+        }
+
+        Ok(grant)
+    }
+}
@@ -0,0 +1,126 @@
+use async_trait::async_trait;
+use serde::Serialize;
+
+use crate::common::model::Client;
+
+/// Token exchange provider trait.
+/// This is an optional trait that can be implemented by the end user, to enable the token exchange grant
+/// as described in RFC 8693.
+///
+/// This trait is used to resolve the tokens presented by a client, and to decide whether the client is allowed
+/// to exchange them for a token meant for the requested audience.
+#[async_trait]
+pub trait TokenExchangeProvider: 'static + Send + Sync {
+    /// This is the type of the owner id that is used to identify the resource owner.
+    /// This type will need to match the OwnerId used in [AuthorizationProvider](crate::authorize::AuthorizationProvider).
+    type OwnerId;
+    /// This is the error type that can be returned by the token exchange provider implementing this trait.
+    /// This type will need to match the Error used in [ClientProvider](crate::common::model::ClientProvider).
+    type Error;
+
+    /// Validate the subject token of a token exchange request.
+    ///
+    /// # Implementation notes
+    /// The subject token can be any type of security token, like an access token issued by this authorization
+    /// server, or a JWT issued by a trusted third party. Tokens of an unsupported type, or tokens that are expired,
+    /// revoked or otherwise invalid, should return [None].
+    ///
+    /// # Arguments
+    /// * `subject_token` - The token that represents the party on behalf of whom the request is made.
+    /// * `subject_token_type` - The type identifier of the subject token, like `urn:ietf:params:oauth:token-type:access_token`.
+    ///
+    /// # Returns
+    /// An [Option] containing the [TokenSubject] the token represents, or [None] if the token is invalid.
+    ///
+    /// # Errors
+    /// If the provider fails to validate the token, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn validate_subject_token(
+        &self,
+        subject_token: String,
+        subject_token_type: String,
+    ) -> Result<Option<TokenSubject<Self::OwnerId>>, Self::Error>;
+
+    /// Validate the actor token of a token exchange request, which is used for delegation.
+    ///
+    /// # Implementation notes
+    /// The [Actor::act] of the returned actor is replaced by the actors of the subject token,
+    /// so the issued token describes the full chain of delegation.
+    ///
+    /// # Arguments
+    /// * `actor_token` - The token that represents the acting party.
+    /// * `actor_token_type` - The type identifier of the actor token.
+    ///
+    /// # Returns
+    /// An [Option] containing the [Actor] the token represents, or [None] if the token is invalid.
+    ///
+    /// # Errors
+    /// If the provider fails to validate the token, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn validate_actor_token(
+        &self,
+        actor_token: String,
+        actor_token_type: String,
+    ) -> Result<Option<Actor>, Self::Error>;
+
+    /// Decide whether a client is allowed to perform a token exchange.
+    ///
+    /// # Implementation notes
+    /// This is the policy that gates which services can act on behalf of which resource owners, and for which
+    /// target services they can obtain tokens. The requested token type should also be checked here, as the
+    /// [TokenProvider](crate::token::TokenProvider) will have to issue a token of that type.
+    ///
+    /// # Arguments
+    /// * `client` - The authenticated client that requests the exchange.
+    /// * `grant` - The grant that will be passed to the [TokenProvider](crate::token::TokenProvider) if the exchange is allowed.
+    ///
+    /// # Returns
+    /// True if the client is allowed to perform the exchange.
+    ///
+    /// # Errors
+    /// If the provider fails to authorize the exchange, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn authorize_exchange(
+        &self,
+        client: &Client,
+        grant: &TokenExchangeGrant<Self::OwnerId>,
+    ) -> Result<bool, Self::Error>;
+}
+
+/// The party a subject token represents, returned by the [TokenExchangeProvider].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenSubject<OwnerId> {
+    /// The resource owner on behalf of whom the request is made.
+    pub resource_owner: OwnerId,
+    /// The scope granted to the subject token, which the issued token can not exceed.
+    pub scope: Vec<String>,
+    /// The actors the subject token was already delegated to, if any.
+    pub actor: Option<Actor>,
+}
+
+/// An acting party that a token was delegated to, modelled after the `act` claim of RFC 8693 section 4.1.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Actor {
+    /// The subject identifier of the acting party.
+    pub sub: String,
+    /// The prior actor in the chain of delegation, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub act: Option<Box<Actor>>,
+}
+
+/// A token exchange grant passed to the [TokenProvider](crate::token::TokenProvider) when issuing the exchanged token.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenExchangeGrant<OwnerId> {
+    /// The resource owner on behalf of whom the token is issued.
+    pub resource_owner: OwnerId,
+    /// The scope of the issued token.
+    pub scope: Vec<String>,
+    /// The logical names of the target services the token is meant for.
+    pub audience: Vec<String>,
+    /// The uris of the target services or resources the token is meant for.
+    pub resource: Vec<String>,
+    /// The type identifier of the token that should be issued.
+    pub issued_token_type: String,
+    /// The party the token is delegated to, which should be included as `act` claim, or [None] for impersonation.
+    pub actor: Option<Actor>,
+}
@@ -0,0 +1,68 @@
+use crate::common::{
+    frontend::{FrontendRequest, OAuthValidationError},
+    util::NoneIfEmpty,
+};
+
+/// The parameters of a token exchange request, as described in RFC 8693 section 2.1.
+#[derive(Debug)]
+pub struct TokenExchangeParameters {
+    /// The token that represents the party on behalf of whom the request is made.
+    pub subject_token: String,
+    /// The type of the subject token, like `urn:ietf:params:oauth:token-type:access_token`.
+    pub subject_token_type: String,
+    /// The token that represents the acting party, used for delegation.
+    pub actor_token: Option<String>,
+    /// The type of the actor token, which is present if and only if the actor token is present.
+    pub actor_token_type: Option<String>,
+    /// The logical names of the target services the issued token is meant for.
+    pub audience: Vec<String>,
+    /// The uris of the target services or resources the issued token is meant for.
+    pub resource: Vec<String>,
+    /// The type of token the client wants to be issued, an access token if omitted.
+    pub requested_token_type: Option<String>,
+}
+
+impl TokenExchangeParameters {
+    /// Parse the token exchange parameters from the body of a token request.
+    ///
+    /// # Parameters
+    /// - `request` - The unparsed incoming token request from the client.
+    pub(crate) fn from_request(
+        request: &dyn FrontendRequest,
+    ) -> Result<Self, OAuthValidationError> {
+        // We should treat empty values as if they were omitted from the request
+        let body_param = |key| request.body_param(key).none_if_empty();
+        let body_param_values =
+            |key| request.body_param_values(key).into_iter().filter(|v| !v.is_empty()).collect();
+
+        let Some(subject_token) = body_param("subject_token") else {
+            return Err(OAuthValidationError::MissingRequiredParameter("subject_token"));
+        };
+        let Some(subject_token_type) = body_param("subject_token_type") else {
+            return Err(OAuthValidationError::MissingRequiredParameter("subject_token_type"));
+        };
+
+        // The actor_token_type is required when the actor_token is present, and must not be included otherwise
+        let actor_token = body_param("actor_token");
+        let actor_token_type = body_param("actor_token_type");
+        match (&actor_token, &actor_token_type) {
+            (Some(_), None) => {
+                return Err(OAuthValidationError::MissingRequiredParameter("actor_token_type"))
+            }
+            (None, Some(_)) => {
+                return Err(OAuthValidationError::MissingRequiredParameter("actor_token"))
+            }
+            _ => {}
+        }
+
+        Ok(TokenExchangeParameters {
+            subject_token,
+            subject_token_type,
+            actor_token,
+            actor_token_type,
+            audience: body_param_values("audience"),
+            resource: body_param_values("resource"),
+            requested_token_type: body_param("requested_token_type"),
+        })
+    }
+}
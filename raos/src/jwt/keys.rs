@@ -0,0 +1,265 @@
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+
+use crate::{
+    common::jose::{JsonWebKeySet, Jwk},
+    jwt::{JwsAlgorithm, SigningKey, SigningKeySource},
+};
+
+/// A signing key managed by a [KeyStore].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManagedKey {
+    /// The key id, unique within the key store.
+    pub kid: String,
+    /// The algorithm the key signs with.
+    pub algorithm: JwsAlgorithm,
+    /// The public part of the key, as published in the JSON Web Key Set.
+    pub public_key: Jwk,
+}
+
+impl ManagedKey {
+    /// The public key as published, with the `kid`, `alg` and `use` parameters filled in.
+    fn published_key(&self) -> Jwk {
+        Jwk {
+            kid: Some(self.kid.clone()),
+            alg: Some(self.algorithm.as_str().to_string()),
+            key_use: Some("sig".to_string()),
+            ..self.public_key.clone()
+        }
+    }
+}
+
+/// A key that is no longer used for signing, but is still published and accepted
+/// until the JWTs it signed have expired.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetiredKey {
+    /// The retired key.
+    pub key: ManagedKey,
+    /// The moment the last JWT signed with this key expires, after which the key is removed.
+    pub expires_at: SystemTime,
+}
+
+/// The keys of the authorization server at a point in time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeySet {
+    /// The key all JWTs are signed with.
+    pub active: ManagedKey,
+    /// The moment the active key became active, used to schedule the next rotation.
+    pub active_since: SystemTime,
+    /// The key that becomes active on the next rotation.
+    /// It is published ahead of time, so that verifiers already know it when it starts being used.
+    pub next: Option<ManagedKey>,
+    /// The keys that signed JWTs which may not have expired yet.
+    pub retired: Vec<RetiredKey>,
+}
+
+/// Key store trait.
+/// This trait has to be implemented by the end user to store signing keys, and to sign with them
+/// using the cryptography library of their choice.
+///
+/// The rotation of the keys is handled by the [KeyManager], the key store only persists the key set
+/// and holds the private keys.
+#[async_trait]
+pub trait KeyStore: 'static + Send + Sync {
+    /// This is the error type that can be returned by the key store implementing this trait.
+    type Error;
+
+    /// Load the current key set.
+    ///
+    /// # Returns
+    /// The [KeySet] that was last saved, or [None] if no keys were created yet.
+    ///
+    /// # Errors
+    /// If the key store fails to load the key set, through whatever error.
+    async fn load_key_set(&self) -> Result<Option<KeySet>, Self::Error>;
+
+    /// Save the key set, after keys were created, rotated or removed.
+    ///
+    /// # Implementation notes
+    /// The private keys of keys that are no longer part of the key set can be deleted.
+    /// If multiple instances of the authorization server share the key store, the key set should only be
+    /// replaced if it was not changed since it was loaded, to prevent a key from being rotated twice.
+    ///
+    /// # Arguments
+    /// * `key_set` - The new key set.
+    ///
+    /// # Errors
+    /// If the key store fails to save the key set, through whatever error.
+    async fn save_key_set(&self, key_set: &KeySet) -> Result<(), Self::Error>;
+
+    /// Generate a new key pair.
+    ///
+    /// # Implementation notes
+    /// The private key has to be kept by the key store, so that it can be used in [sign](KeyStore::sign).
+    /// The key id must be unique and never reused.
+    ///
+    /// # Returns
+    /// The new [ManagedKey], holding only the public part of the key.
+    ///
+    /// # Errors
+    /// If the key store fails to generate the key, through whatever error.
+    async fn generate_key(&self) -> Result<ManagedKey, Self::Error>;
+
+    /// Sign data with a key.
+    ///
+    /// # Arguments
+    /// * `key` - The key to sign with, the active key of the key set.
+    /// * `signing_input` - The data to sign, being the encoded header and payload separated by a period.
+    ///
+    /// # Returns
+    /// The signature, encoded as described in RFC 7518 for the algorithm of the key.
+    ///
+    /// # Errors
+    /// If the key store fails to sign the data, through whatever error.
+    async fn sign(&self, key: &SigningKey, signing_input: &[u8]) -> Result<Vec<u8>, Self::Error>;
+}
+
+/// Manages the signing keys in a [KeyStore], rotating them on a schedule.
+///
+/// Every rotation, the next key becomes the active key and the active key is retired. Retired keys stay published
+/// and are accepted for verification until the JWTs they signed have expired.
+/// The rotation is checked whenever the key set is used, so no background task is needed.
+///
+/// The key manager implements [SigningKeySource], so it can be used to sign the JWTs issued by the library,
+/// which are always signed with the active key.
+pub struct KeyManager<S> {
+    pub(crate) store: S,
+    rotation_interval: Duration,
+    retention: Duration,
+}
+
+impl<S: KeyStore> KeyManager<S> {
+    /// Create a new key manager.
+    ///
+    /// # Parameters
+    /// - `store` - The [KeyStore] holding the keys.
+    /// - `rotation_interval` - How long a key is active before it is rotated.
+    /// - `retention` - How long a key stays published after it was retired.
+    ///   This must be at least the lifetime of the longest living JWT that is signed.
+    pub fn new(store: S, rotation_interval: Duration, retention: Duration) -> Self {
+        Self { store, rotation_interval, retention }
+    }
+
+    /// Get the current key set, rotating the keys if the rotation is due and removing expired keys.
+    ///
+    /// # Errors
+    /// If the key store fails to load, generate or save keys.
+    pub async fn key_set(&self) -> Result<KeySet, S::Error> {
+        let now = SystemTime::now();
+        let Some(mut key_set) = self.store.load_key_set().await? else {
+            let key_set = KeySet {
+                active: self.store.generate_key().await?,
+                active_since: now,
+                next: Some(self.store.generate_key().await?),
+                retired: Vec::new(),
+            };
+            self.store.save_key_set(&key_set).await?;
+            return Ok(key_set);
+        };
+
+        let mut changed = false;
+        if key_set.active_since + self.rotation_interval <= now {
+            self.rotate_key_set(&mut key_set, now).await?;
+            changed = true;
+        }
+        let retired = key_set.retired.len();
+        key_set.retired.retain(|key| key.expires_at > now);
+        changed |= key_set.retired.len() != retired;
+        if key_set.next.is_none() {
+            key_set.next = Some(self.store.generate_key().await?);
+            changed = true;
+        }
+
+        if changed {
+            self.store.save_key_set(&key_set).await?;
+        }
+        Ok(key_set)
+    }
+
+    /// Rotate the keys immediately, regardless of the schedule.
+    /// This can be used when the active key is suspected to be compromised, in which case the retired key
+    /// should also be removed from the key store.
+    ///
+    /// # Returns
+    /// The rotated [KeySet].
+    ///
+    /// # Errors
+    /// If the key store fails to load, generate or save keys.
+    pub async fn rotate(&self) -> Result<KeySet, S::Error> {
+        let mut key_set = self.key_set().await?;
+        self.rotate_key_set(&mut key_set, SystemTime::now()).await?;
+        self.store.save_key_set(&key_set).await?;
+        Ok(key_set)
+    }
+
+    async fn rotate_key_set(&self, key_set: &mut KeySet, now: SystemTime) -> Result<(), S::Error> {
+        let next = match key_set.next.take() {
+            Some(next) => next,
+            None => self.store.generate_key().await?,
+        };
+        let retired = std::mem::replace(&mut key_set.active, next);
+        key_set.retired.push(RetiredKey { key: retired, expires_at: now + self.retention });
+        key_set.active_since = now;
+        key_set.next = Some(self.store.generate_key().await?);
+        Ok(())
+    }
+
+    /// Generate the JSON Web Key Set of the authorization server, containing the active, next and retired keys.
+    /// This document is meant to be served on `/.well-known/jwks.json`.
+    ///
+    /// # Returns
+    /// A [JsonWebKeySet] that can be used to build a response, which in turn
+    /// implements the [FrontendResponse](crate::common::frontend::FrontendResponse) trait.
+    ///
+    /// # Errors
+    /// If the key store fails to load, generate or save keys.
+    pub async fn jwks(&self) -> Result<JsonWebKeySet, S::Error> {
+        let key_set = self.key_set().await?;
+        let keys = std::iter::once(&key_set.active)
+            .chain(&key_set.next)
+            .chain(key_set.retired.iter().map(|retired| &retired.key))
+            .map(ManagedKey::published_key)
+            // A key store should never hand out private keys, but make sure they are never published
+            .filter(|key| !key.has_private_parameters())
+            .collect();
+        Ok(JsonWebKeySet { keys })
+    }
+
+    /// Find the public key a JWT signed by the authorization server should be verified with.
+    /// Only the active key and the retired keys that have not expired are accepted,
+    /// the next key has not signed anything yet.
+    ///
+    /// # Parameters
+    /// - `kid` - The key id from the `kid` header of the JWT.
+    ///
+    /// # Returns
+    /// The public key, or [None] if no key with this id should be accepted.
+    ///
+    /// # Errors
+    /// If the key store fails to load, generate or save keys.
+    pub async fn verification_key(&self, kid: &str) -> Result<Option<Jwk>, S::Error> {
+        let key_set = self.key_set().await?;
+        Ok(std::iter::once(&key_set.active)
+            .chain(key_set.retired.iter().map(|retired| &retired.key))
+            .find(|key| key.kid == kid)
+            .map(ManagedKey::published_key))
+    }
+}
+
+#[async_trait]
+impl<S: KeyStore> SigningKeySource for KeyManager<S>
+where
+    S::Error: Send,
+{
+    type Error = S::Error;
+
+    async fn signing_key(&self) -> Result<SigningKey, Self::Error> {
+        let active = self.key_set().await?.active;
+        Ok(SigningKey { kid: active.kid, algorithm: active.algorithm })
+    }
+
+    async fn sign(&self, key: &SigningKey, signing_input: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        self.store.sign(key, signing_input).await
+    }
+}
@@ -0,0 +1,9 @@
+pub use keys::*;
+pub use provider::*;
+pub use signer::*;
+
+mod keys;
+mod provider;
+mod signer;
+#[cfg(test)]
+mod test;
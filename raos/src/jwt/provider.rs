@@ -0,0 +1,200 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use async_trait::async_trait;
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    common::{auth::Audience, jose::CompactJws, model::Client},
+    jwt::{sign_jwt, JwtSubject, SigningKeySource},
+    token::{
+        GrantType, IssuedToken, RefreshGrant, Token, TokenConfirmation, TokenProvider,
+        TokenTypeHint,
+    },
+};
+
+/// The type of JWT access tokens, sent in the `typ` header as described in RFC 9068.
+pub const ACCESS_TOKEN_JWT_TYPE: &str = "at+jwt";
+
+/// A [TokenProvider] that issues JWT access tokens, as described in RFC 9068.
+///
+/// All other operations are handled by the wrapped token provider, which keeps issuing and storing the tokens as before.
+/// The access token of the wrapped provider is never part of the JWT, which gets a random `jti` claim instead.
+/// The issued JWTs are kept in memory along with the wrapped access token until they expire, so that the wrapped
+/// provider receives its own access token instead of the JWT in [lookup_token](TokenProvider::lookup_token) and
+/// [revoke_token](TokenProvider::revoke_token). JWTs that were not issued by this provider are never resolved.
+pub struct JwtTokenProvider<P, K> {
+    inner: P,
+    key_source: K,
+    issuer: String,
+    audience: String,
+    // Wrapped access tokens by the JWT they were issued as, with the instant the JWT expires
+    issued: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+impl<P, K> JwtTokenProvider<P, K> {
+    /// Create a new JWT token provider.
+    ///
+    /// # Parameters
+    /// - `inner` - The token provider that stores the issued tokens and handles refresh tokens.
+    /// - `key_source` - The [SigningKeySource] the access tokens are signed with.
+    /// - `issuer` - The issuer identifier of the authorization server, used as the `iss` claim.
+    /// - `audience` - The resource server the access tokens are intended for, used as the `aud` claim
+    ///   unless the client restricted the token to specific resources.
+    pub fn new(inner: P, key_source: K, issuer: String, audience: String) -> Self {
+        Self { inner, key_source, issuer, audience, issued: Mutex::default() }
+    }
+
+    /// Find the token of the wrapped provider a token stands for.
+    /// JWT access tokens only resolve if they were issued by this provider,
+    /// any other token, like refresh tokens, is passed on as is.
+    fn inner_token(&self, token: String) -> InnerToken {
+        if let Some((inner_token, _)) = self.issued.lock().unwrap().get(&token) {
+            return InnerToken::AccessToken(inner_token.clone());
+        }
+        match CompactJws::parse(&token) {
+            Some(jws) if jws.header.typ.as_deref() == Some(ACCESS_TOKEN_JWT_TYPE) => {
+                InnerToken::Unknown
+            }
+            _ => InnerToken::Other(token),
+        }
+    }
+}
+
+/// The token of the wrapped provider a token stands for.
+enum InnerToken {
+    /// The wrapped access token a JWT issued by this provider stands for.
+    AccessToken(String),
+    /// Any token that is not a JWT access token, like a refresh token.
+    Other(String),
+    /// A JWT access token that was not issued by this provider, or has expired.
+    Unknown,
+}
+
+/// The claims of a JWT access token, as described in RFC 9068 section 2.2.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AccessTokenClaims {
+    pub(crate) iss: String,
+    pub(crate) sub: String,
+    pub(crate) aud: Audience,
+    pub(crate) client_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) scope: Option<String>,
+    pub(crate) jti: String,
+    pub(crate) iat: u64,
+    pub(crate) exp: u64,
+    #[serde(skip_serializing_if = "Option::is_none", skip_deserializing)]
+    pub(crate) cnf: Option<TokenConfirmation>,
+}
+
+#[async_trait]
+impl<P, K> TokenProvider for JwtTokenProvider<P, K>
+where
+    P: TokenProvider,
+    P::OwnerId: JwtSubject + Send,
+    P::Error: Send,
+    K: SigningKeySource<Error = P::Error>,
+{
+    type OwnerId = P::OwnerId;
+    type Error = P::Error;
+
+    async fn token(
+        &self,
+        client: &Client,
+        grant: GrantType<Self::OwnerId>,
+        confirmation: TokenConfirmation,
+        audience: &[String],
+    ) -> Result<Token, Self::Error> {
+        // For grants without a resource owner, the subject is the client itself
+        let sub =
+            grant.resource_owner().map_or_else(|| client.client_id.clone(), JwtSubject::to_subject);
+        let scope = grant.scope().map(|scope| scope.join(" "));
+        let cnf = (!confirmation.is_empty()).then(|| confirmation.clone());
+        // Tokens restricted to resources through RFC 8707 are meant for those resources instead
+        let aud = match audience {
+            [] => Audience::One(self.audience.clone()),
+            [resource] => Audience::One(resource.clone()),
+            resources => Audience::Many(resources.to_vec()),
+        };
+
+        let token = self.inner.token(client, grant, confirmation, audience).await?;
+
+        let now = SystemTime::now();
+        let exp = now + token.valid_until.saturating_duration_since(Instant::now());
+        let seconds =
+            |time: SystemTime| time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let claims = AccessTokenClaims {
+            iss: self.issuer.clone(),
+            sub,
+            aud,
+            client_id: client.client_id.clone(),
+            scope,
+            jti: thread_rng().sample_iter(&Alphanumeric).take(32).map(char::from).collect(),
+            iat: seconds(now),
+            exp: seconds(exp),
+            cnf,
+        };
+
+        let key = self.key_source.signing_key().await?;
+        let jwt = sign_jwt(&self.key_source, &key, ACCESS_TOKEN_JWT_TYPE, &claims).await?;
+
+        let mut issued = self.issued.lock().unwrap();
+        issued.retain(|_, (_, valid_until)| *valid_until > Instant::now());
+        issued.insert(jwt.clone(), (token.token, token.valid_until));
+        Ok(Token { token: jwt, refresh_token: token.refresh_token, valid_until: token.valid_until })
+    }
+
+    async fn exchange_refresh_token(
+        &self,
+        refresh_token: String,
+    ) -> Result<Option<RefreshGrant<Self::OwnerId>>, Self::Error> {
+        self.inner.exchange_refresh_token(refresh_token).await
+    }
+
+    async fn mark_refresh_token_used(&self, refresh_token: &str) -> Result<bool, Self::Error> {
+        self.inner.mark_refresh_token_used(refresh_token).await
+    }
+
+    async fn revoke_refresh_token_family(&self, family_id: &str) -> Result<(), Self::Error> {
+        self.inner.revoke_refresh_token_family(family_id).await
+    }
+
+    async fn revoke_code_tokens(&self, code_id: &str) -> Result<(), Self::Error> {
+        self.inner.revoke_code_tokens(code_id).await
+    }
+
+    async fn lookup_token(
+        &self,
+        token: String,
+        token_type_hint: Option<TokenTypeHint>,
+    ) -> Result<Option<IssuedToken<Self::OwnerId>>, Self::Error> {
+        match self.inner_token(token) {
+            InnerToken::AccessToken(token) => self.inner.lookup_token(token, token_type_hint).await,
+            // Access tokens are only handed out as JWTs, the access token of the wrapped provider is no credential
+            InnerToken::Other(token) => Ok(self
+                .inner
+                .lookup_token(token, token_type_hint)
+                .await?
+                .filter(|issued| issued.token_type != TokenTypeHint::AccessToken)),
+            InnerToken::Unknown => Ok(None),
+        }
+    }
+
+    async fn revoke_token(
+        &self,
+        client: &Client,
+        token: String,
+        token_type_hint: Option<TokenTypeHint>,
+    ) -> Result<(), Self::Error> {
+        match self.inner_token(token) {
+            InnerToken::AccessToken(token) | InnerToken::Other(token) => {
+                self.inner.revoke_token(client, token, token_type_hint).await
+            }
+            InnerToken::Unknown => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use serde::Serialize;
+#[cfg(feature = "oidc")]
+use sha2::{Digest, Sha256, Sha512};
+
+/// The JWS algorithms the authorization server can sign JWTs with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JwsAlgorithm {
+    /// RSASSA-PKCS1-v1_5 using SHA-256.
+    RS256,
+    /// ECDSA using P-256 and SHA-256.
+    ES256,
+    /// EdDSA using Ed25519, as described in RFC 8037.
+    EdDSA,
+}
+
+impl JwsAlgorithm {
+    /// The name of the algorithm, as used in the `alg` header parameter.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JwsAlgorithm::RS256 => "RS256",
+            JwsAlgorithm::ES256 => "ES256",
+            JwsAlgorithm::EdDSA => "EdDSA",
+        }
+    }
+
+    /// Hash data with the hash function of the algorithm, as used for the `at_hash` claim of ID tokens.
+    /// Ed25519 uses SHA-512, as it does internally.
+    #[cfg(feature = "oidc")]
+    pub(crate) fn hash(&self, data: &[u8]) -> Vec<u8> {
+        match self {
+            JwsAlgorithm::RS256 | JwsAlgorithm::ES256 => Sha256::digest(data).to_vec(),
+            JwsAlgorithm::EdDSA => Sha512::digest(data).to_vec(),
+        }
+    }
+}
+
+/// A reference to a key that JWTs are signed with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SigningKey {
+    /// The key id, sent in the `kid` header so that verifiers can select the key from the published key set.
+    pub kid: String,
+    /// The algorithm the key signs with.
+    pub algorithm: JwsAlgorithm,
+}
+
+/// Signing key source trait.
+/// This trait has to be implemented by the end user to sign JWTs, using the cryptography library of their choice.
+///
+/// The library builds and encodes the JWT, only the selection of the key and the signature itself are delegated.
+#[async_trait]
+pub trait SigningKeySource: 'static + Send + Sync {
+    /// This is the error type that can be returned by the key source implementing this trait.
+    type Error;
+
+    /// Get the key the next JWT should be signed with.
+    ///
+    /// # Implementation notes
+    /// Returning a different key over time allows keys to be rotated,
+    /// as long as the previous keys stay published until the JWTs they signed have expired.
+    ///
+    /// # Returns
+    /// The [SigningKey] to sign with.
+    ///
+    /// # Errors
+    /// If the key source fails to select a key, through whatever error.
+    async fn signing_key(&self) -> Result<SigningKey, Self::Error>;
+
+    /// Sign data with a key.
+    ///
+    /// # Arguments
+    /// * `key` - The key to sign with, as returned by [signing_key](SigningKeySource::signing_key).
+    /// * `signing_input` - The data to sign, being the encoded header and payload separated by a period.
+    ///
+    /// # Returns
+    /// The signature, encoded as described in RFC 7518 for the algorithm of the key.
+    /// For ES256 this is the concatenation of R and S, not a DER encoded signature.
+    ///
+    /// # Errors
+    /// If the key source fails to sign the data, through whatever error.
+    async fn sign(&self, key: &SigningKey, signing_input: &[u8]) -> Result<Vec<u8>, Self::Error>;
+}
+
+#[async_trait]
+impl<K: SigningKeySource + ?Sized> SigningKeySource for Arc<K> {
+    type Error = K::Error;
+
+    async fn signing_key(&self) -> Result<SigningKey, Self::Error> {
+        (**self).signing_key().await
+    }
+
+    async fn sign(&self, key: &SigningKey, signing_input: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        (**self).sign(key, signing_input).await
+    }
+}
+
+/// Conversion of an owner id into the `sub` (subject) claim of issued JWTs.
+///
+/// # Implementation notes
+/// The subject must be unique for the resource owner and never reassigned.
+pub trait JwtSubject {
+    /// Get the subject identifier of this resource owner.
+    fn to_subject(&self) -> String;
+}
+
+macro_rules! impl_jwt_subject {
+    ($($t:ty),*) => {
+        $(impl JwtSubject for $t {
+            fn to_subject(&self) -> String {
+                self.to_string()
+            }
+        })*
+    };
+}
+
+impl_jwt_subject!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, String);
+
+#[derive(Serialize)]
+struct JwtHeader<'a> {
+    alg: &'static str,
+    typ: &'a str,
+    kid: &'a str,
+}
+
+/// Sign the claims as a JWT of the given type, using a key of the key source.
+pub(crate) async fn sign_jwt<K: SigningKeySource + ?Sized>(
+    key_source: &K,
+    key: &SigningKey,
+    typ: &str,
+    claims: &impl Serialize,
+) -> Result<String, K::Error> {
+    let header = JwtHeader { alg: key.algorithm.as_str(), typ, kid: &key.kid };
+
+    let encode = |value: String| BASE64_URL_SAFE_NO_PAD.encode(value);
+    let signing_input = format!(
+        "{}.{}",
+        encode(
+            serde_json::to_string(&header).expect("Unexpected error: header is not serializable")
+        ),
+        encode(
+            serde_json::to_string(claims).expect("Unexpected error: claims are not serializable")
+        ),
+    );
+    let signature = key_source.sign(key, signing_input.as_bytes()).await?;
+
+    Ok(format!("{signing_input}.{}", BASE64_URL_SAFE_NO_PAD.encode(signature)))
+}
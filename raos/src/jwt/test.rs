@@ -0,0 +1,460 @@
+use crate::{
+    common::{
+        frontend::{FrontendResponse, FrontendResponseExt},
+        jose::{CompactJws, Jwk},
+        model::Client,
+    },
+    jwt::{
+        sign_jwt, JwsAlgorithm, JwtTokenProvider, KeyManager, KeySet, KeyStore, ManagedKey,
+        RetiredKey, SigningKey, SigningKeySource, ACCESS_TOKEN_JWT_TYPE,
+    },
+    test::{mock::MockTokenProvider, DEFAULT_CLIENT_ID, DEFAULT_REFRESH_TOKEN, DEFAULT_TOKEN},
+    token::{GrantType, IssuedToken, Token, TokenConfirmation, TokenProvider, TokenTypeHint},
+};
+use async_trait::async_trait;
+use mockall::predicate::{always, eq};
+use serde_json::{json, Value};
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime},
+};
+
+/// A key source that signs everything with the signature "signature".
+struct TestKeySource;
+
+#[async_trait]
+impl SigningKeySource for TestKeySource {
+    type Error = ();
+
+    async fn signing_key(&self) -> Result<SigningKey, Self::Error> {
+        Ok(SigningKey { kid: "key".to_string(), algorithm: JwsAlgorithm::ES256 })
+    }
+
+    async fn sign(&self, _key: &SigningKey, _signing_input: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        Ok(b"signature".to_vec())
+    }
+}
+
+fn jwt_token_provider(
+    inner: MockTokenProvider,
+) -> JwtTokenProvider<MockTokenProvider, TestKeySource> {
+    JwtTokenProvider::new(
+        inner,
+        TestKeySource,
+        "https://example.com".to_string(),
+        "https://api.example.com".to_string(),
+    )
+}
+
+fn inner_token_provider() -> MockTokenProvider {
+    let mut inner = MockTokenProvider::new();
+    inner.expect_token().returning(|_, _, _, _| {
+        Ok(Token {
+            token: DEFAULT_TOKEN.to_string(),
+            refresh_token: Some(DEFAULT_REFRESH_TOKEN.to_string()),
+            valid_until: Instant::now() + Duration::from_secs(3600),
+        })
+    });
+    inner
+}
+
+fn decode(token: &str) -> (CompactJws, Value) {
+    let jws = CompactJws::parse(token).unwrap();
+    let claims = jws.claims().unwrap();
+    (jws, claims)
+}
+
+#[tokio::test]
+async fn test_jwt_access_token_contains_claims() {
+    // Arrange
+    let provider = jwt_token_provider(inner_token_provider());
+    let grant = GrantType::AuthorizationCode {
+        resource_owner: 1,
+        scope: vec!["read".to_string(), "write".to_string()],
+        code_id: "code_id".to_string(),
+    };
+
+    // Act
+    let result = provider.token(&Client::default(), grant, TokenConfirmation::default(), &[]).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok");
+    let token = result.unwrap();
+    assert_eq!(Some(DEFAULT_REFRESH_TOKEN.to_string()), token.refresh_token);
+
+    let (jws, claims) = decode(&token.token);
+    assert_eq!("ES256", jws.header.alg);
+    assert_eq!(Some(ACCESS_TOKEN_JWT_TYPE), jws.header.typ.as_deref());
+    assert_eq!(Some("key"), jws.header.kid.as_deref());
+    assert_eq!(b"signature".to_vec(), jws.signature);
+
+    assert_eq!(json!("https://example.com"), claims["iss"]);
+    assert_eq!(json!("1"), claims["sub"]);
+    assert_eq!(json!("https://api.example.com"), claims["aud"]);
+    assert_eq!(json!(DEFAULT_CLIENT_ID), claims["client_id"]);
+    assert_eq!(json!("read write"), claims["scope"]);
+    // The access token of the wrapped provider must not be readable from the JWT
+    assert_ne!(json!(DEFAULT_TOKEN), claims["jti"]);
+    assert!(!token.token.contains(DEFAULT_TOKEN), "token contains the wrapped access token");
+    let lifetime = claims["exp"].as_u64().unwrap() - claims["iat"].as_u64().unwrap();
+    assert!((3599..=3600).contains(&lifetime), "lifetime is {lifetime}");
+    assert_eq!(None, claims.get("cnf"));
+}
+
+#[tokio::test]
+async fn test_jwt_access_token_for_client_credentials() {
+    // In cases of access tokens obtained through grants where no resource owner is involved, such as the client credentials grant,
+    // the value of "sub" SHOULD correspond to an identifier the authorization server uses to indicate the client application.
+
+    // Arrange
+    let provider = jwt_token_provider(inner_token_provider());
+
+    // Act
+    let result = provider
+        .token(&Client::default(), GrantType::ClientCredentials, TokenConfirmation::default(), &[])
+        .await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok");
+    let (_, claims) = decode(&result.unwrap().token);
+    assert_eq!(json!(DEFAULT_CLIENT_ID), claims["sub"]);
+    assert_eq!(None, claims.get("scope"));
+}
+
+#[tokio::test]
+async fn test_jwt_access_token_is_bound_to_confirmation() {
+    // Arrange
+    let mut inner = MockTokenProvider::new();
+    let confirmation =
+        TokenConfirmation { jkt: Some("jkt".to_string()), x5t_s256: Some("x5t".to_string()) };
+    inner
+        .expect_token()
+        .with(always(), always(), eq(confirmation.clone()), always())
+        .times(1)
+        .returning(|_, _, _, _| {
+            Ok(Token {
+                token: DEFAULT_TOKEN.to_string(),
+                refresh_token: None,
+                valid_until: Instant::now(),
+            })
+        });
+    let provider = jwt_token_provider(inner);
+
+    // Act
+    let result =
+        provider.token(&Client::default(), GrantType::ClientCredentials, confirmation, &[]).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok");
+    let (_, claims) = decode(&result.unwrap().token);
+    assert_eq!(json!({ "jkt": "jkt", "x5t#S256": "x5t" }), claims["cnf"]);
+}
+
+#[tokio::test]
+async fn test_jwt_access_token_audience_is_restricted_to_resources() {
+    // Arrange
+    let provider = jwt_token_provider(inner_token_provider());
+    let one = ["https://one.example.com".to_string()];
+    let many = ["https://one.example.com".to_string(), "https://two.example.com".to_string()];
+
+    // Act
+    let client = Client::default();
+    let restricted =
+        provider.token(&client, GrantType::ClientCredentials, Default::default(), &one).await;
+    let restricted_many =
+        provider.token(&client, GrantType::ClientCredentials, Default::default(), &many).await;
+
+    // Assert
+    assert!(restricted.is_ok(), "result is not Ok");
+    assert!(restricted_many.is_ok(), "result is not Ok");
+    let (_, claims) = decode(&restricted.unwrap().token);
+    assert_eq!(json!("https://one.example.com"), claims["aud"]);
+    let (_, claims) = decode(&restricted_many.unwrap().token);
+    assert_eq!(json!(["https://one.example.com", "https://two.example.com"]), claims["aud"]);
+}
+
+#[tokio::test]
+async fn test_jwt_access_token_is_looked_up_by_identifier() {
+    // Arrange
+    let mut inner = inner_token_provider();
+    inner
+        .expect_lookup_token()
+        .with(eq(DEFAULT_TOKEN.to_string()), always())
+        .times(1)
+        .returning(|_, _| Ok(None));
+    inner
+        .expect_lookup_token()
+        .with(eq(DEFAULT_REFRESH_TOKEN.to_string()), always())
+        .times(1)
+        .returning(|_, _| Ok(None));
+    let provider = jwt_token_provider(inner);
+    let token = provider
+        .token(&Client::default(), GrantType::ClientCredentials, TokenConfirmation::default(), &[])
+        .await
+        .unwrap();
+
+    // Act
+    let access_token = provider.lookup_token(token.token, Some(TokenTypeHint::AccessToken)).await;
+    let refresh_token = provider.lookup_token(token.refresh_token.unwrap(), None).await;
+
+    // Assert
+    assert!(access_token.is_ok(), "result is not Ok");
+    assert!(refresh_token.is_ok(), "result is not Ok");
+}
+
+#[tokio::test]
+async fn test_forged_jwt_access_token_is_not_looked_up() {
+    // Arrange
+    let mut inner = inner_token_provider();
+    inner.expect_lookup_token().with(eq(DEFAULT_TOKEN.to_string()), always()).returning(|_, _| {
+        Ok(Some(IssuedToken {
+            client_id: DEFAULT_CLIENT_ID.to_string(),
+            grant: GrantType::ClientCredentials,
+            token_type: TokenTypeHint::AccessToken,
+            valid_until: Instant::now() + Duration::from_secs(3600),
+            confirmation: TokenConfirmation::default(),
+        }))
+    });
+    let provider = jwt_token_provider(inner);
+    let token = provider
+        .token(&Client::default(), GrantType::ClientCredentials, TokenConfirmation::default(), &[])
+        .await
+        .unwrap()
+        .token;
+    let (_, mut claims) = decode(&token);
+    claims["scope"] = json!("admin");
+    let key = SigningKey { kid: "key".to_string(), algorithm: JwsAlgorithm::ES256 };
+    let forged = sign_jwt(&TestKeySource, &key, ACCESS_TOKEN_JWT_TYPE, &claims).await.unwrap();
+    let unsigned =
+        token.rsplit_once('.').map(|(signing_input, _)| format!("{signing_input}.")).unwrap();
+    claims["jti"] = json!(DEFAULT_TOKEN);
+    let forged_jti = sign_jwt(&TestKeySource, &key, ACCESS_TOKEN_JWT_TYPE, &claims).await.unwrap();
+
+    // Act
+    let issued = provider.lookup_token(token, None).await;
+    let forged = provider.lookup_token(forged, None).await;
+    let unsigned = provider.lookup_token(unsigned, None).await;
+    let forged_jti = provider.lookup_token(forged_jti, None).await;
+    let wrapped = provider.lookup_token(DEFAULT_TOKEN.to_string(), None).await;
+
+    // Assert
+    assert!(issued.unwrap().is_some(), "issued token is not found");
+    assert_eq!(None, forged.unwrap());
+    assert_eq!(None, unsigned.unwrap());
+    assert_eq!(None, forged_jti.unwrap());
+    assert_eq!(None, wrapped.unwrap());
+}
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A key store that keeps the key set in memory, and signs with the key id as signature.
+#[derive(Default)]
+struct TestKeyStore {
+    key_set: Mutex<Option<KeySet>>,
+    generated: Mutex<u32>,
+}
+
+impl TestKeyStore {
+    fn with_key_set(key_set: KeySet) -> Self {
+        Self { key_set: Mutex::new(Some(key_set)), ..Default::default() }
+    }
+}
+
+fn managed_key(kid: &str) -> ManagedKey {
+    ManagedKey {
+        kid: kid.to_string(),
+        algorithm: JwsAlgorithm::EdDSA,
+        public_key: serde_json::from_value(json!({ "kty": "OKP", "crv": "Ed25519", "x": kid }))
+            .unwrap(),
+    }
+}
+
+#[async_trait]
+impl KeyStore for TestKeyStore {
+    type Error = ();
+
+    async fn load_key_set(&self) -> Result<Option<KeySet>, Self::Error> {
+        Ok(self.key_set.lock().unwrap().clone())
+    }
+
+    async fn save_key_set(&self, key_set: &KeySet) -> Result<(), Self::Error> {
+        *self.key_set.lock().unwrap() = Some(key_set.clone());
+        Ok(())
+    }
+
+    async fn generate_key(&self) -> Result<ManagedKey, Self::Error> {
+        let mut generated = self.generated.lock().unwrap();
+        *generated += 1;
+        Ok(managed_key(&format!("generated-{generated}")))
+    }
+
+    async fn sign(&self, key: &SigningKey, _signing_input: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        Ok(key.kid.as_bytes().to_vec())
+    }
+}
+
+fn key_manager(store: TestKeyStore) -> KeyManager<TestKeyStore> {
+    KeyManager::new(store, 30 * DAY, DAY)
+}
+
+/// A key set with an active key that was activated the given time ago,
+/// a retired key that is still valid and one that expired.
+fn key_set(active_for: Duration) -> KeySet {
+    let now = SystemTime::now();
+    KeySet {
+        active: managed_key("active"),
+        active_since: now - active_for,
+        next: Some(managed_key("next")),
+        retired: vec![
+            RetiredKey { key: managed_key("retired"), expires_at: now + DAY },
+            RetiredKey { key: managed_key("expired"), expires_at: now - DAY },
+        ],
+    }
+}
+
+fn kids(keys: &[Jwk]) -> Vec<&str> {
+    keys.iter().filter_map(|key| key.kid.as_deref()).collect()
+}
+
+#[tokio::test]
+async fn test_key_manager_creates_initial_keys() {
+    // Arrange
+    let manager = key_manager(TestKeyStore::default());
+
+    // Act
+    let result = manager.key_set().await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok");
+    let key_set = result.unwrap();
+    assert_eq!("generated-1", key_set.active.kid);
+    assert_eq!(Some("generated-2"), key_set.next.as_ref().map(|key| key.kid.as_str()));
+    assert!(key_set.retired.is_empty());
+    assert_eq!(Some(key_set), manager.store.load_key_set().await.unwrap());
+}
+
+#[tokio::test]
+async fn test_key_manager_rotates_when_due() {
+    // Arrange
+    let manager = key_manager(TestKeyStore::with_key_set(key_set(31 * DAY)));
+
+    // Act
+    let result = manager.key_set().await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok");
+    let key_set = result.unwrap();
+    assert_eq!("next", key_set.active.kid);
+    assert!(key_set.active_since > SystemTime::now() - Duration::from_secs(60));
+    assert_eq!(Some("generated-1"), key_set.next.as_ref().map(|key| key.kid.as_str()));
+    assert_eq!(
+        vec!["retired", "active"],
+        key_set.retired.iter().map(|retired| retired.key.kid.as_str()).collect::<Vec<_>>()
+    );
+    assert!(key_set.retired[1].expires_at > SystemTime::now() + DAY - Duration::from_secs(60));
+    assert_eq!(Some(key_set), manager.store.load_key_set().await.unwrap());
+}
+
+#[tokio::test]
+async fn test_key_manager_keeps_keys_until_due() {
+    // Arrange
+    let manager = key_manager(TestKeyStore::with_key_set(key_set(29 * DAY)));
+
+    // Act
+    let result = manager.key_set().await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok");
+    let key_set = result.unwrap();
+    assert_eq!("active", key_set.active.kid);
+    assert_eq!(Some("next"), key_set.next.as_ref().map(|key| key.kid.as_str()));
+    assert_eq!(
+        vec!["retired"],
+        key_set.retired.iter().map(|retired| retired.key.kid.as_str()).collect::<Vec<_>>()
+    );
+}
+
+#[tokio::test]
+async fn test_key_manager_rotates_on_demand() {
+    // Arrange
+    let manager = key_manager(TestKeyStore::with_key_set(key_set(DAY)));
+
+    // Act
+    let result = manager.rotate().await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok");
+    let key_set = result.unwrap();
+    assert_eq!("next", key_set.active.kid);
+    assert_eq!(Some(key_set), manager.store.load_key_set().await.unwrap());
+}
+
+#[tokio::test]
+async fn test_jwks_contains_published_keys() {
+    // Arrange
+    let manager = key_manager(TestKeyStore::with_key_set(key_set(DAY)));
+
+    // Act
+    let result = manager.jwks().await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok");
+    let jwks = result.unwrap();
+    assert_eq!(vec!["active", "next", "retired"], kids(&jwks.keys));
+    let FrontendResponse::Success { json } = jwks.into_frontend_response() else {
+        panic!("response is not a success");
+    };
+    assert_eq!(
+        json!({ "kty": "OKP", "kid": "active", "alg": "EdDSA", "use": "sig", "crv": "Ed25519", "x": "active" }),
+        json["keys"][0]
+    );
+}
+
+#[tokio::test]
+async fn test_jwks_never_contains_private_keys() {
+    // Arrange
+    let mut key_set = key_set(DAY);
+    key_set.next.as_mut().unwrap().public_key.other.insert("d".to_string(), json!("private"));
+    let manager = key_manager(TestKeyStore::with_key_set(key_set));
+
+    // Act
+    let result = manager.jwks().await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok");
+    assert_eq!(vec!["active", "retired"], kids(&result.unwrap().keys));
+}
+
+#[tokio::test]
+async fn test_verification_accepts_active_and_retired_keys() {
+    // Arrange
+    let manager = key_manager(TestKeyStore::with_key_set(key_set(DAY)));
+
+    for (kid, accepted) in
+        [("active", true), ("retired", true), ("next", false), ("expired", false), ("other", false)]
+    {
+        // Act
+        let result = manager.verification_key(kid).await;
+
+        // Assert
+        assert!(result.is_ok(), "result is not Ok");
+        assert_eq!(accepted, result.unwrap().is_some(), "unexpected result for {kid}");
+    }
+}
+
+#[tokio::test]
+async fn test_key_manager_signs_with_active_key() {
+    // Arrange
+    let manager = key_manager(TestKeyStore::with_key_set(key_set(31 * DAY)));
+
+    // Act
+    let key = manager.signing_key().await.unwrap();
+    let result = sign_jwt(&manager, &key, ACCESS_TOKEN_JWT_TYPE, &json!({ "sub": "1" })).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok");
+    let jws = CompactJws::parse(&result.unwrap()).unwrap();
+    assert_eq!("EdDSA", jws.header.alg);
+    assert_eq!(Some("next"), jws.header.kid.as_deref());
+    assert_eq!(b"next".to_vec(), jws.signature);
+}
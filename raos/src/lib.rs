@@ -12,14 +12,36 @@
 
 pub use async_trait::async_trait;
 
+/// The assertion module handles the JWT bearer authorization grant.
+pub mod assertion;
 /// The authorization module handles the authorization code flow.
 pub mod authorize;
 /// The builder module contains the builder for the manager.
 pub mod builder;
 /// The common module contains common types used throughout the library.
 pub mod common;
+/// The device module handles the device authorization grant.
+pub mod device;
+/// The dpop module handles DPoP proofs used to sender-constrain tokens.
+pub mod dpop;
+/// The exchange module handles the token exchange grant.
+pub mod exchange;
+/// The jwt module contains the issuer of JWT access tokens and the management of the keys they are signed with.
+#[cfg(feature = "jwt")]
+pub mod jwt;
 /// The manager module contains the OAuthManager.
 pub mod manager;
+/// The metadata module contains the authorization server metadata document.
+pub mod metadata;
+/// The oidc module contains the OpenID Connect layer, which issues ID tokens and serves the UserInfo endpoint.
+#[cfg(feature = "oidc")]
+pub mod oidc;
+/// The par module handles pushed authorization requests.
+pub mod par;
+/// The registration module handles dynamic client registration and the management of registered clients.
+pub mod registration;
+/// The resource module validates the bearer access tokens sent to protected resources.
+pub mod resource;
 /// Test module, contains test support code, unit tests and integration tests.
 #[cfg(any(test, feature = "_doctest"))]
 pub mod test;
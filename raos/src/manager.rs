@@ -1,10 +1,26 @@
 use crate::{
+    assertion::JwtBearerProvider,
     authorize::AuthorizationProvider,
     builder::{
         NeedsAuthorizationProvider, NeedsClientProvider, NeedsTokenProvider, OAuthManagerBuilder,
     },
-    common::model::{Client, ClientProvider},
-    token::TokenProvider,
+    common::{
+        jose::JwsVerifier,
+        model::{Client, ClientProvider},
+    },
+    device::DeviceAuthorizationProvider,
+    dpop::DPoPProvider,
+    exchange::TokenExchangeProvider,
+    metadata::Endpoint,
+    par::PushedAuthorizationProvider,
+    registration::ClientRegistrationProvider,
+    token::{GrantTypeHandler, TokenProvider},
+};
+#[cfg(feature = "oidc")]
+use crate::{jwt::SigningKeySource, oidc::UserInfoProvider};
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
 };
 
 /// The OAuthManager is the main struct that is used to interact with the OAuth2.1 server.
@@ -16,24 +32,80 @@ pub struct OAuthManager<OwnerIdType, ErrorType, Extras> {
     pub(crate) authorization_provider:
         Box<dyn AuthorizationProvider<OwnerId = OwnerIdType, Error = ErrorType, Extras = Extras>>,
     pub(crate) token_provider: Box<dyn TokenProvider<OwnerId = OwnerIdType, Error = ErrorType>>,
+    pub(crate) device_authorization_provider:
+        Option<Box<dyn DeviceAuthorizationProvider<OwnerId = OwnerIdType, Error = ErrorType>>>,
+    pub(crate) pushed_authorization_provider:
+        Option<Box<dyn PushedAuthorizationProvider<Error = ErrorType>>>,
+    pub(crate) dpop_provider: Option<Box<dyn DPoPProvider<Error = ErrorType>>>,
+    pub(crate) jws_verifier: Option<Box<dyn JwsVerifier>>,
+    pub(crate) client_registration_provider:
+        Option<Box<dyn ClientRegistrationProvider<Error = ErrorType>>>,
+    pub(crate) token_exchange_provider:
+        Option<Box<dyn TokenExchangeProvider<OwnerId = OwnerIdType, Error = ErrorType>>>,
+    pub(crate) jwt_bearer_provider:
+        Option<Box<dyn JwtBearerProvider<OwnerId = OwnerIdType, Error = ErrorType>>>,
+    pub(crate) grant_type_handlers:
+        HashMap<String, Box<dyn GrantTypeHandler<OwnerId = OwnerIdType, Error = ErrorType>>>,
+    #[cfg(feature = "oidc")]
+    pub(crate) user_info_provider:
+        Option<Box<dyn UserInfoProvider<OwnerId = OwnerIdType, Error = ErrorType>>>,
+    #[cfg(feature = "oidc")]
+    pub(crate) signing_key_source: Option<Box<dyn SigningKeySource<Error = ErrorType>>>,
     pub(crate) config: OAuthConfig,
 }
 
 impl OAuthManager<(), (), ()> {
     /// Create a new OAuthManagerBuilder to build an OAuthManager.
     /// This function is the entry point to create an OAuthManager.
-    pub fn builder(
-    ) -> OAuthManagerBuilder<NeedsClientProvider, NeedsAuthorizationProvider, NeedsTokenProvider>
-    {
+    pub fn builder<O, E>() -> OAuthManagerBuilder<
+        NeedsClientProvider,
+        NeedsAuthorizationProvider,
+        NeedsTokenProvider,
+        O,
+        E,
+    > {
         OAuthManagerBuilder::new()
     }
 }
 
-#[derive(Default)]
 pub(crate) struct OAuthConfig {
     pub(crate) require_code_challenge: CodeChallengeRequirement,
     pub(crate) disallow_plain_code_challenge: bool,
     pub(crate) authorization_server_identifier: Option<String>,
+    pub(crate) endpoints: HashMap<Endpoint, String>,
+    pub(crate) device_code_interval: Duration,
+    pub(crate) require_pushed_authorization: PushedAuthorizationRequirement,
+    pub(crate) dpop_proof_lifetime: Duration,
+    pub(crate) mutual_tls: bool,
+    pub(crate) id_token_lifetime: Duration,
+}
+
+impl Default for OAuthConfig {
+    fn default() -> Self {
+        Self {
+            require_code_challenge: CodeChallengeRequirement::default(),
+            disallow_plain_code_challenge: false,
+            authorization_server_identifier: None,
+            endpoints: HashMap::new(),
+            device_code_interval: Duration::from_secs(5),
+            require_pushed_authorization: PushedAuthorizationRequirement::default(),
+            dpop_proof_lifetime: Duration::from_secs(60),
+            mutual_tls: false,
+            id_token_lifetime: Duration::from_secs(3600),
+        }
+    }
+}
+
+impl OAuthConfig {
+    /// Get the configured url of an endpoint, or derive it from the authorization server identifier.
+    pub(crate) fn endpoint_url(&self, endpoint: Endpoint) -> Option<String> {
+        if let Some(url) = self.endpoints.get(&endpoint) {
+            return Some(url.clone());
+        }
+
+        let identifier = self.authorization_server_identifier.as_ref()?;
+        Some(format!("{}{}", identifier.trim_end_matches('/'), endpoint.default_path()))
+    }
 }
 
 #[derive(Default)]
@@ -53,3 +125,21 @@ impl CodeChallengeRequirement {
         }
     }
 }
+
+#[derive(Default)]
+pub(crate) enum PushedAuthorizationRequirement {
+    Always,
+    ForClients(HashSet<String>),
+    #[default]
+    Never,
+}
+
+impl PushedAuthorizationRequirement {
+    pub(crate) fn is_required_for(&self, client_id: &str) -> bool {
+        match self {
+            PushedAuthorizationRequirement::Always => true,
+            PushedAuthorizationRequirement::ForClients(clients) => clients.contains(client_id),
+            PushedAuthorizationRequirement::Never => false,
+        }
+    }
+}
@@ -0,0 +1,177 @@
+pub use response::*;
+
+use crate::{
+    assertion::JWT_BEARER_GRANT_TYPE,
+    common::{
+        frontend::{OAuthError, OAuthValidationError},
+        jose::hmac::HMAC_ALGORITHMS,
+    },
+    device::DEVICE_CODE_GRANT_TYPE,
+    exchange::TOKEN_EXCHANGE_GRANT_TYPE,
+    manager::{OAuthManager, PushedAuthorizationRequirement},
+};
+
+mod response;
+#[cfg(test)]
+mod test;
+
+/// The endpoints served by the authorization server, as advertised in the metadata document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    /// The authorization endpoint, handled by [OAuthManager::handle_authorization_request].
+    Authorization,
+    /// The token endpoint, handled by [OAuthManager::handle_token_request].
+    Token,
+    /// The revocation endpoint, handled by [OAuthManager::handle_revocation_request].
+    Revocation,
+    /// The introspection endpoint, handled by [OAuthManager::handle_introspection_request].
+    Introspection,
+    /// The device authorization endpoint, handled by [OAuthManager::handle_device_authorization_request].
+    DeviceAuthorization,
+    /// The pushed authorization request endpoint, handled by [OAuthManager::handle_pushed_authorization_request].
+    PushedAuthorization,
+    /// The user-facing page where the resource owner enters the user code,
+    /// handled by [OAuthManager::handle_device_verification_request].
+    DeviceVerification,
+    /// The OpenID Connect UserInfo endpoint, handled by `OAuthManager::handle_userinfo_request`
+    /// when the `oidc` feature is enabled.
+    UserInfo,
+    /// The JSON Web Key Set document containing the public keys the JWTs of the authorization server
+    /// can be verified with, like the one generated by `KeyManager::jwks` when the `jwt` feature is enabled.
+    Jwks,
+    /// The client registration endpoint, handled by [OAuthManager::handle_client_registration_request].
+    /// The client configuration endpoints, handled by [OAuthManager::handle_client_management_request],
+    /// are served below this url.
+    Registration,
+}
+
+impl Endpoint {
+    /// The path this endpoint is assumed to be served on, relative to the authorization server identifier,
+    /// if no url was configured through [set_endpoint](crate::builder::OAuthManagerBuilder::set_endpoint).
+    pub fn default_path(&self) -> &'static str {
+        match self {
+            Endpoint::Authorization => "/authorize",
+            Endpoint::Token => "/token",
+            Endpoint::Revocation => "/revoke",
+            Endpoint::Introspection => "/introspect",
+            Endpoint::DeviceAuthorization => "/device_authorization",
+            Endpoint::DeviceVerification => "/device",
+            Endpoint::PushedAuthorization => "/par",
+            Endpoint::UserInfo => "/userinfo",
+            Endpoint::Jwks => "/.well-known/jwks.json",
+            Endpoint::Registration => "/register",
+        }
+    }
+}
+
+impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
+    /// Generate the authorization server metadata document, as described in RFC 8414.
+    /// This document is meant to be served on `/.well-known/oauth-authorization-server`,
+    /// and is derived from the configuration of the manager.
+    ///
+    /// # Returns
+    /// An [AuthorizationServerMetadata] that can be used to build a response to the client, which in turn
+    /// implements the [FrontendResponse](crate::common::frontend::FrontendResponse) trait.
+    ///
+    /// # Errors
+    /// This function will return an [OAuthError] if no authorization server identifier was configured,
+    /// as the issuer is a required field in the metadata document.
+    ///
+    /// # Example
+    /// ```
+    /// # use raos::test::doctest::oauth_manager_from_application_state;
+    ///
+    /// let manager = oauth_manager_from_application_state();
+    /// let metadata = manager.authorization_server_metadata().unwrap();
+    /// assert_eq!("https://example.com/token", metadata.token_endpoint);
+    /// ```
+    pub fn authorization_server_metadata(
+        &self,
+    ) -> Result<AuthorizationServerMetadata, OAuthError<E>> {
+        let Some(issuer) = self.config.authorization_server_identifier.clone() else {
+            return Err(OAuthValidationError::NoAuthorizationServerIdentifier.into());
+        };
+        let endpoint = |endpoint| self.config.endpoint_url(endpoint).unwrap_or_default();
+
+        let mut grant_types_supported =
+            vec!["authorization_code", "client_credentials", "refresh_token"];
+        let mut device_authorization_endpoint = None;
+        if self.device_authorization_provider.is_some() {
+            grant_types_supported.push(DEVICE_CODE_GRANT_TYPE);
+            device_authorization_endpoint = Some(endpoint(Endpoint::DeviceAuthorization));
+        }
+        if self.token_exchange_provider.is_some() {
+            grant_types_supported.push(TOKEN_EXCHANGE_GRANT_TYPE);
+        }
+        if self.jwt_bearer_provider.is_some() {
+            grant_types_supported.push(JWT_BEARER_GRANT_TYPE);
+        }
+        let mut grant_types_supported: Vec<String> =
+            grant_types_supported.into_iter().map(str::to_string).collect();
+        // Custom grant types are sorted, so the metadata does not depend on the order of the handlers
+        let mut extension_grant_types: Vec<String> =
+            self.grant_type_handlers.keys().cloned().collect();
+        extension_grant_types.sort();
+        grant_types_supported.extend(extension_grant_types);
+
+        let pushed_authorization_request_endpoint = self
+            .pushed_authorization_provider
+            .is_some()
+            .then(|| endpoint(Endpoint::PushedAuthorization));
+        let require_pushed_authorization_requests = matches!(
+            self.config.require_pushed_authorization,
+            PushedAuthorizationRequirement::Always
+        );
+
+        let registration_endpoint =
+            self.client_registration_provider.is_some().then(|| endpoint(Endpoint::Registration));
+
+        let dpop_signing_alg_values_supported = match (&self.dpop_provider, &self.jws_verifier) {
+            (Some(_), Some(jws_verifier)) => Some(jws_verifier.supported_algorithms()),
+            _ => None,
+        };
+
+        // Client assertions signed with a shared secret are always supported, private keys require a JWS verifier
+        let mut client_auth_methods =
+            vec!["client_secret_basic", "client_secret_post", "client_secret_jwt"];
+        let mut token_endpoint_auth_signing_alg_values_supported = HMAC_ALGORITHMS.to_vec();
+        if let Some(jws_verifier) = &self.jws_verifier {
+            client_auth_methods.push("private_key_jwt");
+            token_endpoint_auth_signing_alg_values_supported
+                .extend(jws_verifier.supported_algorithms());
+        }
+        if self.config.mutual_tls {
+            client_auth_methods.extend(["tls_client_auth", "self_signed_tls_client_auth"]);
+        }
+
+        let code_challenge_methods_supported = if self.config.disallow_plain_code_challenge {
+            vec!["S256"]
+        } else {
+            vec!["S256", "plain"]
+        };
+
+        Ok(AuthorizationServerMetadata {
+            authorization_endpoint: endpoint(Endpoint::Authorization),
+            token_endpoint: endpoint(Endpoint::Token),
+            revocation_endpoint: endpoint(Endpoint::Revocation),
+            introspection_endpoint: endpoint(Endpoint::Introspection),
+            device_authorization_endpoint,
+            pushed_authorization_request_endpoint,
+            registration_endpoint,
+            require_pushed_authorization_requests,
+            dpop_signing_alg_values_supported,
+            response_types_supported: vec!["code"],
+            grant_types_supported,
+            code_challenge_methods_supported,
+            token_endpoint_auth_methods_supported: [client_auth_methods.as_slice(), &["none"]]
+                .concat(),
+            token_endpoint_auth_signing_alg_values_supported,
+            revocation_endpoint_auth_methods_supported: [client_auth_methods.as_slice(), &["none"]]
+                .concat(),
+            introspection_endpoint_auth_methods_supported: client_auth_methods,
+            tls_client_certificate_bound_access_tokens: self.config.mutual_tls,
+            authorization_response_iss_parameter_supported: true,
+            issuer,
+        })
+    }
+}
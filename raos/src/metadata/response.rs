@@ -0,0 +1,57 @@
+use serde::Serialize;
+
+use crate::common::frontend::{FrontendResponse, FrontendResponseExt};
+
+/// The authorization server metadata document, as described in RFC 8414.
+/// This struct is serialized into JSON to be served on `/.well-known/oauth-authorization-server`.
+#[derive(Debug, Serialize)]
+pub struct AuthorizationServerMetadata {
+    /// The authorization server's issuer identifier.
+    pub issuer: String,
+    /// The url of the authorization endpoint.
+    pub authorization_endpoint: String,
+    /// The url of the token endpoint.
+    pub token_endpoint: String,
+    /// The url of the revocation endpoint.
+    pub revocation_endpoint: String,
+    /// The url of the introspection endpoint.
+    pub introspection_endpoint: String,
+    /// The url of the device authorization endpoint, if the device authorization grant is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_authorization_endpoint: Option<String>,
+    /// The url of the pushed authorization request endpoint, if pushed authorization requests are enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pushed_authorization_request_endpoint: Option<String>,
+    /// The url of the client registration endpoint, if dynamic client registration is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registration_endpoint: Option<String>,
+    /// Whether all clients are required to use pushed authorization requests.
+    pub require_pushed_authorization_requests: bool,
+    /// The JWS algorithms supported for DPoP proofs, if DPoP is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dpop_signing_alg_values_supported: Option<Vec<&'static str>>,
+    /// The response types supported by the authorization endpoint.
+    pub response_types_supported: Vec<&'static str>,
+    /// The grant types supported by the token endpoint.
+    pub grant_types_supported: Vec<String>,
+    /// The PKCE code challenge methods supported by the authorization endpoint.
+    pub code_challenge_methods_supported: Vec<&'static str>,
+    /// The client authentication methods supported by the token endpoint.
+    pub token_endpoint_auth_methods_supported: Vec<&'static str>,
+    /// The JWS algorithms supported for client assertions used to authenticate at the token endpoint.
+    pub token_endpoint_auth_signing_alg_values_supported: Vec<&'static str>,
+    /// The client authentication methods supported by the revocation endpoint.
+    pub revocation_endpoint_auth_methods_supported: Vec<&'static str>,
+    /// The client authentication methods supported by the introspection endpoint.
+    pub introspection_endpoint_auth_methods_supported: Vec<&'static str>,
+    /// Whether the authorization server binds access tokens to the certificate the client used for mutual TLS.
+    pub tls_client_certificate_bound_access_tokens: bool,
+    /// Whether the authorization server sends the iss parameter in authorization responses.
+    pub authorization_response_iss_parameter_supported: bool,
+}
+
+impl FrontendResponseExt for AuthorizationServerMetadata {
+    fn into_frontend_response(self) -> FrontendResponse {
+        FrontendResponse::Success { json: serde_json::to_value(self).unwrap() }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{
+    common::frontend::{OAuthError, OAuthValidationError},
+    metadata::Endpoint,
+    test::TestEnvironment,
+};
+
+#[test]
+fn test_metadata_requires_issuer() {
+    // The "issuer" metadata value is REQUIRED.
+
+    // Arrange
+    let manager = TestEnvironment::new().build();
+
+    // Act
+    let result = manager.authorization_server_metadata();
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::NoAuthorizationServerIdentifier),
+        result.unwrap_err()
+    );
+}
+
+#[test]
+fn test_metadata_endpoints_derived_from_issuer() {
+    // Arrange
+    let mut manager = TestEnvironment::new().build();
+    manager.config.authorization_server_identifier = Some("https://example.com/".to_string());
+    manager.config.endpoints.insert(Endpoint::Token, "https://token.example.com".to_string());
+
+    // Act
+    let result = manager.authorization_server_metadata();
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    let metadata = result.unwrap();
+    assert_eq!("https://example.com/", metadata.issuer);
+    assert_eq!("https://example.com/authorize", metadata.authorization_endpoint);
+    assert_eq!("https://token.example.com", metadata.token_endpoint);
+    assert_eq!("https://example.com/revoke", metadata.revocation_endpoint);
+}
+
+#[test]
+fn test_metadata_code_challenge_methods_follow_configuration() {
+    // Arrange
+    let mut manager = TestEnvironment::new().build();
+    manager.config.authorization_server_identifier = Some("https://example.com".to_string());
+
+    // Act
+    let with_plain = manager.authorization_server_metadata().unwrap();
+    manager.config.disallow_plain_code_challenge = true;
+    let without_plain = manager.authorization_server_metadata().unwrap();
+
+    // Assert
+    assert_eq!(vec!["S256", "plain"], with_plain.code_challenge_methods_supported);
+    assert_eq!(vec!["S256"], without_plain.code_challenge_methods_supported);
+}
+
+#[test]
+fn test_metadata_advertises_mutual_tls() {
+    // Arrange
+    let mut manager = TestEnvironment::new().build();
+    manager.config.authorization_server_identifier = Some("https://example.com".to_string());
+
+    // Act
+    let without_mutual_tls = manager.authorization_server_metadata().unwrap();
+    manager.config.mutual_tls = true;
+    let with_mutual_tls = manager.authorization_server_metadata().unwrap();
+
+    // Assert
+    assert!(!without_mutual_tls.tls_client_certificate_bound_access_tokens);
+    assert!(!without_mutual_tls.token_endpoint_auth_methods_supported.contains(&"tls_client_auth"));
+    assert!(with_mutual_tls.tls_client_certificate_bound_access_tokens);
+    assert!(with_mutual_tls.token_endpoint_auth_methods_supported.contains(&"tls_client_auth"));
+    assert!(with_mutual_tls
+        .token_endpoint_auth_methods_supported
+        .contains(&"self_signed_tls_client_auth"));
+}
@@ -0,0 +1,106 @@
+use serde::Serialize;
+
+use crate::{
+    common::{
+        frontend::{FrontendResponse, FrontendResponseExt, OAuthError, OAuthValidationError},
+        model::OPENID_SCOPE,
+    },
+    manager::OAuthManager,
+    metadata::{AuthorizationServerMetadata, Endpoint},
+    oidc::SCOPED_CLAIMS,
+};
+
+/// The claims that are set by the authorization server, regardless of the granted scopes.
+const ISSUED_CLAIMS: &[&str] =
+    &["iss", "sub", "aud", "exp", "iat", "auth_time", "nonce", "at_hash"];
+
+/// The OpenID Provider metadata document, as described in OpenID Connect Discovery 1.0 section 3.
+/// This struct is serialized into JSON to be served on `/.well-known/openid-configuration`.
+#[derive(Debug, Serialize)]
+pub struct OpenIdProviderMetadata {
+    /// The authorization server metadata, which the OpenID Provider metadata extends.
+    #[serde(flatten)]
+    pub authorization_server: AuthorizationServerMetadata,
+    /// The url of the UserInfo endpoint.
+    pub userinfo_endpoint: String,
+    /// The url of the JSON Web Key Set document, containing the keys ID tokens are signed with.
+    pub jwks_uri: String,
+    /// The scopes the claims about the resource owner are released for.
+    pub scopes_supported: Vec<&'static str>,
+    /// The claims that can be included in ID tokens and UserInfo responses.
+    pub claims_supported: Vec<&'static str>,
+    /// The JWS algorithms ID tokens are signed with.
+    pub id_token_signing_alg_values_supported: Vec<&'static str>,
+    /// The JWS algorithms signed UserInfo responses are signed with.
+    pub userinfo_signing_alg_values_supported: Vec<&'static str>,
+    /// The subject identifier types supported, only `public` as subject identifiers are the same for every client.
+    pub subject_types_supported: Vec<&'static str>,
+}
+
+impl FrontendResponseExt for OpenIdProviderMetadata {
+    fn into_frontend_response(self) -> FrontendResponse {
+        FrontendResponse::Success { json: serde_json::to_value(self).unwrap() }
+    }
+}
+
+impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
+    /// Generate the OpenID Provider metadata document, as described in OpenID Connect Discovery 1.0.
+    /// This document is meant to be served on `/.well-known/openid-configuration`,
+    /// and is derived from the configuration of the manager, like the
+    /// [authorization server metadata](OAuthManager::authorization_server_metadata) it extends.
+    ///
+    /// # Returns
+    /// An [OpenIdProviderMetadata] that can be used to build a response to the client, which in turn
+    /// implements the [FrontendResponse](crate::common::frontend::FrontendResponse) trait.
+    ///
+    /// # Errors
+    /// This function will return an [OAuthError] if OpenID Connect is not enabled, if no authorization server
+    /// identifier was configured, or if the signing key source fails to provide the signing key.
+    ///
+    /// # Example
+    /// ```
+    /// # use raos::test::doctest::oauth_manager_from_application_state;
+    ///
+    /// let manager = oauth_manager_from_application_state();
+    /// # tokio_test::block_on(async {
+    /// let metadata = manager.openid_provider_metadata().await.unwrap();
+    /// assert_eq!("https://example.com/userinfo", metadata.userinfo_endpoint);
+    /// # });
+    /// ```
+    pub async fn openid_provider_metadata(&self) -> Result<OpenIdProviderMetadata, OAuthError<E>> {
+        if self.user_info_provider.is_none() {
+            return Err(OAuthValidationError::OpenIdConnectNotSupported.into());
+        }
+        let authorization_server = self.authorization_server_metadata()?;
+        let endpoint = |endpoint| self.config.endpoint_url(endpoint).unwrap_or_default();
+
+        // ID tokens and UserInfo responses are always signed with the active key
+        let algorithm = self
+            .signing_key_source()
+            .signing_key()
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?
+            .algorithm
+            .as_str();
+
+        let scopes_supported = std::iter::once(OPENID_SCOPE)
+            .chain(SCOPED_CLAIMS.iter().map(|(scope, _)| *scope))
+            .collect();
+        let claims_supported = ISSUED_CLAIMS
+            .iter()
+            .chain(SCOPED_CLAIMS.iter().flat_map(|(_, claims)| claims.iter()))
+            .copied()
+            .collect();
+
+        Ok(OpenIdProviderMetadata {
+            authorization_server,
+            userinfo_endpoint: endpoint(Endpoint::UserInfo),
+            jwks_uri: endpoint(Endpoint::Jwks),
+            scopes_supported,
+            claims_supported,
+            id_token_signing_alg_values_supported: vec![algorithm],
+            userinfo_signing_alg_values_supported: vec![algorithm],
+            subject_types_supported: vec!["public"],
+        })
+    }
+}
@@ -0,0 +1,162 @@
+pub use discovery::*;
+pub use provider::*;
+pub use userinfo::*;
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::{
+    common::{
+        frontend::{OAuthError, OAuthValidationError},
+        model::OPENID_SCOPE,
+    },
+    jwt::{sign_jwt, SigningKeySource},
+    manager::OAuthManager,
+    token::{GrantType, ValidatedTokenRequest},
+};
+
+mod discovery;
+mod provider;
+#[cfg(test)]
+mod test;
+mod userinfo;
+
+/// The type of ID tokens, sent in the `typ` header.
+const ID_TOKEN_JWT_TYPE: &str = "JWT";
+
+/// The claims of an ID token that are set by the authorization server, these can not be overridden by the [UserInfoProvider].
+const REGISTERED_CLAIMS: &[&str] =
+    &["iss", "sub", "aud", "exp", "iat", "auth_time", "nonce", "at_hash", "azp"];
+
+/// The standard claims that are only released when their scope is granted, as described in OpenID Connect Core 1.0 section 5.4.
+const SCOPED_CLAIMS: &[(&str, &[&str])] = &[
+    (
+        "profile",
+        &[
+            "name",
+            "family_name",
+            "given_name",
+            "middle_name",
+            "nickname",
+            "preferred_username",
+            "profile",
+            "picture",
+            "website",
+            "gender",
+            "birthdate",
+            "zoneinfo",
+            "locale",
+            "updated_at",
+        ],
+    ),
+    ("email", &["email", "email_verified"]),
+    ("address", &["address"]),
+    ("phone", &["phone_number", "phone_number_verified"]),
+];
+
+/// Remove the claims the client may not see: the claims set by the authorization server,
+/// and the standard claims of scopes that were not granted. Other claims are left to the [UserInfoProvider].
+fn retain_granted_claims(claims: &mut Map<String, Value>, scope: &[String]) {
+    claims.retain(|claim, _| {
+        !REGISTERED_CLAIMS.contains(&claim.as_str())
+            && SCOPED_CLAIMS.iter().all(|(claim_scope, scoped_claims)| {
+                !scoped_claims.contains(&claim.as_str())
+                    || scope.iter().any(|scope| scope == claim_scope)
+            })
+    });
+}
+
+/// The claims of an ID token, as described in OpenID Connect Core 1.0 section 2.
+#[derive(Debug, Serialize)]
+pub(crate) struct IdTokenClaims {
+    iss: String,
+    sub: String,
+    aud: String,
+    exp: u64,
+    iat: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    auth_time: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nonce: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    at_hash: Option<String>,
+    #[serde(flatten)]
+    claims: Map<String, Value>,
+}
+
+fn seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
+    /// Collect the claims of the ID token for a token request, if one should be issued.
+    /// An ID token is issued when OpenID Connect is enabled, and the `openid` scope was granted through an authorization code.
+    pub(crate) async fn id_token_claims(
+        &self,
+        validated: &ValidatedTokenRequest<U>,
+    ) -> Result<Option<IdTokenClaims>, OAuthError<E>> {
+        let Some(user_info_provider) = &self.user_info_provider else {
+            return Ok(None);
+        };
+        let GrantType::AuthorizationCode { resource_owner, scope, .. } = &validated.grant_type
+        else {
+            return Ok(None);
+        };
+        if !scope.iter().any(|scope| scope == OPENID_SCOPE) {
+            return Ok(None);
+        }
+        let Some(iss) = self.config.authorization_server_identifier.clone() else {
+            return Err(OAuthValidationError::NoAuthorizationServerIdentifier.into());
+        };
+
+        let mut user_info = user_info_provider
+            .user_info(resource_owner, scope)
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?;
+        retain_granted_claims(&mut user_info.claims, scope);
+
+        let now = SystemTime::now();
+        Ok(Some(IdTokenClaims {
+            iss,
+            sub: user_info.sub,
+            aud: validated.client.client_id.clone(),
+            exp: seconds(now + self.config.id_token_lifetime),
+            iat: seconds(now),
+            auth_time: validated.auth_time.map(seconds),
+            nonce: validated.nonce.clone(),
+            at_hash: None,
+            claims: user_info.claims,
+        }))
+    }
+
+    /// Sign an ID token, binding it to the access token issued alongside it through the `at_hash` claim.
+    pub(crate) async fn sign_id_token(
+        &self,
+        mut claims: IdTokenClaims,
+        access_token: &str,
+    ) -> Result<String, OAuthError<E>> {
+        let signing_key_source = self.signing_key_source();
+        let key = signing_key_source
+            .signing_key()
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?;
+
+        // The at_hash is the left-most half of the hash of the access token, using the hash function of the signature
+        let hash = key.algorithm.hash(access_token.as_bytes());
+        claims.at_hash = Some(BASE64_URL_SAFE_NO_PAD.encode(&hash[..hash.len() / 2]));
+
+        sign_jwt(signing_key_source, &key, ID_TOKEN_JWT_TYPE, &claims)
+            .await
+            .map_err(OAuthError::ProviderImplementationError)
+    }
+
+    /// The signing key source configured along with the [UserInfoProvider].
+    fn signing_key_source(&self) -> &dyn SigningKeySource<Error = E> {
+        self.signing_key_source
+            .as_deref()
+            .expect("Unexpected error: OpenID Connect is enabled without a signing key source")
+    }
+}
@@ -0,0 +1,49 @@
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// The claims about a resource owner, as returned by a [UserInfoProvider].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UserInfo {
+    /// The subject identifier of the resource owner, unique and never reassigned.
+    pub sub: String,
+    /// The other claims about the resource owner, like `name` or `email`, as described in OpenID Connect Core 1.0 section 5.1.
+    #[serde(flatten)]
+    pub claims: Map<String, Value>,
+}
+
+/// User info provider trait.
+/// This trait has to be implemented by the end user to use OpenID Connect,
+/// it provides the claims about resource owners that are included in ID tokens and UserInfo responses.
+#[async_trait]
+pub trait UserInfoProvider: 'static + Send + Sync {
+    /// This is the type of the owner id that is used to identify the resource owner.
+    /// This type will need to match the OwnerId used in [TokenProvider](crate::token::TokenProvider).
+    type OwnerId;
+    /// This is the error type that can be returned by the user info provider implementing this trait.
+    type Error;
+
+    /// Get the claims about a resource owner.
+    ///
+    /// # Implementation notes
+    /// Only the claims the granted scopes give access to should be returned, for example
+    /// the `email` and `email_verified` claims for the `email` scope, as described in OpenID Connect Core 1.0 section 5.4.
+    /// The standard claims of the `profile`, `email`, `address` and `phone` scopes are also removed by the library
+    /// when their scope was not granted.
+    /// Claims that are set by the authorization server, like `iss`, `aud` and `nonce`, are ignored.
+    ///
+    /// # Arguments
+    /// * `owner_id` - The resource owner the claims are about.
+    /// * `scopes` - The scopes granted by the resource owner.
+    ///
+    /// # Returns
+    /// The [UserInfo] of the resource owner.
+    ///
+    /// # Errors
+    /// If the user info provider fails to get the claims, through whatever error.
+    async fn user_info(
+        &self,
+        owner_id: &Self::OwnerId,
+        scopes: &[String],
+    ) -> Result<UserInfo, Self::Error>;
+}
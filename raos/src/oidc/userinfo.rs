@@ -0,0 +1,199 @@
+use serde_json::Value;
+
+use crate::{
+    common::{
+        frontend::{
+            FrontendRequest, FrontendRequestMethod, FrontendResponse, FrontendResponseExt,
+            OAuthError, OAuthValidationError,
+        },
+        model::OPENID_SCOPE,
+    },
+    jwt::sign_jwt,
+    manager::OAuthManager,
+    metadata::Endpoint,
+    oidc::{retain_granted_claims, UserInfo},
+    resource::{BearerTokenValidator, ProtectedResourceRequest},
+};
+
+/// The type of signed UserInfo responses, sent in the `typ` header.
+const USER_INFO_JWT_TYPE: &str = "JWT";
+
+/// The response to a UserInfo request.
+#[derive(Debug, PartialEq)]
+pub enum UserInfoResponse {
+    /// The claims about the resource owner, returned as JSON.
+    Json(UserInfo),
+    /// The claims about the resource owner, returned as a signed JWT that includes the `iss` and `aud` claims.
+    /// This is used when the client registered for signed UserInfo responses.
+    Jwt(String),
+}
+
+impl FrontendResponseExt for UserInfoResponse {
+    fn into_frontend_response(self) -> FrontendResponse {
+        match self {
+            UserInfoResponse::Json(user_info) => {
+                FrontendResponse::Success { json: serde_json::to_value(user_info).unwrap() }
+            }
+            UserInfoResponse::Jwt(jwt) => FrontendResponse::Jwt { jwt },
+        }
+    }
+}
+
+impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
+    /// Handle an incoming UserInfo request from a client.
+    /// This function will parse the incoming request, and return the claims about the resource owner
+    /// that authorized the access token.
+    ///
+    /// # Parameters
+    /// - `req` - The unparsed incoming request from the client, represented by a [FrontendRequest]
+    ///
+    /// # Returns
+    /// A [UserInfoResponse] that can be used to build a response to the client, which in turn
+    /// implements the [FrontendResponse] trait.
+    ///
+    /// # Errors
+    /// This function can return an [OAuthError] if the access token is missing or invalid,
+    /// or if any of the providers fail.
+    ///
+    /// # Example
+    /// ```
+    /// # use raos::{
+    /// #    oidc::UserInfoResponse,
+    /// #    test::{doctest::oauth_manager_from_application_state, mock::request_from_raw_http},
+    /// # };
+    ///
+    /// let manager = oauth_manager_from_application_state();
+    /// let req = request_from_raw_http(r#"
+    ///     GET /userinfo HTTP/1.1
+    ///     authorization: Bearer ACCESS_TOKEN
+    /// "#);
+    ///
+    /// # tokio_test::block_on(async {
+    /// let result = manager.handle_userinfo_request(req).await;
+    /// assert!(matches!(result.unwrap(), UserInfoResponse::Json(_)));
+    /// # });
+    /// ```
+    pub async fn handle_userinfo_request(
+        &self,
+        req: impl FrontendRequest,
+    ) -> Result<UserInfoResponse, OAuthError<E>> {
+        // The UserInfo endpoint MUST support the use of the HTTP GET and HTTP POST methods
+        if !matches!(req.request_method(), FrontendRequestMethod::GET | FrontendRequestMethod::POST)
+        {
+            return Err(OAuthValidationError::InvalidRequestMethod {
+                expected: FrontendRequestMethod::GET,
+                actual: req.request_method(),
+            }
+            .into());
+        }
+
+        let request = ProtectedResourceRequest::from_request(&req, false)?;
+        self.handle_userinfo(request).await
+    }
+
+    /// Handle an incoming UserInfo request from a client.
+    /// This function will validate the access token like any other protected resource through
+    /// [handle_protected_resource](OAuthManager::handle_protected_resource), and get the claims about the resource owner from the [UserInfoProvider](crate::oidc::UserInfoProvider).
+    /// Only the standard claims of the granted scopes are returned.
+    ///
+    /// # Parameters
+    /// - `req` - The parsed incoming request from the client, represented by a [ProtectedResourceRequest]
+    ///
+    /// # Returns
+    /// A [UserInfoResponse] that can be used to build a response to the client, which in turn
+    /// implements the [FrontendResponse] trait.
+    ///
+    /// # Errors
+    /// This function can return an [OAuthError] if the access token is unknown, expired or was not
+    /// granted the `openid` scope, or if any of the providers fail.
+    ///
+    /// # Example
+    /// ```
+    /// # use raos::{
+    /// #    common::frontend::FrontendRequestMethod,
+    /// #    oidc::UserInfoResponse,
+    /// #    resource::ProtectedResourceRequest,
+    /// #    test::doctest::oauth_manager_from_application_state,
+    /// # };
+    ///
+    /// let manager = oauth_manager_from_application_state();
+    /// let req = ProtectedResourceRequest {
+    ///     access_token: "ACCESS_TOKEN".to_string(),
+    ///     request_method: FrontendRequestMethod::GET,
+    ///     dpop_proof: None,
+    ///     client_certificate: None,
+    /// };
+    ///
+    /// # tokio_test::block_on(async {
+    /// let result = manager.handle_userinfo(req).await;
+    /// let UserInfoResponse::Json(user_info) = result.unwrap() else { panic!() };
+    /// assert_eq!("SUBJECT", user_info.sub);
+    /// # });
+    /// ```
+    pub async fn handle_userinfo(
+        &self,
+        req: ProtectedResourceRequest,
+    ) -> Result<UserInfoResponse, OAuthError<E>> {
+        let Some(user_info_provider) = &self.user_info_provider else {
+            return Err(OAuthValidationError::OpenIdConnectNotSupported.into());
+        };
+
+        // DPoP proofs sent to the UserInfo endpoint are made for its configured url
+        let mut validator = BearerTokenValidator::new();
+        if let Some(uri) = self.config.endpoint_url(Endpoint::UserInfo) {
+            validator = validator.accept_dpop(uri);
+        }
+        let issued = self.handle_protected_resource(req, &validator).await?;
+
+        // The access token must be obtained through an OpenID Connect authentication request
+        let (Some(resource_owner), Some(scope)) =
+            (issued.grant.resource_owner(), issued.grant.scope())
+        else {
+            return Err(OAuthValidationError::InsufficientScope(OPENID_SCOPE.to_string()).into());
+        };
+        if !scope.iter().any(|scope| scope == OPENID_SCOPE) {
+            return Err(OAuthValidationError::InsufficientScope(OPENID_SCOPE.to_string()).into());
+        }
+
+        let Some(client) = self
+            .client_provider
+            .get_client_by_id(&issued.client_id)
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?
+        else {
+            return Err(OAuthValidationError::InvalidAccessToken.into());
+        };
+
+        let mut user_info = user_info_provider
+            .user_info(resource_owner, scope)
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?;
+        retain_granted_claims(&mut user_info.claims, scope);
+
+        let signed = self
+            .client_provider
+            .user_info_signed_response(&client)
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?;
+        if !signed {
+            return Ok(UserInfoResponse::Json(user_info));
+        }
+
+        // A signed response SHOULD contain the iss and aud claims
+        let Some(iss) = self.config.authorization_server_identifier.clone() else {
+            return Err(OAuthValidationError::NoAuthorizationServerIdentifier.into());
+        };
+        user_info.claims.insert("iss".to_string(), Value::String(iss));
+        user_info.claims.insert("aud".to_string(), Value::String(client.client_id));
+
+        let signing_key_source = self.signing_key_source();
+        let key = signing_key_source
+            .signing_key()
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?;
+        let jwt = sign_jwt(signing_key_source, &key, USER_INFO_JWT_TYPE, &user_info)
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?;
+        Ok(UserInfoResponse::Jwt(jwt))
+    }
+}
@@ -0,0 +1,68 @@
+use std::time::Instant;
+
+use async_trait::async_trait;
+
+use crate::authorize::AuthorizationRequest;
+
+/// Pushed authorization provider trait.
+/// This is an optional trait that can be implemented by the end user, to enable pushed authorization requests.
+///
+/// This trait is used to store authorization requests pushed by clients, and resolve them when the client
+/// redirects the resource owner to the authorization endpoint with the returned `request_uri`.
+#[async_trait]
+pub trait PushedAuthorizationProvider: 'static + Send + Sync {
+    /// This is the error type that can be returned by the pushed authorization provider implementing this trait.
+    /// This type will need to match the Error used in [ClientProvider](crate::common::model::ClientProvider).
+    type Error;
+
+    /// Store a validated authorization request, and generate a reference to it.
+    ///
+    /// # Implementation notes
+    /// The reference should be a fully random, high-entropy value, as it is used by the client in the authorization
+    /// request in place of the authorization parameters.
+    /// The library turns the reference into a `request_uri` by prefixing it with [REQUEST_URI_PREFIX](crate::par::REQUEST_URI_PREFIX).
+    /// The stored request MUST expire shortly after it is pushed, a lifetime between 5 and 600 seconds is RECOMMENDED.
+    ///
+    /// # Arguments
+    /// * `request` - The validated authorization request pushed by the client.
+    ///
+    /// # Returns
+    /// The [PushedRequestReference] that is sent back to the client.
+    ///
+    /// # Errors
+    /// If the provider fails to store the request, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn store_authorization_request(
+        &self,
+        request: AuthorizationRequest,
+    ) -> Result<PushedRequestReference, Self::Error>;
+
+    /// Take a previously pushed authorization request by its reference.
+    ///
+    /// # Implementation notes
+    /// A pushed request SHOULD only be used once, so it should be removed after it is taken.
+    ///
+    /// # Arguments
+    /// * `reference` - The reference that was returned by [store_authorization_request](PushedAuthorizationProvider::store_authorization_request).
+    ///
+    /// # Returns
+    /// An [Option] containing the [AuthorizationRequest] if the reference is known,
+    /// or [None] if the reference is invalid, expired or already used.
+    ///
+    /// # Errors
+    /// If the provider fails to look up the request, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn take_authorization_request(
+        &self,
+        reference: &str,
+    ) -> Result<Option<AuthorizationRequest>, Self::Error>;
+}
+
+/// A reference to a pushed authorization request, generated by the [PushedAuthorizationProvider].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PushedRequestReference {
+    /// The random reference to the stored request.
+    pub reference: String,
+    /// The instant at which the pushed request expires.
+    pub valid_until: Instant,
+}
@@ -0,0 +1,65 @@
+use crate::{
+    authorize::AuthorizationRequest,
+    common::{
+        auth::{client_credentials_from_request, ClientCredentials},
+        frontend::{FrontendRequest, FrontendRequestMethod, OAuthValidationError},
+        util::NoneIfEmpty,
+    },
+};
+
+/// A parsed pushed authorization request from a client, as described in RFC 9126.
+#[derive(Debug)]
+pub struct PushedAuthorizationRequest {
+    /// The client secret, for confidential clients.
+    pub client_secret: Option<String>,
+    /// Whether the client secret was sent using HTTP Basic authentication, instead of in the request body.
+    pub client_secret_basic: bool,
+    /// The client assertion, a JWT used to authenticate the client as described in RFC 7523.
+    pub client_assertion: Option<String>,
+    /// The DER encoded certificate the client presented for mutual TLS, as described in RFC 8705.
+    pub client_certificate: Option<Vec<u8>>,
+    /// The authorization parameters pushed by the client.
+    pub authorization_request: AuthorizationRequest,
+}
+
+impl TryFrom<&dyn FrontendRequest> for PushedAuthorizationRequest {
+    type Error = OAuthValidationError;
+
+    fn try_from(request: &dyn FrontendRequest) -> Result<Self, Self::Error> {
+        if !matches!(request.request_method(), FrontendRequestMethod::POST) {
+            return Err(OAuthValidationError::InvalidRequestMethod {
+                expected: FrontendRequestMethod::POST,
+                actual: request.request_method(),
+            });
+        }
+
+        // We should treat empty values as if they were omitted from the request
+        let body_param = |key| request.body_param(key).none_if_empty();
+
+        let ClientCredentials {
+            client_id,
+            client_secret,
+            client_secret_basic,
+            client_assertion,
+            client_certificate,
+        } = client_credentials_from_request(request)?;
+
+        // The request_uri parameter MUST NOT be provided in a pushed authorization request
+        if let Some(request_uri) = body_param("request_uri") {
+            return Err(OAuthValidationError::InvalidParameterValue("request_uri", request_uri));
+        }
+
+        let authorization_request =
+            AuthorizationRequest::from_params(client_id, body_param, |key| {
+                request.body_param_values(key)
+            })?;
+
+        Ok(Self {
+            client_secret,
+            client_secret_basic,
+            client_assertion,
+            client_certificate,
+            authorization_request,
+        })
+    }
+}
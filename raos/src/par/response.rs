@@ -0,0 +1,18 @@
+use serde::Serialize;
+
+use crate::common::frontend::{FrontendResponse, FrontendResponseExt};
+
+/// The response to a pushed authorization request.
+#[derive(Debug, Serialize)]
+pub struct PushedAuthorizationResponse {
+    /// The reference to the pushed request, to be used in the authorization request.
+    pub request_uri: String,
+    /// The time in seconds until the request uri expires.
+    pub expires_in: u64,
+}
+
+impl FrontendResponseExt for PushedAuthorizationResponse {
+    fn into_frontend_response(self) -> FrontendResponse {
+        FrontendResponse::Created { json: serde_json::to_value(self).unwrap() }
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{
+    authorize::{AuthorizationRequest, GrantAuthorizationResult},
+    common::frontend::{FrontendRequest, OAuthError, OAuthValidationError},
+    manager::PushedAuthorizationRequirement,
+    par::{PushedAuthorizationRequest, PushedRequestReference},
+    test::{
+        mock::request_from_raw_http, TestEnvironment, DEFAULT_AUTHORIZATION_CODE, DEFAULT_CLIENT_ID,
+    },
+};
+use mockall::predicate::{always, eq};
+use std::time::{Duration, Instant};
+
+static REQUEST_URI: &str = "urn:ietf:params:oauth:request_uri:reference";
+
+fn request_uri_authorization_request() -> AuthorizationRequest {
+    AuthorizationRequest { request_uri: Some(REQUEST_URI.to_string()), ..Default::default() }
+}
+
+#[test]
+fn test_pushed_authorization_request_rejects_request_uri() {
+    // The request_uri authorization request parameter MUST NOT be provided in this case.
+
+    // Arrange
+    let request = request_from_raw_http(
+        r#"
+            POST /par HTTP/1.1
+            Content-Type: application/x-www-form-urlencoded
+
+            client_id=client&response_type=code&code_challenge=challenge&request_uri=urn%3Aexample
+        "#,
+    );
+
+    // Act
+    let result = PushedAuthorizationRequest::try_from(&request as &dyn FrontendRequest);
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthValidationError::InvalidParameterValue("request_uri", "urn:example".to_string()),
+        result.unwrap_err()
+    );
+}
+
+#[tokio::test]
+async fn test_pushed_authorization_request_is_stored() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.pushed_authorization_provider
+        .expect_store_authorization_request()
+        .with(eq(AuthorizationRequest::default()))
+        .times(1)
+        .returning(|_| {
+            Ok(PushedRequestReference {
+                reference: "reference".to_string(),
+                valid_until: Instant::now() + Duration::from_secs(61),
+            })
+        });
+    let manager = test.build();
+
+    let request = PushedAuthorizationRequest {
+        client_secret: None,
+        client_secret_basic: false,
+        client_assertion: None,
+        client_certificate: None,
+        authorization_request: AuthorizationRequest::default(),
+    };
+
+    // Act
+    let result = manager.handle_pushed_authorization(request).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    let result = result.unwrap();
+    assert_eq!(REQUEST_URI, result.request_uri);
+    assert_eq!(60, result.expires_in);
+}
+
+#[tokio::test]
+async fn test_pushed_authorization_request_is_validated() {
+    // The authorization server MUST validate the pushed request as it would an authorization request sent to the authorization endpoint.
+
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.pushed_authorization_provider.expect_store_authorization_request().never();
+    let manager = test.build();
+
+    let request = PushedAuthorizationRequest {
+        client_secret: None,
+        client_secret_basic: false,
+        client_assertion: None,
+        client_certificate: None,
+        authorization_request: AuthorizationRequest {
+            redirect_uri: Some("https://example.com/other".to_string()),
+            ..Default::default()
+        },
+    };
+
+    // Act
+    let result = manager.handle_pushed_authorization(request).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::UnknownRedirectUri),
+        result.unwrap_err()
+    );
+}
+
+#[tokio::test]
+async fn test_pushed_authorization_request_is_resolved() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.pushed_authorization_provider
+        .expect_take_authorization_request()
+        .with(eq("reference"))
+        .times(1)
+        .returning(|_| Ok(Some(AuthorizationRequest::default())));
+    test.authorization_provider
+        .expect_authorize_grant()
+        .with(always(), always(), always())
+        .returning(|_, _, _| Ok(GrantAuthorizationResult::Authorized(1)));
+    test.authorization_provider
+        .expect_generate_code_for_grant()
+        .returning(|_| Ok(DEFAULT_AUTHORIZATION_CODE.to_string()));
+    let manager = test.build();
+
+    // Act
+    let result = manager.handle_authorization(request_uri_authorization_request(), None).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+}
+
+#[tokio::test]
+async fn test_pushed_authorization_request_belongs_to_client() {
+    // The authorization server MUST validate that the request_uri was issued to the client that uses it.
+
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.pushed_authorization_provider.expect_take_authorization_request().returning(|_| {
+        Ok(Some(AuthorizationRequest {
+            client_id: "other_client".to_string(),
+            ..Default::default()
+        }))
+    });
+    let manager = test.build();
+
+    // Act
+    let result = manager.validate_authorization_request(request_uri_authorization_request()).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::InvalidRequestUri),
+        result.unwrap_err()
+    );
+}
+
+#[tokio::test]
+async fn test_pushed_authorization_request_unknown_request_uri() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.pushed_authorization_provider.expect_take_authorization_request().returning(|_| Ok(None));
+    let manager = test.build();
+
+    // Act
+    let result = manager.validate_authorization_request(request_uri_authorization_request()).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::InvalidRequestUri),
+        result.unwrap_err()
+    );
+}
+
+#[tokio::test]
+async fn test_pushed_authorization_request_required_for_client() {
+    // The authorization server MAY require clients to use pushed authorization requests.
+
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    let mut manager = test.build();
+    manager.config.require_pushed_authorization =
+        PushedAuthorizationRequirement::ForClients([DEFAULT_CLIENT_ID.to_string()].into());
+
+    // Act
+    let result = manager.validate_authorization_request(AuthorizationRequest::default()).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::PushedAuthorizationRequired),
+        result.unwrap_err()
+    );
+}
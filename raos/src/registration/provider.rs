@@ -0,0 +1,135 @@
+use async_trait::async_trait;
+
+use crate::{common::model::Client, registration::ClientMetadata};
+
+/// Client registration provider trait.
+/// This is an optional trait that can be implemented by the end user, to enable dynamic client registration
+/// as described in RFC 7591, and the management of registered clients as described in RFC 7592.
+///
+/// Registered clients are looked up through the [ClientProvider](crate::common::model::ClientProvider) like any
+/// other client, so the clients stored by this provider have to be returned from there as well.
+#[async_trait]
+pub trait ClientRegistrationProvider: 'static + Send + Sync {
+    /// This is the error type that can be returned by the client registration provider implementing this trait.
+    /// This type will need to match the Error used in [ClientProvider](crate::common::model::ClientProvider).
+    type Error;
+
+    /// Decide whether a client is allowed to register, based on the initial access token it presented.
+    ///
+    /// # Implementation notes
+    /// This is the policy that gates who can register clients. An authorization server that allows open registration
+    /// can accept requests without an initial access token, otherwise the token should be checked against the tokens
+    /// handed out to developers, for example through a developer portal.
+    ///
+    /// # Arguments
+    /// * `initial_access_token` - The bearer token sent along with the registration request, if any.
+    /// * `metadata` - The metadata the client wants to register with, which has not been validated yet.
+    ///
+    /// # Returns
+    /// True if the client is allowed to register.
+    ///
+    /// # Errors
+    /// If the provider fails to check the initial access token, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn allow_registration(
+        &self,
+        initial_access_token: Option<String>,
+        metadata: &ClientMetadata,
+    ) -> Result<bool, Self::Error>;
+
+    /// Store a newly registered client.
+    ///
+    /// # Implementation notes
+    /// The client secret and registration access token are only handed out once, so they should be stored in a
+    /// hashed form, like described in [ClientProvider::verify_client_secret](crate::common::model::ClientProvider::verify_client_secret).
+    ///
+    /// # Arguments
+    /// * `registration` - The registered client, along with its metadata and credentials.
+    ///
+    /// # Errors
+    /// If the provider fails to store the client, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn register_client(&self, registration: ClientRegistration) -> Result<(), Self::Error>;
+
+    /// Verify the registration access token a client uses to manage its registration.
+    ///
+    /// # Implementation notes
+    /// Please ensure the token is compared in a constant time manner.
+    ///
+    /// # Arguments
+    /// * `client_id` - The client id of the registration that is managed.
+    /// * `registration_access_token` - The bearer token sent along with the request.
+    ///
+    /// # Returns
+    /// True if the token was issued for this client, false if it is unknown or the client does not exist.
+    ///
+    /// # Errors
+    /// If the provider fails to verify the token, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn verify_registration_access_token(
+        &self,
+        client_id: &str,
+        registration_access_token: &str,
+    ) -> Result<bool, Self::Error>;
+
+    /// Get the metadata a client was registered with.
+    ///
+    /// # Arguments
+    /// * `client_id` - The client id of the registered client.
+    ///
+    /// # Returns
+    /// An [Option] containing the [ClientMetadata] of the client, or [None] if the client does not exist.
+    ///
+    /// # Errors
+    /// If the provider fails to get the metadata, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn get_client_metadata(
+        &self,
+        client_id: &str,
+    ) -> Result<Option<ClientMetadata>, Self::Error>;
+
+    /// Replace the metadata of a registered client.
+    ///
+    /// # Implementation notes
+    /// The client secret and registration access token stay the same.
+    ///
+    /// # Arguments
+    /// * `client` - The updated client.
+    /// * `metadata` - The validated metadata that replaces the registered metadata.
+    ///
+    /// # Errors
+    /// If the provider fails to update the client, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn update_client(
+        &self,
+        client: &Client,
+        metadata: &ClientMetadata,
+    ) -> Result<(), Self::Error>;
+
+    /// Delete a registered client.
+    ///
+    /// # Implementation notes
+    /// The client secret and registration access token of the client should be invalidated,
+    /// as well as the grants and tokens issued to the client.
+    ///
+    /// # Arguments
+    /// * `client_id` - The client id of the client to delete.
+    ///
+    /// # Errors
+    /// If the provider fails to delete the client, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn delete_client(&self, client_id: &str) -> Result<(), Self::Error>;
+}
+
+/// A newly registered client, generated by the library to be stored by the [ClientRegistrationProvider].
+#[derive(Debug, Clone)]
+pub struct ClientRegistration {
+    /// The registered client.
+    pub client: Client,
+    /// The validated metadata the client registered with.
+    pub metadata: ClientMetadata,
+    /// The client secret issued to the client, if it authenticates using its client secret.
+    pub client_secret: Option<String>,
+    /// The token the client uses to read, update or delete its registration.
+    pub registration_access_token: String,
+}
@@ -0,0 +1,199 @@
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::common::{
+    auth::bearer_token_from_request,
+    frontend::{FrontendRequest, FrontendRequestMethod, OAuthValidationError},
+    jose::JsonWebKeySet,
+    model::{ClientDisplayMetadata, TlsClientAuthentication, TokenEndpointAuthMethod},
+};
+
+/// The metadata of a client, as described in RFC 7591 section 2.
+/// Metadata the authorization server does not understand is ignored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClientMetadata {
+    /// The redirect uris the client is allowed to use.
+    #[serde(default)]
+    pub redirect_uris: Vec<String>,
+    /// The method the client uses to authenticate at the token endpoint, `client_secret_basic` by default.
+    #[serde(default)]
+    pub token_endpoint_auth_method: TokenEndpointAuthMethod,
+    /// The grant types the client uses, `authorization_code` by default.
+    #[serde(default = "default_grant_types")]
+    pub grant_types: Vec<String>,
+    /// The response types the client uses, `code` by default.
+    #[serde(default = "default_response_types")]
+    pub response_types: Vec<String>,
+    /// The metadata shown to the resource owner, like the name and logo of the client.
+    #[serde(flatten)]
+    pub display: ClientDisplayMetadata,
+    /// The space separated scopes the client can use.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// The public keys of the client, used for `private_key_jwt` and `self_signed_tls_client_auth`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jwks: Option<JsonWebKeySet>,
+    /// The expected subject distinguished name of the certificate, used for `tls_client_auth`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_client_auth_subject_dn: Option<String>,
+    /// The dNSName the certificate must contain, used for `tls_client_auth`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_client_auth_san_dns: Option<String>,
+    /// The uniformResourceIdentifier the certificate must contain, used for `tls_client_auth`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_client_auth_san_uri: Option<String>,
+    /// The iPAddress the certificate must contain, used for `tls_client_auth`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_client_auth_san_ip: Option<String>,
+    /// The rfc822Name the certificate must contain, used for `tls_client_auth`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_client_auth_san_email: Option<String>,
+}
+
+fn default_grant_types() -> Vec<String> {
+    vec!["authorization_code".to_string()]
+}
+
+fn default_response_types() -> Vec<String> {
+    vec!["code".to_string()]
+}
+
+impl ClientMetadata {
+    /// Parse client metadata from the JSON body of a request.
+    fn from_json(json: Option<Value>) -> Result<Self, OAuthValidationError> {
+        let Some(json) = json else {
+            return Err(OAuthValidationError::InvalidClientMetadata("the body is not JSON"));
+        };
+        serde_json::from_value(json)
+            .map_err(|_| OAuthValidationError::InvalidClientMetadata("the metadata is malformed"))
+    }
+
+    /// The mutual TLS registration of the client, to be returned from
+    /// [ClientProvider::get_tls_client_authentication](crate::common::model::ClientProvider::get_tls_client_authentication).
+    ///
+    /// # Returns
+    /// The [TlsClientAuthentication] of the client, or [None] if the client does not authenticate using mutual TLS.
+    pub fn tls_client_authentication(&self) -> Option<TlsClientAuthentication> {
+        match self.token_endpoint_auth_method {
+            TokenEndpointAuthMethod::TlsClientAuth => {
+                if let Some(subject_dn) = &self.tls_client_auth_subject_dn {
+                    Some(TlsClientAuthentication::SubjectDn(subject_dn.clone()))
+                } else if let Some(dns) = &self.tls_client_auth_san_dns {
+                    Some(TlsClientAuthentication::SanDns(dns.clone()))
+                } else if let Some(uri) = &self.tls_client_auth_san_uri {
+                    Some(TlsClientAuthentication::SanUri(uri.clone()))
+                } else if let Some(ip) = &self.tls_client_auth_san_ip {
+                    ip.parse::<IpAddr>().ok().map(TlsClientAuthentication::SanIp)
+                } else {
+                    self.tls_client_auth_san_email.clone().map(TlsClientAuthentication::SanEmail)
+                }
+            }
+            TokenEndpointAuthMethod::SelfSignedTlsClientAuth => self
+                .jwks
+                .as_ref()
+                .map(|jwks| TlsClientAuthentication::SelfSigned(jwks.keys.clone())),
+            _ => None,
+        }
+    }
+}
+
+/// A parsed request to register a client, as described in RFC 7591 section 3.1.
+#[derive(Debug)]
+pub struct ClientRegistrationRequest {
+    /// The initial access token sent as bearer token, used to decide whether the client may register.
+    pub initial_access_token: Option<String>,
+    /// The metadata the client wants to register with.
+    pub metadata: ClientMetadata,
+}
+
+impl TryFrom<&dyn FrontendRequest> for ClientRegistrationRequest {
+    type Error = OAuthValidationError;
+
+    fn try_from(request: &dyn FrontendRequest) -> Result<Self, Self::Error> {
+        if !matches!(request.request_method(), FrontendRequestMethod::POST) {
+            return Err(OAuthValidationError::InvalidRequestMethod {
+                expected: FrontendRequestMethod::POST,
+                actual: request.request_method(),
+            });
+        }
+
+        Ok(ClientRegistrationRequest {
+            initial_access_token: bearer_token_from_request(request),
+            metadata: ClientMetadata::from_json(request.body_json())?,
+        })
+    }
+}
+
+/// The operation requested on the client configuration endpoint, as described in RFC 7592 section 2.
+#[derive(Debug)]
+pub enum ClientManagementOperation {
+    /// Read the current registration of the client, through a GET request.
+    Read,
+    /// Replace the metadata of the client, through a PUT request.
+    Update {
+        /// The client id in the request body, which must match the client that is managed.
+        client_id: Option<String>,
+        /// The client secret in the request body, which must match the secret of the client if included.
+        client_secret: Option<String>,
+        /// The new metadata of the client.
+        metadata: Box<ClientMetadata>,
+    },
+    /// Delete the registration of the client, through a DELETE request.
+    Delete,
+}
+
+/// A parsed request to the client configuration endpoint, as described in RFC 7592.
+#[derive(Debug)]
+pub struct ClientManagementRequest {
+    /// The client id of the client that is managed, taken from the url of the client configuration endpoint.
+    pub client_id: String,
+    /// The registration access token, sent as bearer token.
+    pub registration_access_token: String,
+    /// The requested operation.
+    pub operation: ClientManagementOperation,
+}
+
+impl ClientManagementRequest {
+    /// Parse a request to the client configuration endpoint.
+    ///
+    /// # Parameters
+    /// - `client_id` - The client id from the url of the client configuration endpoint.
+    /// - `request` - The unparsed incoming request from the client.
+    pub(crate) fn from_request(
+        client_id: String,
+        request: &dyn FrontendRequest,
+    ) -> Result<Self, OAuthValidationError> {
+        let operation = match request.request_method() {
+            FrontendRequestMethod::GET => ClientManagementOperation::Read,
+            FrontendRequestMethod::PUT => {
+                let json = request.body_json();
+                let field = |name| {
+                    json.as_ref()
+                        .and_then(|json| json.get(name))
+                        .and_then(Value::as_str)
+                        .map(str::to_string)
+                };
+                ClientManagementOperation::Update {
+                    client_id: field("client_id"),
+                    client_secret: field("client_secret"),
+                    metadata: Box::new(ClientMetadata::from_json(json.clone())?),
+                }
+            }
+            FrontendRequestMethod::DELETE => ClientManagementOperation::Delete,
+            actual => {
+                return Err(OAuthValidationError::InvalidRequestMethod {
+                    expected: FrontendRequestMethod::GET,
+                    actual,
+                })
+            }
+        };
+
+        let Some(registration_access_token) = bearer_token_from_request(request) else {
+            return Err(OAuthValidationError::InvalidAccessToken);
+        };
+
+        Ok(ClientManagementRequest { client_id, registration_access_token, operation })
+    }
+}
@@ -0,0 +1,59 @@
+use serde::Serialize;
+
+use crate::{
+    common::frontend::{FrontendResponse, FrontendResponseExt},
+    registration::ClientMetadata,
+};
+
+/// The registration of a client, as returned by the registration and client configuration endpoints,
+/// described in RFC 7591 section 3.2.1 and RFC 7592 section 3.
+#[derive(Debug, Serialize)]
+pub struct ClientInformationResponse {
+    /// The client id issued to the client.
+    pub client_id: String,
+    /// The client secret issued to the client.
+    /// This is only returned on registration, after which only a hash should be stored.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+    /// The time at which the client id was issued, in seconds since the unix epoch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id_issued_at: Option<u64>,
+    /// The time at which the client secret expires in seconds since the unix epoch, always 0 as secrets do not expire.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret_expires_at: Option<u64>,
+    /// The token the client uses to manage its registration.
+    /// This is only returned on registration, after which only a hash should be stored.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registration_access_token: Option<String>,
+    /// The url of the client configuration endpoint of the client.
+    pub registration_client_uri: String,
+    /// The registered metadata of the client.
+    #[serde(flatten)]
+    pub metadata: ClientMetadata,
+}
+
+impl FrontendResponseExt for ClientInformationResponse {
+    fn into_frontend_response(self) -> FrontendResponse {
+        FrontendResponse::Created { json: serde_json::to_value(self).unwrap() }
+    }
+}
+
+/// The response to a request to the client configuration endpoint.
+#[derive(Debug)]
+pub enum ClientManagementResponse {
+    /// The current registration of the client, after it was read or updated.
+    Client(Box<ClientInformationResponse>),
+    /// The client was deleted.
+    Deleted,
+}
+
+impl FrontendResponseExt for ClientManagementResponse {
+    fn into_frontend_response(self) -> FrontendResponse {
+        match self {
+            ClientManagementResponse::Client(client) => {
+                FrontendResponse::Success { json: serde_json::to_value(client).unwrap() }
+            }
+            ClientManagementResponse::Deleted => FrontendResponse::NoContent,
+        }
+    }
+}
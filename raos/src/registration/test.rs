@@ -0,0 +1,466 @@
+use mockall::predicate::{always, eq};
+use serde_json::json;
+use std::{
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use crate::{
+    common::{
+        frontend::{
+            FrontendRequest, FrontendResponse, FrontendResponseExt, OAuthError,
+            OAuthValidationError,
+        },
+        model::TokenEndpointAuthMethod,
+    },
+    manager::OAuthManager,
+    registration::{ClientManagementResponse, ClientMetadata, ClientRegistration},
+    test::{
+        mock::request_from_raw_http, TestEnvironment, DEFAULT_CLIENT_ID, DEFAULT_REDIRECT_URI,
+        DEFAULT_TOKEN,
+    },
+    token::{GrantType, RequestedGrantType, Token, TokenRequest},
+};
+
+static INITIAL_ACCESS_TOKEN: &str = "initial_access_token";
+static REGISTRATION_ACCESS_TOKEN: &str = "registration_access_token";
+
+fn build(test: TestEnvironment) -> OAuthManager<u32, (), ()> {
+    let mut manager = test.build();
+    manager.config.authorization_server_identifier = Some("https://example.com".to_string());
+    manager
+}
+
+fn registration_request(body: &str) -> impl FrontendRequest {
+    request_from_raw_http(&format!(
+        r#"
+        POST /register HTTP/1.1
+        Content-Type: application/json
+        authorization: Bearer {INITIAL_ACCESS_TOKEN}
+
+        {body}
+    "#
+    ))
+}
+
+/// A test environment where the default client was registered with the given metadata.
+fn management_environment(metadata: serde_json::Value) -> TestEnvironment {
+    let mut test = TestEnvironment::new();
+    test.client_registration_provider.expect_verify_registration_access_token().returning(
+        |client_id, token| Ok(client_id == DEFAULT_CLIENT_ID && token == REGISTRATION_ACCESS_TOKEN),
+    );
+    let metadata: ClientMetadata = serde_json::from_value(metadata).unwrap();
+    test.client_registration_provider
+        .expect_get_client_metadata()
+        .with(eq(DEFAULT_CLIENT_ID))
+        .returning(move |_| Ok(Some(metadata.clone())));
+    test
+}
+
+#[tokio::test]
+async fn test_registration_issues_client_credentials() {
+    // The authorization server assigns this client a unique client identifier, optionally assigns a client secret,
+    // and associates the metadata provided in the request with the issued client identifier.
+
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.client_registration_provider
+        .expect_allow_registration()
+        .with(eq(Some(INITIAL_ACCESS_TOKEN.to_string())), always())
+        .returning(|_, _| Ok(true));
+    test.client_registration_provider
+        .expect_register_client()
+        .withf(|registration| {
+            registration.client.confidential
+                && registration.client.redirect_uris == vec![DEFAULT_REDIRECT_URI.to_string()]
+                && registration.client_secret.is_some()
+        })
+        .times(1)
+        .returning(|_| Ok(()));
+    let manager = build(test);
+    let req = registration_request(&format!(
+        r#"{{ "redirect_uris": ["{DEFAULT_REDIRECT_URI}"], "client_name": "Client", "software_version": "ignored" }}"#
+    ));
+
+    // Act
+    let result = manager.handle_client_registration_request(req).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    let information = result.unwrap();
+    assert_eq!(Some(0), information.client_secret_expires_at);
+    assert!(information.registration_access_token.is_some());
+    assert_eq!(
+        format!("https://example.com/register/{}", information.client_id),
+        information.registration_client_uri
+    );
+    let FrontendResponse::Created { json } = information.into_frontend_response() else {
+        panic!("response is not 201 Created");
+    };
+    assert_eq!(json!("client_secret_basic"), json["token_endpoint_auth_method"]);
+    assert_eq!(json!(["authorization_code"]), json["grant_types"]);
+    assert_eq!(json!("Client"), json["client_name"]);
+    assert_eq!(None, json.get("software_version"));
+}
+
+#[tokio::test]
+async fn test_registration_of_public_client_issues_no_secret() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.client_registration_provider.expect_allow_registration().returning(|_, _| Ok(true));
+    test.client_registration_provider
+        .expect_register_client()
+        .withf(|registration| {
+            !registration.client.confidential && registration.client_secret.is_none()
+        })
+        .times(1)
+        .returning(|_| Ok(()));
+    let manager = build(test);
+    let req = registration_request(&format!(
+        r#"{{ "redirect_uris": ["{DEFAULT_REDIRECT_URI}"], "token_endpoint_auth_method": "none" }}"#
+    ));
+
+    // Act
+    let result = manager.handle_client_registration_request(req).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    let information = result.unwrap();
+    assert_eq!(None, information.client_secret);
+    assert_eq!(None, information.client_secret_expires_at);
+    assert_eq!(TokenEndpointAuthMethod::None, information.metadata.token_endpoint_auth_method);
+}
+
+#[tokio::test]
+async fn test_registration_denied_without_initial_access_token() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.client_registration_provider
+        .expect_allow_registration()
+        .with(eq(None), always())
+        .returning(|_, _| Ok(false));
+    test.client_registration_provider.expect_register_client().never();
+    let manager = build(test);
+    let req = request_from_raw_http(&format!(
+        r#"
+        POST /register HTTP/1.1
+        Content-Type: application/json
+
+        {{ "redirect_uris": ["{DEFAULT_REDIRECT_URI}"] }}
+    "#
+    ));
+
+    // Act
+    let result = manager.handle_client_registration_request(req).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::InvalidAccessToken),
+        result.unwrap_err()
+    );
+}
+
+#[tokio::test]
+async fn test_registration_of_client_credentials_client_needs_no_redirect_uris() {
+    // Arrange
+    let registered: Arc<Mutex<Option<ClientRegistration>>> = Arc::default();
+    let mut test = TestEnvironment::new();
+    test.client_registration_provider.expect_allow_registration().returning(|_, _| Ok(true));
+    let store = registered.clone();
+    test.client_registration_provider
+        .expect_register_client()
+        .withf(|registration| {
+            registration.client.redirect_uris.is_empty()
+                && registration.client.default_scopes
+                    == vec!["read".to_string(), "write".to_string()]
+        })
+        .times(1)
+        .returning(move |registration| {
+            *store.lock().unwrap() = Some(registration.clone());
+            Ok(())
+        });
+    let store = registered.clone();
+    test.client_provider
+        .expect_get_client_by_id()
+        .returning(move |_| Ok(store.lock().unwrap().as_ref().map(|r| r.client.clone())));
+    let store = registered.clone();
+    test.client_provider.expect_verify_client_secret().returning(move |_, secret| {
+        Ok(store.lock().unwrap().as_ref().and_then(|r| r.client_secret.as_deref()) == Some(secret))
+    });
+    test.client_provider.expect_allow_client_scopes().returning(|_, scopes| Ok(scopes));
+    test.token_provider
+        .expect_token()
+        .with(always(), eq(GrantType::ClientCredentials), always(), always())
+        .times(1)
+        .returning(|_, _, _, _| {
+            Ok(Token {
+                token: DEFAULT_TOKEN.to_string(),
+                refresh_token: None,
+                valid_until: Instant::now(),
+            })
+        });
+    let manager = build(test);
+    let req = registration_request(
+        r#"{ "grant_types": ["client_credentials"], "response_types": [], "scope": "read write" }"#,
+    );
+
+    // Act
+    let registration = manager.handle_client_registration_request(req).await;
+    assert!(registration.is_ok(), "result is not Ok, result is {:?}", registration);
+    let information = registration.unwrap();
+    let result = manager
+        .handle_token(TokenRequest {
+            client_id: information.client_id.clone(),
+            client_secret: information.client_secret.clone(),
+            grant_type: RequestedGrantType::ClientCredentials,
+            redirect_uri: None,
+            scope: None,
+            ..Default::default()
+        })
+        .await;
+
+    // Assert
+    assert_eq!(Some("read write".to_string()), information.metadata.scope);
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+}
+
+#[tokio::test]
+async fn test_registration_rejects_invalid_metadata() {
+    // If the client metadata is invalid, the authorization server responds with invalid_redirect_uri
+    // for redirection URIs, and with invalid_client_metadata for any other metadata value.
+
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.client_registration_provider.expect_allow_registration().returning(|_, _| Ok(true));
+    test.client_registration_provider.expect_register_client().never();
+    let manager = build(test);
+    let cases = [
+        (r#"{ "redirect_uris": [] }"#, OAuthValidationError::InvalidClientRedirectUri),
+        (
+            r#"{ "redirect_uris": ["https://example.com/#fragment"] }"#,
+            OAuthValidationError::InvalidClientRedirectUri,
+        ),
+        (
+            r#"{ "redirect_uris": ["https://example.com"], "grant_types": ["implicit"] }"#,
+            OAuthValidationError::InvalidClientMetadata("unsupported grant_types"),
+        ),
+        (
+            r#"{ "redirect_uris": ["https://example.com"], "grant_types": ["client_credentials"] }"#,
+            OAuthValidationError::InvalidClientMetadata(
+                "grant_types and response_types are inconsistent",
+            ),
+        ),
+        (
+            r#"{ "redirect_uris": ["https://example.com"], "token_endpoint_auth_method": "none",
+                 "grant_types": ["authorization_code", "client_credentials"] }"#,
+            OAuthValidationError::InvalidClientMetadata(
+                "public clients can not use the client_credentials grant",
+            ),
+        ),
+        (
+            r#"{ "redirect_uris": ["https://example.com"], "token_endpoint_auth_method": "tls_client_auth" }"#,
+            OAuthValidationError::InvalidClientMetadata(
+                "mutual TLS client authentication is not supported",
+            ),
+        ),
+        (
+            r#"{ "redirect_uris": "https://example.com" }"#,
+            OAuthValidationError::InvalidClientMetadata("the metadata is malformed"),
+        ),
+        (
+            r#"{ "redirect_uris": ["https://example.com"], "scope": "read  write" }"#,
+            OAuthValidationError::InvalidClientMetadata("the scope is malformed"),
+        ),
+        (
+            r#"{ "redirect_uris": ["https://example.com"], "scope": "read \"write\"" }"#,
+            OAuthValidationError::InvalidClientMetadata("the scope is malformed"),
+        ),
+    ];
+
+    for (body, error) in cases {
+        // Act
+        let result = manager.handle_client_registration_request(registration_request(body)).await;
+
+        // Assert
+        assert!(result.is_err(), "result is not Err for {body}, result is {:?}", result);
+        assert_eq!(OAuthError::ValidationFailed(error), result.unwrap_err());
+    }
+}
+
+#[tokio::test]
+async fn test_registration_requires_jwks_for_private_key_jwt() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.client_registration_provider.expect_allow_registration().returning(|_, _| Ok(true));
+    test.client_registration_provider.expect_register_client().times(1).returning(|_| Ok(()));
+    let manager = build(test);
+    let without_jwks = registration_request(
+        r#"{ "redirect_uris": ["https://example.com"], "token_endpoint_auth_method": "private_key_jwt" }"#,
+    );
+    let with_private_key = registration_request(
+        r#"{ "redirect_uris": ["https://example.com"], "token_endpoint_auth_method": "private_key_jwt",
+             "jwks": { "keys": [{ "kty": "oct", "k": "c2VjcmV0" }] } }"#,
+    );
+    let with_jwks = registration_request(
+        r#"{ "redirect_uris": ["https://example.com"], "token_endpoint_auth_method": "private_key_jwt",
+             "jwks": { "keys": [{ "kty": "EC", "crv": "P-256", "x": "eA", "y": "eQ" }] } }"#,
+    );
+
+    // Act
+    let without_jwks = manager.handle_client_registration_request(without_jwks).await;
+    let with_private_key = manager.handle_client_registration_request(with_private_key).await;
+    let with_jwks = manager.handle_client_registration_request(with_jwks).await;
+
+    // Assert
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::InvalidClientMetadata(
+            "private_key_jwt requires jwks"
+        )),
+        without_jwks.unwrap_err()
+    );
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::InvalidClientMetadata(
+            "jwks contains private keys"
+        )),
+        with_private_key.unwrap_err()
+    );
+    assert!(with_jwks.is_ok(), "result is not Ok, result is {:?}", with_jwks);
+    assert_eq!(None, with_jwks.unwrap().client_secret);
+}
+
+#[tokio::test]
+async fn test_management_reads_registration() {
+    // The authorization server responds with the current configuration of the client,
+    // which includes the registration_client_uri.
+
+    // Arrange
+    let test = management_environment(json!({ "redirect_uris": [DEFAULT_REDIRECT_URI] }));
+    let manager = build(test);
+    let req = request_from_raw_http(&format!(
+        r#"
+        GET /register/{DEFAULT_CLIENT_ID} HTTP/1.1
+        authorization: Bearer {REGISTRATION_ACCESS_TOKEN}
+    "#
+    ));
+
+    // Act
+    let result = manager.handle_client_management_request(DEFAULT_CLIENT_ID, req).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    let ClientManagementResponse::Client(information) = result.unwrap() else {
+        panic!("response is not the client information");
+    };
+    assert_eq!(DEFAULT_CLIENT_ID, information.client_id);
+    assert_eq!(
+        format!("https://example.com/register/{DEFAULT_CLIENT_ID}"),
+        information.registration_client_uri
+    );
+    assert_eq!(vec![DEFAULT_REDIRECT_URI.to_string()], information.metadata.redirect_uris);
+}
+
+#[tokio::test]
+async fn test_management_rejects_invalid_registration_access_token() {
+    // If the registration access token used to make this request is not valid,
+    // the server MUST respond with an error as described in OAuth 2.0 Bearer Token Usage.
+
+    // Arrange
+    let test = management_environment(json!({ "redirect_uris": [DEFAULT_REDIRECT_URI] }));
+    let manager = build(test);
+    let req = request_from_raw_http(&format!(
+        r#"
+        DELETE /register/{DEFAULT_CLIENT_ID} HTTP/1.1
+        authorization: Bearer invalid
+    "#
+    ));
+
+    // Act
+    let result = manager.handle_client_management_request(DEFAULT_CLIENT_ID, req).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::InvalidAccessToken),
+        result.unwrap_err()
+    );
+}
+
+#[tokio::test]
+async fn test_management_updates_registration() {
+    // The client MUST include its client_id field in the request, and it MUST be the same as its currently
+    // issued client identifier.
+
+    // Arrange
+    let mut test = management_environment(
+        json!({ "redirect_uris": [DEFAULT_REDIRECT_URI], "token_endpoint_auth_method": "none" }),
+    );
+    test.client_registration_provider
+        .expect_update_client()
+        .withf(|client, metadata| {
+            client.client_id == DEFAULT_CLIENT_ID
+                && metadata.redirect_uris == vec!["https://example.com/new".to_string()]
+        })
+        .times(1)
+        .returning(|_, _| Ok(()));
+    let manager = build(test);
+    let update = |client_id: &str| {
+        request_from_raw_http(&format!(
+            r#"
+            PUT /register/{DEFAULT_CLIENT_ID} HTTP/1.1
+            Content-Type: application/json
+            authorization: Bearer {REGISTRATION_ACCESS_TOKEN}
+
+            {{ "client_id": "{client_id}", "redirect_uris": ["https://example.com/new"], "token_endpoint_auth_method": "none" }}
+        "#
+        ))
+    };
+
+    // Act
+    let mismatch =
+        manager.handle_client_management_request(DEFAULT_CLIENT_ID, update("other")).await;
+    let result = manager
+        .handle_client_management_request(DEFAULT_CLIENT_ID, update(DEFAULT_CLIENT_ID))
+        .await;
+
+    // Assert
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::InvalidClientMetadata(
+            "client_id does not match the registration"
+        )),
+        mismatch.unwrap_err()
+    );
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    let ClientManagementResponse::Client(information) = result.unwrap() else {
+        panic!("response is not the client information");
+    };
+    assert_eq!(vec!["https://example.com/new".to_string()], information.metadata.redirect_uris);
+}
+
+#[tokio::test]
+async fn test_management_deletes_registration() {
+    // If a client has been successfully deprovisioned, the authorization server MUST respond with
+    // an HTTP 204 No Content message.
+
+    // Arrange
+    let mut test = management_environment(json!({ "redirect_uris": [DEFAULT_REDIRECT_URI] }));
+    test.client_registration_provider
+        .expect_delete_client()
+        .with(eq(DEFAULT_CLIENT_ID))
+        .times(1)
+        .returning(|_| Ok(()));
+    let manager = build(test);
+    let req = request_from_raw_http(&format!(
+        r#"
+        DELETE /register/{DEFAULT_CLIENT_ID} HTTP/1.1
+        authorization: Bearer {REGISTRATION_ACCESS_TOKEN}
+    "#
+    ));
+
+    // Act
+    let result = manager.handle_client_management_request(DEFAULT_CLIENT_ID, req).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    assert_eq!(FrontendResponse::NoContent, result.unwrap().into_frontend_response());
+}
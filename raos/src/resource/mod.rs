@@ -0,0 +1,170 @@
+pub use request::*;
+pub use validator::*;
+
+use std::time::Instant;
+
+use crate::{
+    common::{
+        frontend::{FrontendRequest, OAuthError, OAuthValidationError},
+        x509::certificate_thumbprint,
+    },
+    manager::OAuthManager,
+    token::{IssuedToken, TokenConfirmation, TokenTypeHint},
+};
+
+mod request;
+#[cfg(test)]
+mod test;
+mod validator;
+
+impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
+    /// Handle an incoming request to a protected resource.
+    /// This function will extract the access token from the request, and then validate it
+    /// against the requirements of the resource, returning the [IssuedToken] the request was made with.
+    ///
+    /// # Parameters
+    /// - `req` - The unparsed incoming request from the client, represented by a [FrontendRequest]
+    /// - `validator` - The requirements of the protected resource, represented by a [BearerTokenValidator]
+    ///
+    /// # Returns
+    /// The [IssuedToken] that describes the client, resource owner and scope the request was authorized for.
+    ///
+    /// # Errors
+    /// This function can return an [OAuthError] if the access token is missing, unknown or expired,
+    /// if it was not granted the required scopes, or if the token provider fails to look up the token.
+    /// These errors are turned into the `invalid_token` and `insufficient_scope` errors of RFC 6750 section 3.1,
+    /// including the `WWW-Authenticate` header. A missing access token is answered with a challenge without an error code.
+    ///
+    /// # Example
+    /// ```
+    /// # use raos::{
+    /// #    resource::BearerTokenValidator,
+    /// #    test::{doctest::oauth_manager_from_application_state, mock::request_from_raw_http},
+    /// # };
+    ///
+    /// let manager = oauth_manager_from_application_state();
+    /// let req = request_from_raw_http(r#"
+    ///     GET /resource HTTP/1.1
+    ///     authorization: Bearer ACCESS_TOKEN
+    /// "#);
+    /// let validator = BearerTokenValidator::new().require_scope("SCOPE");
+    ///
+    /// # tokio_test::block_on(async {
+    /// let result = manager.handle_protected_resource_request(req, &validator).await;
+    /// assert_eq!("CLIENT_ID", result.unwrap().client_id);
+    /// # });
+    /// ```
+    pub async fn handle_protected_resource_request(
+        &self,
+        req: impl FrontendRequest,
+        validator: &BearerTokenValidator,
+    ) -> Result<IssuedToken<U>, OAuthError<E>> {
+        let request = ProtectedResourceRequest::from_request(&req, validator.allows_form_body())?;
+        self.handle_protected_resource(request, validator).await
+    }
+
+    /// Handle an incoming request to a protected resource.
+    /// This function will look up the access token through the [TokenProvider](crate::token::TokenProvider),
+    /// and check that it is still valid and was granted the scopes required by the resource.
+    /// Access tokens bound to a key are only accepted along with proof of possession of that key,
+    /// through a DPoP proof or the certificate the client presented for mutual TLS.
+    ///
+    /// # Parameters
+    /// - `req` - The parsed incoming request from the client, represented by a [ProtectedResourceRequest]
+    /// - `validator` - The requirements of the protected resource, represented by a [BearerTokenValidator]
+    ///
+    /// # Returns
+    /// The [IssuedToken] that describes the client, resource owner and scope the request was authorized for.
+    ///
+    /// # Errors
+    /// This function can return an [OAuthError] if the access token is unknown or expired,
+    /// if the proof of possession of a bound access token is missing or invalid,
+    /// if it was not granted the required scopes, or if the token provider fails to look up the token.
+    ///
+    /// # Example
+    /// ```
+    /// # use raos::{
+    /// #    common::frontend::{FrontendRequestMethod, OAuthError, OAuthValidationError},
+    /// #    resource::{BearerTokenValidator, ProtectedResourceRequest},
+    /// #    test::doctest::oauth_manager_from_application_state,
+    /// # };
+    ///
+    /// let manager = oauth_manager_from_application_state();
+    /// let req = ProtectedResourceRequest {
+    ///     access_token: "ACCESS_TOKEN".to_string(),
+    ///     request_method: FrontendRequestMethod::GET,
+    ///     dpop_proof: None,
+    ///     client_certificate: None,
+    /// };
+    /// let validator = BearerTokenValidator::new().require_scope("admin");
+    ///
+    /// # tokio_test::block_on(async {
+    /// let result = manager.handle_protected_resource(req, &validator).await;
+    /// assert_eq!(
+    ///     Err(OAuthError::ValidationFailed(OAuthValidationError::InsufficientScope("admin".to_string()))),
+    ///     result
+    /// );
+    /// # });
+    /// ```
+    pub async fn handle_protected_resource(
+        &self,
+        req: ProtectedResourceRequest,
+        validator: &BearerTokenValidator,
+    ) -> Result<IssuedToken<U>, OAuthError<E>> {
+        let Some(issued) = self
+            .token_provider
+            .lookup_token(req.access_token.clone(), Some(TokenTypeHint::AccessToken))
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?
+        else {
+            return Err(OAuthValidationError::InvalidAccessToken.into());
+        };
+        if issued.token_type != TokenTypeHint::AccessToken || issued.valid_until <= Instant::now() {
+            return Err(OAuthValidationError::InvalidAccessToken.into());
+        }
+        self.verify_token_confirmation(&issued.confirmation, &req, validator).await?;
+
+        let granted = issued.grant.scope().unwrap_or_default();
+        if !validator.required_scopes().iter().all(|required| granted.contains(required)) {
+            return Err(OAuthValidationError::InsufficientScope(
+                validator.required_scopes().join(" "),
+            )
+            .into());
+        }
+
+        Ok(issued)
+    }
+
+    /// Verify the client sending a bound access token possesses the key the token is bound to.
+    /// Without this, a stolen bound access token could be used by anyone sending it as a bearer token.
+    async fn verify_token_confirmation(
+        &self,
+        confirmation: &TokenConfirmation,
+        req: &ProtectedResourceRequest,
+        validator: &BearerTokenValidator,
+    ) -> Result<(), OAuthError<E>> {
+        if let Some(jkt) = &confirmation.jkt {
+            let (Some(proof), Some(uri)) = (&req.dpop_proof, validator.dpop_uri()) else {
+                return Err(OAuthValidationError::InvalidAccessToken.into());
+            };
+
+            // The proof must be signed by the key the token is bound to, and include the hash of the token
+            let proof_jkt = self
+                .verify_dpop_proof(proof, req.request_method.as_str(), uri, Some(&req.access_token))
+                .await?;
+            if &proof_jkt != jkt {
+                return Err(OAuthValidationError::InvalidAccessToken.into());
+            }
+        }
+
+        if let Some(x5t_s256) = &confirmation.x5t_s256 {
+            // The certificate presented for mutual TLS must be the one the token is bound to
+            let presented = req.client_certificate.as_deref().map(certificate_thumbprint);
+            if presented.as_ref() != Some(x5t_s256) {
+                return Err(OAuthValidationError::InvalidAccessToken.into());
+            }
+        }
+
+        Ok(())
+    }
+}
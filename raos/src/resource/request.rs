@@ -0,0 +1,56 @@
+use crate::common::{
+    auth::access_token_from_request,
+    frontend::{FrontendRequest, FrontendRequestMethod, OAuthValidationError},
+    util::NoneIfEmpty,
+};
+
+/// A parsed request to a protected resource, carrying a bearer access token as described in RFC 6750,
+/// or a DPoP-bound access token as described in RFC 9449.
+#[derive(Debug)]
+pub struct ProtectedResourceRequest {
+    /// The access token, sent in the `Authorization` header or the form-encoded body.
+    pub access_token: String,
+    /// The method of the request, which the DPoP proof of a DPoP-bound access token is made for.
+    pub request_method: FrontendRequestMethod,
+    /// The DPoP proof sent in the `DPoP` header, proving possession of the key a DPoP-bound access token is bound to.
+    pub dpop_proof: Option<String>,
+    /// The DER encoded certificate the client presented for mutual TLS,
+    /// which a certificate-bound access token must be sent along with as described in RFC 8705 section 3.
+    pub client_certificate: Option<Vec<u8>>,
+}
+
+impl ProtectedResourceRequest {
+    /// Parse a request to a protected resource.
+    ///
+    /// # Parameters
+    /// - `request` - The unparsed incoming request from the client.
+    /// - `allow_form_body` - Whether the access token may be sent in the form-encoded body, as described in RFC 6750 section 2.2.
+    pub(crate) fn from_request(
+        request: &dyn FrontendRequest,
+        allow_form_body: bool,
+    ) -> Result<Self, OAuthValidationError> {
+        let header_token = access_token_from_request(request);
+
+        // The body method is never used for GET requests, as those have no entity-body
+        let body_token =
+            if allow_form_body && !matches!(request.request_method(), FrontendRequestMethod::GET) {
+                request.body_param("access_token").none_if_empty()
+            } else {
+                None
+            };
+
+        // Clients MUST NOT use more than one method to transmit the token in each request
+        let access_token = match (header_token, body_token) {
+            (Some(_), Some(_)) => return Err(OAuthValidationError::MultipleAccessTokens),
+            (Some(access_token), None) | (None, Some(access_token)) => access_token,
+            (None, None) => return Err(OAuthValidationError::MissingAccessToken),
+        };
+
+        Ok(ProtectedResourceRequest {
+            access_token,
+            request_method: request.request_method(),
+            dpop_proof: request.header_param("dpop").none_if_empty(),
+            client_certificate: request.peer_certificate(),
+        })
+    }
+}
@@ -0,0 +1,257 @@
+use mockall::predicate::{always, eq};
+use std::time::{Duration, Instant};
+
+use crate::{
+    common::{
+        frontend::{
+            FrontendRequest, FrontendRequestMethod, OAuthError, OAuthValidationError,
+            PublicOAuthError,
+        },
+        x509::certificate_thumbprint,
+    },
+    resource::{BearerTokenValidator, ProtectedResourceRequest},
+    test::{mock::request_from_raw_http, TestEnvironment, DEFAULT_CLIENT_ID, DEFAULT_TOKEN},
+    token::{GrantType, IssuedToken, TokenConfirmation, TokenTypeHint},
+};
+
+/// A test environment where the default token was issued with the given scope, valid for the given duration.
+fn resource_environment(scope: &'static [&'static str], valid_for: Duration) -> TestEnvironment {
+    bound_resource_environment(scope, valid_for, TokenConfirmation::default())
+}
+
+/// A test environment like [resource_environment], where the default token is bound to the given key.
+fn bound_resource_environment(
+    scope: &'static [&'static str],
+    valid_for: Duration,
+    confirmation: TokenConfirmation,
+) -> TestEnvironment {
+    let mut test = TestEnvironment::new();
+    test.token_provider
+        .expect_lookup_token()
+        .with(eq(DEFAULT_TOKEN.to_string()), eq(Some(TokenTypeHint::AccessToken)))
+        .returning(move |_, _| {
+            Ok(Some(IssuedToken {
+                client_id: DEFAULT_CLIENT_ID.to_string(),
+                grant: GrantType::AuthorizationCode {
+                    resource_owner: 1,
+                    scope: scope.iter().map(|scope| scope.to_string()).collect(),
+                    code_id: "code_id".to_string(),
+                },
+                token_type: TokenTypeHint::AccessToken,
+                valid_until: Instant::now() + valid_for,
+                confirmation: confirmation.clone(),
+            }))
+        });
+    test
+}
+
+fn resource_request() -> impl FrontendRequest {
+    request_from_raw_http(&format!(
+        r#"
+        GET /resource HTTP/1.1
+        authorization: Bearer {DEFAULT_TOKEN}
+    "#
+    ))
+}
+
+/// A request with the default token in the form-encoded body, along with the given additional header.
+fn form_body_request(header: &str) -> impl FrontendRequest {
+    request_from_raw_http(&format!(
+        r#"
+        POST /resource HTTP/1.1
+        Content-Type: application/x-www-form-urlencoded
+        {header}
+
+        access_token={DEFAULT_TOKEN}
+    "#
+    ))
+}
+
+#[tokio::test]
+async fn test_valid_token_with_required_scope_is_accepted() {
+    // Arrange
+    let manager = resource_environment(&["read", "write"], Duration::from_secs(60)).build();
+    let validator = BearerTokenValidator::new().require_scope("read");
+
+    // Act
+    let result = manager.handle_protected_resource_request(resource_request(), &validator).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    let issued = result.unwrap();
+    assert_eq!(DEFAULT_CLIENT_ID, issued.client_id);
+    assert_eq!(Some(&1), issued.grant.resource_owner());
+}
+
+#[tokio::test]
+async fn test_missing_token_is_rejected() {
+    // If the request lacks any authentication information, the resource server SHOULD NOT include an error code
+    // or other error information. We still reject the request with a 401, so the client knows it has to authenticate.
+
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.token_provider.expect_lookup_token().never();
+    let manager = test.build();
+    let req = request_from_raw_http(
+        r#"
+        GET /resource HTTP/1.1
+    "#,
+    );
+
+    // Act
+    let result = manager.handle_protected_resource_request(req, &BearerTokenValidator::new()).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    let error = result.unwrap_err();
+    assert_eq!(OAuthError::ValidationFailed(OAuthValidationError::MissingAccessToken), error);
+    let error = PublicOAuthError::from(error);
+    assert_eq!(401, error.status_code());
+    assert_eq!(vec![("WWW-Authenticate", "Bearer".to_string())], error.headers());
+}
+
+#[tokio::test]
+async fn test_unknown_token_is_invalid_token() {
+    // The access token provided is expired, revoked, malformed, or invalid for other reasons.
+    // The resource SHOULD respond with the HTTP 401 (Unauthorized) status code.
+
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.token_provider.expect_lookup_token().with(always(), always()).returning(|_, _| Ok(None));
+    let manager = test.build();
+
+    // Act
+    let result = manager
+        .handle_protected_resource_request(resource_request(), &BearerTokenValidator::new())
+        .await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    let error = PublicOAuthError::from(result.unwrap_err());
+    assert_eq!(PublicOAuthError::InvalidToken, error);
+    assert_eq!(401, error.status_code());
+    assert_eq!(
+        vec![("WWW-Authenticate", r#"Bearer error="invalid_token""#.to_string())],
+        error.headers()
+    );
+}
+
+#[tokio::test]
+async fn test_expired_token_is_invalid_token() {
+    // Arrange
+    let manager = resource_environment(&["read"], Duration::ZERO).build();
+
+    // Act
+    let result = manager
+        .handle_protected_resource_request(resource_request(), &BearerTokenValidator::new())
+        .await;
+
+    // Assert
+    assert_eq!(Err(OAuthError::ValidationFailed(OAuthValidationError::InvalidAccessToken)), result);
+}
+
+#[tokio::test]
+async fn test_missing_scope_is_insufficient_scope() {
+    // The request requires higher privileges than provided by the access token.
+    // The resource server SHOULD respond with the HTTP 403 (Forbidden) status code
+    // and MAY include the "scope" attribute with the scope necessary to access the protected resource.
+
+    // Arrange
+    let manager = resource_environment(&["read"], Duration::from_secs(60)).build();
+    let validator = BearerTokenValidator::new().require_scope("read").require_scope("write");
+
+    // Act
+    let result = manager.handle_protected_resource_request(resource_request(), &validator).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    let error = PublicOAuthError::from(result.unwrap_err());
+    assert_eq!(PublicOAuthError::InsufficientScope("read write".to_string()), error);
+    assert_eq!(403, error.status_code());
+    assert_eq!(
+        vec![(
+            "WWW-Authenticate",
+            r#"Bearer error="insufficient_scope", scope="read write""#.to_string()
+        )],
+        error.headers()
+    );
+}
+
+#[tokio::test]
+async fn test_form_body_token_is_only_accepted_when_allowed() {
+    // Arrange
+    let manager = resource_environment(&[], Duration::from_secs(60)).build();
+
+    // Act
+    let denied = manager
+        .handle_protected_resource_request(
+            form_body_request("Accept: application/json"),
+            &BearerTokenValidator::new(),
+        )
+        .await;
+    let allowed = manager
+        .handle_protected_resource_request(
+            form_body_request("Accept: application/json"),
+            &BearerTokenValidator::new().allow_form_body(),
+        )
+        .await;
+
+    // Assert
+    assert_eq!(Err(OAuthError::ValidationFailed(OAuthValidationError::MissingAccessToken)), denied);
+    assert!(allowed.is_ok(), "result is not Ok, result is {:?}", allowed);
+}
+
+#[tokio::test]
+async fn test_token_in_header_and_body_is_rejected() {
+    // Clients MUST NOT use more than one method to transmit the token in each request.
+
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.token_provider.expect_lookup_token().never();
+    let manager = test.build();
+    let req = form_body_request(&format!("authorization: Bearer {DEFAULT_TOKEN}"));
+
+    // Act
+    let result = manager
+        .handle_protected_resource_request(req, &BearerTokenValidator::new().allow_form_body())
+        .await;
+
+    // Assert
+    assert_eq!(
+        Err(OAuthError::ValidationFailed(OAuthValidationError::MultipleAccessTokens)),
+        result
+    );
+}
+
+#[tokio::test]
+async fn test_certificate_bound_token_requires_same_certificate() {
+    // The protected resource MUST obtain the client certificate used for mutual TLS authentication
+    // and MUST verify that the certificate matches the certificate associated with the access token.
+
+    // Arrange
+    let confirmation = TokenConfirmation {
+        x5t_s256: Some(certificate_thumbprint(b"certificate")),
+        ..Default::default()
+    };
+    let manager = bound_resource_environment(&[], Duration::from_secs(60), confirmation).build();
+    let request = |client_certificate: Option<&[u8]>| ProtectedResourceRequest {
+        access_token: DEFAULT_TOKEN.to_string(),
+        request_method: FrontendRequestMethod::GET,
+        dpop_proof: None,
+        client_certificate: client_certificate.map(<[u8]>::to_vec),
+    };
+    let validator = BearerTokenValidator::new();
+
+    // Act
+    let same = manager.handle_protected_resource(request(Some(b"certificate")), &validator).await;
+    let other = manager.handle_protected_resource(request(Some(b"other")), &validator).await;
+    let missing = manager.handle_protected_resource(request(None), &validator).await;
+
+    // Assert
+    assert!(same.is_ok(), "result is not Ok, result is {:?}", same);
+    assert_eq!(Err(OAuthError::ValidationFailed(OAuthValidationError::InvalidAccessToken)), other);
+    assert_eq!(
+        Err(OAuthError::ValidationFailed(OAuthValidationError::InvalidAccessToken)),
+        missing
+    );
+}
@@ -0,0 +1,67 @@
+/// The requirements a protected resource places on the bearer access tokens it accepts.
+/// Passed to [OAuthManager::handle_protected_resource_request](crate::manager::OAuthManager::handle_protected_resource_request)
+/// to validate the token of an incoming request.
+///
+/// # Example
+/// ```
+/// # use raos::resource::BearerTokenValidator;
+/// let validator = BearerTokenValidator::new().require_scope("read").require_scope("write");
+/// assert_eq!(vec!["read", "write"], validator.required_scopes());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BearerTokenValidator {
+    required_scopes: Vec<String>,
+    allow_form_body: bool,
+    dpop_uri: Option<String>,
+}
+
+impl BearerTokenValidator {
+    /// Create a validator that accepts any valid access token sent in the `Authorization` header.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require the access token to be granted the given scope.
+    /// When called multiple times, all of the scopes are required.
+    ///
+    /// # Parameters
+    /// - `scope` - The scope the access token must be granted.
+    pub fn require_scope(mut self, scope: impl Into<String>) -> Self {
+        self.required_scopes.push(scope.into());
+        self
+    }
+
+    /// Also accept the access token in the `access_token` parameter of a form-encoded request body,
+    /// as described in RFC 6750 section 2.2.
+    /// This method SHOULD NOT be used, unless the client is unable to use the `Authorization` header.
+    pub fn allow_form_body(mut self) -> Self {
+        self.allow_form_body = true;
+        self
+    }
+
+    /// Accept DPoP-bound access tokens, as described in RFC 9449 section 7.
+    /// The DPoP proof sent along with such a token must be made for the given uri of the protected resource.
+    /// Without this, DPoP-bound access tokens are rejected.
+    ///
+    /// # Parameters
+    /// - `uri` - The full uri of the protected resource, like `https://example.com/resource`.
+    pub fn accept_dpop(mut self, uri: impl Into<String>) -> Self {
+        self.dpop_uri = Some(uri.into());
+        self
+    }
+
+    /// The scopes the access token must be granted.
+    pub fn required_scopes(&self) -> &[String] {
+        &self.required_scopes
+    }
+
+    /// Whether the access token may be sent in the form-encoded request body.
+    pub fn allows_form_body(&self) -> bool {
+        self.allow_form_body
+    }
+
+    /// The uri of the protected resource DPoP proofs must be made for, if DPoP-bound access tokens are accepted.
+    pub fn dpop_uri(&self) -> Option<&str> {
+        self.dpop_uri.as_deref()
+    }
+}
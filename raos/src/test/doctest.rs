@@ -1,8 +1,24 @@
 use crate::{
-    authorize::{AuthorizationProvider, GrantAuthorizationResult},
-    common::model::{Client, ClientProvider, CodeChallenge, Grant},
+    authorize::{
+        AuthorizationProvider, AuthorizationRequest, GrantAuthorizationResult, ResponseType,
+    },
+    common::model::{Client, ClientProvider, CodeChallenge, Grant, TokenEndpointAuthMethod},
+    device::{
+        DeviceAuthorization, DeviceAuthorizationDecision, DeviceAuthorizationProvider,
+        DeviceCodeStatus, DeviceCodes, DeviceGrant, DEVICE_CODE_GRANT_TYPE,
+    },
     manager::OAuthManager,
-    token::{GrantType, RefreshGrant, Token, TokenProvider},
+    par::{PushedAuthorizationProvider, PushedRequestReference},
+    registration::{ClientMetadata, ClientRegistration, ClientRegistrationProvider},
+    token::{
+        GrantType, IssuedToken, RefreshGrant, Token, TokenConfirmation, TokenProvider,
+        TokenTypeHint,
+    },
+};
+#[cfg(feature = "oidc")]
+use crate::{
+    jwt::{JwsAlgorithm, SigningKey, SigningKeySource},
+    oidc::{UserInfo, UserInfoProvider},
 };
 use async_trait::async_trait;
 use std::{
@@ -15,6 +31,40 @@ static DOCTEST_CLIENT: LazyLock<Client> = LazyLock::new(|| Client {
     client_id: "CLIENT_ID".to_string(),
     redirect_uris: vec!["https://example.com".to_string()],
     confidential: false,
+    grant_types: [
+        "authorization_code",
+        "client_credentials",
+        "refresh_token",
+        DEVICE_CODE_GRANT_TYPE,
+    ]
+    .map(str::to_string)
+    .to_vec(),
+    response_types: vec!["code".to_string()],
+    token_endpoint_auth_method: TokenEndpointAuthMethod::ClientSecretBasic,
+    default_scopes: Vec::new(),
+    access_token_lifetime: None,
+    refresh_token_lifetime: None,
+    display: Default::default(),
+});
+
+static DOCTEST_RESOURCE_SERVER: LazyLock<Client> = LazyLock::new(|| Client {
+    client_id: "RESOURCE_SERVER".to_string(),
+    redirect_uris: vec!["https://example.com".to_string()],
+    confidential: true,
+    grant_types: [
+        "authorization_code",
+        "client_credentials",
+        "refresh_token",
+        DEVICE_CODE_GRANT_TYPE,
+    ]
+    .map(str::to_string)
+    .to_vec(),
+    response_types: vec!["code".to_string()],
+    token_endpoint_auth_method: TokenEndpointAuthMethod::ClientSecretBasic,
+    default_scopes: Vec::new(),
+    access_token_lifetime: None,
+    refresh_token_lifetime: None,
+    display: Default::default(),
 });
 
 /// Mock function to return an owner ID used in other tests
@@ -24,11 +74,17 @@ pub fn owner_id_from_session() -> u32 {
 
 /// Mock function to return an oauth manager with basic data
 pub fn oauth_manager_from_application_state() -> OAuthManager<u32, (), ()> {
-    OAuthManager::builder()
+    let builder = OAuthManager::builder()
         .client_provider(DocTestClientProvider)
         .authorization_provider(DocTestAuthorizationProvider)
         .token_provider(DocTestTokenProvider)
-        .build()
+        .device_authorization_provider(DocTestDeviceAuthorizationProvider)
+        .pushed_authorization_provider(DocTestPushedAuthorizationProvider)
+        .client_registration_provider(DocTestClientRegistrationProvider)
+        .set_authorization_server_identifier("https://example.com".to_string());
+    #[cfg(feature = "oidc")]
+    let builder = builder.openid_connect(DocTestUserInfoProvider, DocTestSigningKeySource);
+    builder.build()
 }
 
 struct DocTestClientProvider;
@@ -38,11 +94,10 @@ impl ClientProvider for DocTestClientProvider {
     type Error = ();
 
     async fn get_client_by_id(&self, client_id: &str) -> Result<Option<Client>, Self::Error> {
-        if client_id == DOCTEST_CLIENT.client_id {
-            Ok(Some(DOCTEST_CLIENT.clone()))
-        } else {
-            Ok(None)
-        }
+        Ok([&*DOCTEST_CLIENT, &*DOCTEST_RESOURCE_SERVER]
+            .into_iter()
+            .find(|client| client.client_id == client_id)
+            .cloned())
     }
 
     async fn allow_client_scopes(
@@ -95,15 +150,24 @@ impl AuthorizationProvider for DocTestAuthorizationProvider {
                 owner_id: 1,
                 client_id: "CLIENT_ID".to_string(),
                 scope: vec!["SCOPE".to_string()],
+                resource: Vec::new(),
                 redirect_uri: "https://example.com".parse().unwrap(),
                 code_challenge: CodeChallenge::Plain {
                     code_challenge: "CODE_CHALLENGE".to_string(),
                 },
+                code_id: "CODE_ID".to_string(),
+                dpop_jkt: None,
+                openid: Default::default(),
+                auth_time: None,
             }))
         } else {
             Ok(None)
         }
     }
+
+    async fn mark_code_used(&self, _code: &str) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
 }
 
 struct DocTestTokenProvider;
@@ -117,6 +181,8 @@ impl TokenProvider for DocTestTokenProvider {
         &self,
         _client: &Client,
         _grant: GrantType<Self::OwnerId>,
+        _confirmation: TokenConfirmation,
+        _audience: &[String],
     ) -> Result<Token, Self::Error> {
         Ok(Token {
             token: "ACCESS_TOKEN".to_string(),
@@ -134,9 +200,240 @@ impl TokenProvider for DocTestTokenProvider {
                 client_id: "CLIENT_ID".to_string(),
                 resource_owner: 1,
                 scope: vec!["SCOPE".to_string()],
+                resource: Vec::new(),
+                family_id: "REFRESH_TOKEN_FAMILY".to_string(),
+                dpop_jkt: None,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn mark_refresh_token_used(&self, _refresh_token: &str) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+
+    async fn revoke_refresh_token_family(&self, _family_id: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn revoke_code_tokens(&self, _code_id: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn lookup_token(
+        &self,
+        token: String,
+        _token_type_hint: Option<TokenTypeHint>,
+    ) -> Result<Option<IssuedToken<Self::OwnerId>>, Self::Error> {
+        if token == "ACCESS_TOKEN" {
+            Ok(Some(IssuedToken {
+                client_id: "CLIENT_ID".to_string(),
+                grant: GrantType::AuthorizationCode {
+                    resource_owner: 1,
+                    scope: vec!["openid".to_string(), "SCOPE".to_string()],
+                    code_id: "CODE_ID".to_string(),
+                },
+                token_type: TokenTypeHint::AccessToken,
+                valid_until: Instant::now() + Duration::from_secs(3600),
+                confirmation: TokenConfirmation::default(),
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn revoke_token(
+        &self,
+        _client: &Client,
+        _token: String,
+        _token_type_hint: Option<TokenTypeHint>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+struct DocTestDeviceAuthorizationProvider;
+
+#[async_trait]
+impl DeviceAuthorizationProvider for DocTestDeviceAuthorizationProvider {
+    type OwnerId = u32;
+    type Error = ();
+
+    async fn generate_device_codes(
+        &self,
+        _authorization: DeviceAuthorization,
+    ) -> Result<DeviceCodes, Self::Error> {
+        Ok(DeviceCodes {
+            device_code: "DEVICE_CODE".to_string(),
+            user_code: "USER_CODE".to_string(),
+            valid_until: Instant::now() + Duration::from_secs(600),
+        })
+    }
+
+    async fn get_device_authorization(
+        &self,
+        user_code: &str,
+    ) -> Result<Option<DeviceAuthorization>, Self::Error> {
+        if user_code == "USER_CODE" {
+            Ok(Some(DeviceAuthorization {
+                client_id: "CLIENT_ID".to_string(),
+                scope: vec!["SCOPE".to_string()],
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn complete_device_authorization(
+        &self,
+        _user_code: &str,
+        _decision: DeviceAuthorizationDecision<Self::OwnerId>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn poll_device_code(
+        &self,
+        device_code: String,
+    ) -> Result<DeviceCodeStatus<Self::OwnerId>, Self::Error> {
+        if device_code == "DEVICE_CODE" {
+            Ok(DeviceCodeStatus::Approved(DeviceGrant {
+                client_id: "CLIENT_ID".to_string(),
+                resource_owner: 1,
+                scope: vec!["SCOPE".to_string()],
+            }))
+        } else {
+            Ok(DeviceCodeStatus::Invalid)
+        }
+    }
+}
+
+struct DocTestPushedAuthorizationProvider;
+
+#[async_trait]
+impl PushedAuthorizationProvider for DocTestPushedAuthorizationProvider {
+    type Error = ();
+
+    async fn store_authorization_request(
+        &self,
+        _request: AuthorizationRequest,
+    ) -> Result<PushedRequestReference, Self::Error> {
+        Ok(PushedRequestReference {
+            reference: "REQUEST_URI".to_string(),
+            valid_until: Instant::now() + Duration::from_secs(60),
+        })
+    }
+
+    async fn take_authorization_request(
+        &self,
+        reference: &str,
+    ) -> Result<Option<AuthorizationRequest>, Self::Error> {
+        if reference == "REQUEST_URI" {
+            Ok(Some(AuthorizationRequest {
+                response_type: ResponseType::Code,
+                client_id: "CLIENT_ID".to_string(),
+                code_challenge: CodeChallenge::Plain {
+                    code_challenge: "CODE_CHALLENGE".to_string(),
+                },
+                redirect_uri: Some("https://example.com".to_string()),
+                scope: Some("SCOPE".to_string()),
+                resource: Vec::new(),
+                state: None,
+                dpop_jkt: None,
+                request_uri: None,
+                openid: Default::default(),
             }))
         } else {
             Ok(None)
         }
     }
 }
+
+struct DocTestClientRegistrationProvider;
+
+#[async_trait]
+impl ClientRegistrationProvider for DocTestClientRegistrationProvider {
+    type Error = ();
+
+    async fn allow_registration(
+        &self,
+        initial_access_token: Option<String>,
+        _metadata: &ClientMetadata,
+    ) -> Result<bool, Self::Error> {
+        Ok(initial_access_token.as_deref() == Some("INITIAL_ACCESS_TOKEN"))
+    }
+
+    async fn register_client(&self, _registration: ClientRegistration) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn verify_registration_access_token(
+        &self,
+        client_id: &str,
+        registration_access_token: &str,
+    ) -> Result<bool, Self::Error> {
+        Ok(client_id == "CLIENT_ID" && registration_access_token == "REGISTRATION_ACCESS_TOKEN")
+    }
+
+    async fn get_client_metadata(
+        &self,
+        client_id: &str,
+    ) -> Result<Option<ClientMetadata>, Self::Error> {
+        if client_id == "CLIENT_ID" {
+            Ok(Some(serde_json::from_str(
+                r#"{ "redirect_uris": ["https://example.com"], "token_endpoint_auth_method": "none" }"#,
+            )
+            .unwrap()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn update_client(
+        &self,
+        _client: &Client,
+        _metadata: &ClientMetadata,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn delete_client(&self, _client_id: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "oidc")]
+struct DocTestUserInfoProvider;
+
+#[cfg(feature = "oidc")]
+#[async_trait]
+impl UserInfoProvider for DocTestUserInfoProvider {
+    type OwnerId = u32;
+    type Error = ();
+
+    async fn user_info(
+        &self,
+        _owner_id: &Self::OwnerId,
+        _scopes: &[String],
+    ) -> Result<UserInfo, Self::Error> {
+        Ok(UserInfo { sub: "SUBJECT".to_string(), claims: Default::default() })
+    }
+}
+
+#[cfg(feature = "oidc")]
+struct DocTestSigningKeySource;
+
+#[cfg(feature = "oidc")]
+#[async_trait]
+impl SigningKeySource for DocTestSigningKeySource {
+    type Error = ();
+
+    async fn signing_key(&self) -> Result<SigningKey, Self::Error> {
+        Ok(SigningKey { kid: "KEY_ID".to_string(), algorithm: JwsAlgorithm::ES256 })
+    }
+
+    async fn sign(&self, _key: &SigningKey, _signing_input: &[u8]) -> Result<Vec<u8>, Self::Error> {
+        Ok(b"SIGNATURE".to_vec())
+    }
+}
@@ -1,13 +1,29 @@
+#[cfg(feature = "oidc")]
+use crate::oidc::{UserInfo, UserInfoProvider};
 use crate::{
-    authorize::{AuthorizationProvider, GrantAuthorizationResult},
+    assertion::JwtBearerProvider,
+    authorize::{AuthorizationProvider, AuthorizationRequest, GrantAuthorizationResult},
     common::{
         frontend::FrontendResponse,
-        model::{Client, ClientProvider, Grant},
+        jose::{Jwk, JwsVerifier},
+        model::{Client, ClientAssertionKeys, ClientProvider, Grant, TlsClientAuthentication},
+    },
+    device::{
+        DeviceAuthorization, DeviceAuthorizationDecision, DeviceAuthorizationProvider,
+        DeviceCodeStatus, DeviceCodes,
+    },
+    dpop::{DPoPNonceCheck, DPoPProvider},
+    exchange::{Actor, TokenExchangeGrant, TokenExchangeProvider, TokenSubject},
+    par::{PushedAuthorizationProvider, PushedRequestReference},
+    registration::{ClientMetadata, ClientRegistration, ClientRegistrationProvider},
+    token::{
+        ExtensionGrant, GrantType, GrantTypeHandler, IssuedToken, RefreshGrant, Token,
+        TokenConfirmation, TokenProvider, TokenTypeHint,
     },
-    token::{GrantType, RefreshGrant, Token, TokenProvider},
 };
 use async_trait::async_trait;
 use mockall::mock;
+use std::{collections::HashMap, time::SystemTime};
 
 mock! {
     pub AuthorizationProvider {}
@@ -20,6 +36,8 @@ mock! {
         async fn authorize_grant(&self, _client: &Client, _scopes: &[String], _extras: &mut Option<()>) -> Result<GrantAuthorizationResult<u32>, ()>;
         async fn generate_code_for_grant(&self, grant: Grant<u32>) -> Result<String, ()>;
         async fn exchange_code_for_grant(&self, code: String) -> Result<Option<Grant<u32>>, ()>;
+        async fn mark_code_used(&self, code: &str) -> Result<bool, ()>;
+        async fn authentication_time(&self, owner_id: &u32, extras: &mut Option<()>) -> Result<Option<SystemTime>, ()>;
         async fn handle_required_authentication(&self, extras: &mut Option<()>) -> Result<FrontendResponse, ()>;
         async fn handle_missing_scope_consent(&self, scopes: Vec<String>, extras: &mut Option<()>) -> Result<FrontendResponse, ()>;
     }
@@ -33,7 +51,12 @@ mock! {
         type Error = ();
         async fn get_client_by_id(&self, client_id: &str) -> Result<Option<Client>, ()>;
         async fn allow_client_scopes(&self, client: &Client, requested_scopes: Vec<String>) -> Result<Vec<String>, ()>;
+        async fn allow_client_resources(&self, client: &Client, resources: &[String]) -> Result<bool, ()>;
         async fn verify_client_secret(&self, client: &Client, client_secret: &str) -> Result<bool, ()>;
+        async fn get_client_assertion_keys(&self, client: &Client) -> Result<Option<ClientAssertionKeys>, ()>;
+        async fn get_tls_client_authentication(&self, client: &Client) -> Result<Option<TlsClientAuthentication>, ()>;
+        async fn register_client_assertion_id(&self, client: &Client, jti: &str, valid_until: SystemTime) -> Result<bool, ()>;
+        async fn user_info_signed_response(&self, client: &Client) -> Result<bool, ()>;
     }
 }
 
@@ -44,7 +67,120 @@ mock! {
     impl TokenProvider for TokenProvider {
         type OwnerId = u32;
         type Error = ();
-        async fn token(&self, client: &Client, grant: GrantType<u32>) -> Result<Token, ()>;
+        async fn token(&self, client: &Client, grant: GrantType<u32>, confirmation: TokenConfirmation, audience: &[String]) -> Result<Token, ()>;
         async fn exchange_refresh_token(&self, refresh_token: String) -> Result<Option<RefreshGrant<u32>>, ()>;
+        async fn mark_refresh_token_used(&self, refresh_token: &str) -> Result<bool, ()>;
+        async fn revoke_refresh_token_family(&self, family_id: &str) -> Result<(), ()>;
+        async fn revoke_code_tokens(&self, code_id: &str) -> Result<(), ()>;
+        async fn lookup_token(&self, token: String, token_type_hint: Option<TokenTypeHint>) -> Result<Option<IssuedToken<u32>>, ()>;
+        async fn revoke_token(&self, client: &Client, token: String, token_type_hint: Option<TokenTypeHint>) -> Result<(), ()>;
+    }
+}
+
+mock! {
+    pub DeviceAuthorizationProvider {}
+
+    #[async_trait]
+    impl DeviceAuthorizationProvider for DeviceAuthorizationProvider {
+        type OwnerId = u32;
+        type Error = ();
+        async fn generate_device_codes(&self, authorization: DeviceAuthorization) -> Result<DeviceCodes, ()>;
+        async fn get_device_authorization(&self, user_code: &str) -> Result<Option<DeviceAuthorization>, ()>;
+        async fn complete_device_authorization(&self, user_code: &str, decision: DeviceAuthorizationDecision<u32>) -> Result<(), ()>;
+        async fn poll_device_code(&self, device_code: String) -> Result<DeviceCodeStatus<u32>, ()>;
+    }
+}
+
+mock! {
+    pub PushedAuthorizationProvider {}
+
+    #[async_trait]
+    impl PushedAuthorizationProvider for PushedAuthorizationProvider {
+        type Error = ();
+        async fn store_authorization_request(&self, request: AuthorizationRequest) -> Result<PushedRequestReference, ()>;
+        async fn take_authorization_request(&self, reference: &str) -> Result<Option<AuthorizationRequest>, ()>;
+    }
+}
+
+mock! {
+    pub DPoPProvider {}
+
+    #[async_trait]
+    impl DPoPProvider for DPoPProvider {
+        type Error = ();
+        async fn register_proof_id(&self, jkt: &str, jti: &str, valid_until: SystemTime) -> Result<bool, ()>;
+        async fn check_nonce(&self, nonce: Option<String>) -> Result<DPoPNonceCheck, ()>;
+    }
+}
+
+mock! {
+    pub ClientRegistrationProvider {}
+
+    #[async_trait]
+    impl ClientRegistrationProvider for ClientRegistrationProvider {
+        type Error = ();
+        async fn allow_registration(&self, initial_access_token: Option<String>, metadata: &ClientMetadata) -> Result<bool, ()>;
+        async fn register_client(&self, registration: ClientRegistration) -> Result<(), ()>;
+        async fn verify_registration_access_token(&self, client_id: &str, registration_access_token: &str) -> Result<bool, ()>;
+        async fn get_client_metadata(&self, client_id: &str) -> Result<Option<ClientMetadata>, ()>;
+        async fn update_client(&self, client: &Client, metadata: &ClientMetadata) -> Result<(), ()>;
+        async fn delete_client(&self, client_id: &str) -> Result<(), ()>;
+    }
+}
+
+#[cfg(feature = "oidc")]
+mock! {
+    pub UserInfoProvider {}
+
+    #[async_trait]
+    impl UserInfoProvider for UserInfoProvider {
+        type OwnerId = u32;
+        type Error = ();
+        async fn user_info(&self, owner_id: &u32, scopes: &[String]) -> Result<UserInfo, ()>;
+    }
+}
+
+mock! {
+    pub JwsVerifier {}
+
+    impl JwsVerifier for JwsVerifier {
+        fn supported_algorithms(&self) -> Vec<&'static str>;
+        fn verify(&self, alg: &str, key: &Jwk, signing_input: &[u8], signature: &[u8]) -> bool;
+    }
+}
+
+mock! {
+    pub TokenExchangeProvider {}
+
+    #[async_trait]
+    impl TokenExchangeProvider for TokenExchangeProvider {
+        type OwnerId = u32;
+        type Error = ();
+        async fn validate_subject_token(&self, subject_token: String, subject_token_type: String) -> Result<Option<TokenSubject<u32>>, ()>;
+        async fn validate_actor_token(&self, actor_token: String, actor_token_type: String) -> Result<Option<Actor>, ()>;
+        async fn authorize_exchange(&self, client: &Client, grant: &TokenExchangeGrant<u32>) -> Result<bool, ()>;
+    }
+}
+
+mock! {
+    pub JwtBearerProvider {}
+
+    #[async_trait]
+    impl JwtBearerProvider for JwtBearerProvider {
+        type OwnerId = u32;
+        type Error = ();
+        async fn get_issuer_keys(&self, issuer: &str) -> Result<Option<Vec<Jwk>>, ()>;
+        async fn resolve_subject(&self, client: &Client, issuer: &str, subject: &str) -> Result<Option<u32>, ()>;
+    }
+}
+
+mock! {
+    pub GrantTypeHandler {}
+
+    #[async_trait]
+    impl GrantTypeHandler for GrantTypeHandler {
+        type OwnerId = u32;
+        type Error = ();
+        async fn handle_grant(&self, client: &Client, parameters: &HashMap<String, String>) -> Result<Option<ExtensionGrant<u32>>, ()>;
     }
 }
@@ -1,4 +1,5 @@
 use crate::common::frontend::{FrontendRequest, FrontendRequestMethod};
+use serde_json::Value;
 use std::collections::HashMap;
 
 /// A mocked frontend request for testing purposes.
@@ -7,11 +8,25 @@ pub struct RequestFromRawHttp {
     /// The parsed request method of the request.
     pub method: FrontendRequestMethod,
     /// The parsed query parameters of the request.
-    pub query: HashMap<String, String>,
+    pub query: Vec<(String, String)>,
     /// The parsed headers of the request.
     pub headers: HashMap<String, String>,
     /// The parsed body parameters of the request.
-    pub body: HashMap<String, String>,
+    pub body: Vec<(String, String)>,
+    /// The parsed body of the request, if it was sent as JSON.
+    pub json: Option<Value>,
+    /// The DER encoded certificate presented by the client, if any.
+    pub peer_certificate: Option<Vec<u8>>,
+}
+
+/// Look up the last value of a parameter, as a repeated parameter overrides the earlier values.
+fn param(params: &[(String, String)], key: &str) -> Option<String> {
+    params.iter().rev().find(|(k, _)| k == key).map(|(_, v)| v.clone())
+}
+
+/// Look up all values of a parameter, in the order they were sent.
+fn param_values(params: &[(String, String)], key: &str) -> Vec<String> {
+    params.iter().filter(|(k, _)| k == key).map(|(_, v)| v.clone()).collect()
 }
 
 impl FrontendRequest for RequestFromRawHttp {
@@ -24,11 +39,31 @@ impl FrontendRequest for RequestFromRawHttp {
     }
 
     fn query_param(&self, key: &str) -> Option<String> {
-        self.query.get(key).cloned()
+        param(&self.query, key)
     }
 
     fn body_param(&self, key: &str) -> Option<String> {
-        self.body.get(key).cloned()
+        param(&self.body, key)
+    }
+
+    fn query_param_values(&self, key: &str) -> Vec<String> {
+        param_values(&self.query, key)
+    }
+
+    fn body_param_values(&self, key: &str) -> Vec<String> {
+        param_values(&self.body, key)
+    }
+
+    fn body_params(&self) -> HashMap<String, String> {
+        self.body.iter().cloned().collect()
+    }
+
+    fn body_json(&self) -> Option<Value> {
+        self.json.clone()
+    }
+
+    fn peer_certificate(&self) -> Option<Vec<u8>> {
+        self.peer_certificate.clone()
     }
 }
 
@@ -51,11 +86,13 @@ pub fn request_from_raw_http(request: &str) -> RequestFromRawHttp {
     let method = match first_line.next().unwrap() {
         "GET" => FrontendRequestMethod::GET,
         "POST" => FrontendRequestMethod::POST,
+        "PUT" => FrontendRequestMethod::PUT,
+        "DELETE" => FrontendRequestMethod::DELETE,
         s => FrontendRequestMethod::OtherUnsupported(s.to_string()),
     };
     let query = match first_line.next().unwrap().split_once('?') {
         Some((_, query)) => serde_urlencoded::from_str(query).unwrap(),
-        None => HashMap::new(),
+        None => Vec::new(),
     };
 
     let mut headers = HashMap::new();
@@ -72,9 +109,14 @@ pub fn request_from_raw_http(request: &str) -> RequestFromRawHttp {
     }
 
     let body = request.map(|s| s.trim().to_string()).collect::<Vec<String>>().join("\n");
-    let body = serde_urlencoded::from_str(&body).unwrap();
+    let is_json = headers.get("Content-Type").is_some_and(|v| v.starts_with("application/json"));
+    let (body, json) = if is_json {
+        (Vec::new(), Some(serde_json::from_str(&body).unwrap()))
+    } else {
+        (serde_urlencoded::from_str(&body).unwrap(), None)
+    };
 
-    RequestFromRawHttp { method, query, headers, body }
+    RequestFromRawHttp { method, query, headers, body, json, peer_certificate: None }
 }
 
 #[test]
@@ -1,8 +1,16 @@
 use crate::{
+    assertion::JWT_BEARER_GRANT_TYPE,
     authorize::{AuthorizationRequest, ResponseType},
-    common::model::{Client, CodeChallenge, Grant},
+    common::model::{Client, CodeChallenge, Grant, TokenEndpointAuthMethod},
+    device::DEVICE_CODE_GRANT_TYPE,
+    exchange::TOKEN_EXCHANGE_GRANT_TYPE,
     manager::OAuthManager,
-    test::mock::{MockAuthorizationProvider, MockClientProvider, MockTokenProvider},
+    test::mock::{
+        MockAuthorizationProvider, MockClientProvider, MockClientRegistrationProvider,
+        MockDPoPProvider, MockDeviceAuthorizationProvider, MockGrantTypeHandler, MockJwsVerifier,
+        MockJwtBearerProvider, MockPushedAuthorizationProvider, MockTokenExchangeProvider,
+        MockTokenProvider,
+    },
     token::{GrantType, RefreshGrant, RequestedGrantType, Token, TokenRequest},
 };
 use mockall::predicate::{always, eq};
@@ -12,22 +20,44 @@ pub(crate) struct TestEnvironment {
     pub(crate) client_provider: MockClientProvider,
     pub(crate) authorization_provider: MockAuthorizationProvider,
     pub(crate) token_provider: MockTokenProvider,
+    pub(crate) device_authorization_provider: MockDeviceAuthorizationProvider,
+    pub(crate) pushed_authorization_provider: MockPushedAuthorizationProvider,
+    pub(crate) dpop_provider: MockDPoPProvider,
+    pub(crate) jws_verifier: MockJwsVerifier,
+    pub(crate) client_registration_provider: MockClientRegistrationProvider,
+    pub(crate) token_exchange_provider: MockTokenExchangeProvider,
+    pub(crate) jwt_bearer_provider: MockJwtBearerProvider,
+    pub(crate) grant_type_handler: MockGrantTypeHandler,
 }
 
 pub(crate) static DEFAULT_CLIENT_ID: &str = "client";
 pub(crate) static DEFAULT_CLIENT_SECRET: &str = "client_secret";
 pub(crate) static DEFAULT_REDIRECT_URI: &str = "https://example.com/return";
 pub(crate) static DEFAULT_AUTHORIZATION_CODE: &str = "authorization_code";
+pub(crate) static DEFAULT_CODE_ID: &str = "code_id";
 pub(crate) static DEFAULT_TOKEN: &str = "token";
 pub(crate) static DEFAULT_CODE_VERIFIER: &str = "code_verifier";
 pub(crate) static DEFAULT_REFRESH_TOKEN: &str = "refresh_token";
+pub(crate) static DEFAULT_REFRESH_TOKEN_FAMILY: &str = "refresh_token_family";
+pub(crate) static EXTENSION_GRANT_TYPE: &str = "urn:example:grant-type:sso-ticket";
 
 impl TestEnvironment {
     pub(crate) fn new() -> Self {
+        let mut jws_verifier = MockJwsVerifier::new();
+        jws_verifier.expect_supported_algorithms().returning(|| vec!["ES256"]);
+
         Self {
             client_provider: MockClientProvider::new(),
             authorization_provider: MockAuthorizationProvider::new(),
             token_provider: MockTokenProvider::new(),
+            device_authorization_provider: MockDeviceAuthorizationProvider::new(),
+            pushed_authorization_provider: MockPushedAuthorizationProvider::new(),
+            dpop_provider: MockDPoPProvider::new(),
+            jws_verifier,
+            client_registration_provider: MockClientRegistrationProvider::new(),
+            token_exchange_provider: MockTokenExchangeProvider::new(),
+            jwt_bearer_provider: MockJwtBearerProvider::new(),
+            grant_type_handler: MockGrantTypeHandler::new(),
         }
     }
 
@@ -36,6 +66,14 @@ impl TestEnvironment {
             .client_provider(self.client_provider)
             .authorization_provider(self.authorization_provider)
             .token_provider(self.token_provider)
+            .device_authorization_provider(self.device_authorization_provider)
+            .pushed_authorization_provider(self.pushed_authorization_provider)
+            .dpop_provider(self.dpop_provider)
+            .jws_verifier(self.jws_verifier)
+            .client_registration_provider(self.client_registration_provider)
+            .token_exchange_provider(self.token_exchange_provider)
+            .jwt_bearer_provider(self.jwt_bearer_provider)
+            .grant_type_handler(EXTENSION_GRANT_TYPE, self.grant_type_handler)
             .build()
     }
 
@@ -74,9 +112,12 @@ impl TestEnvironment {
                 eq(GrantType::AuthorizationCode {
                     resource_owner: grant.owner_id,
                     scope: grant.scope.clone(),
+                    code_id: grant.code_id.clone(),
                 }),
+                always(),
+                always(),
             )
-            .returning(move |_, _| {
+            .returning(move |_, _, _, _| {
                 Ok(Token {
                     token: DEFAULT_TOKEN.to_string(),
                     refresh_token: Some(DEFAULT_REFRESH_TOKEN.to_string()),
@@ -93,13 +134,18 @@ impl TestEnvironment {
                     client_id: refresh_grant_origin.client_id.clone(),
                     resource_owner: refresh_grant_origin.owner_id,
                     scope: refresh_grant_origin.scope.clone(),
+                    resource: refresh_grant_origin.resource.clone(),
+                    family_id: DEFAULT_REFRESH_TOKEN_FAMILY.to_string(),
+                    dpop_jkt: None,
                 }))
             });
+        self.token_provider.expect_mark_refresh_token_used().returning(|_| Ok(true));
 
         self.authorization_provider
             .expect_exchange_code_for_grant()
             .with(eq(code))
             .returning(move |_| Ok(Some(grant.clone())));
+        self.authorization_provider.expect_mark_code_used().returning(|_| Ok(true));
     }
 
     pub(crate) fn default_refresh_token(&mut self) {
@@ -116,6 +162,9 @@ impl TestEnvironment {
                     client_id: exchange_grant.client_id.clone(),
                     resource_owner: exchange_grant.owner_id,
                     scope: exchange_grant.scope.clone(),
+                    resource: exchange_grant.resource.clone(),
+                    family_id: DEFAULT_REFRESH_TOKEN_FAMILY.to_string(),
+                    dpop_jkt: None,
                 }))
             });
 
@@ -127,15 +176,22 @@ impl TestEnvironment {
                     client_id: grant.client_id.clone(),
                     resource_owner: grant.owner_id,
                     scope: grant.scope.clone(),
+                    resource: grant.resource.clone(),
+                    family_id: DEFAULT_REFRESH_TOKEN_FAMILY.to_string(),
+                    dpop_jkt: None,
                 })),
+                always(),
+                always(),
             )
-            .returning(move |_, _| {
+            .returning(move |_, _, _, _| {
                 Ok(Token {
                     token: DEFAULT_TOKEN.to_string(),
                     refresh_token: Some(refresh_token.clone()),
                     valid_until: Instant::now(),
                 })
             });
+
+        self.token_provider.expect_mark_refresh_token_used().returning(|_| Ok(true));
     }
 }
 
@@ -145,6 +201,23 @@ impl Default for Client {
             client_id: DEFAULT_CLIENT_ID.to_string(),
             redirect_uris: vec![DEFAULT_REDIRECT_URI.to_string()],
             confidential: false,
+            grant_types: [
+                "authorization_code",
+                "client_credentials",
+                "refresh_token",
+                DEVICE_CODE_GRANT_TYPE,
+                TOKEN_EXCHANGE_GRANT_TYPE,
+                JWT_BEARER_GRANT_TYPE,
+                EXTENSION_GRANT_TYPE,
+            ]
+            .map(str::to_string)
+            .to_vec(),
+            response_types: vec!["code".to_string()],
+            token_endpoint_auth_method: TokenEndpointAuthMethod::ClientSecretBasic,
+            default_scopes: Vec::new(),
+            access_token_lifetime: None,
+            refresh_token_lifetime: None,
+            display: Default::default(),
         }
     }
 }
@@ -159,7 +232,11 @@ impl Default for AuthorizationRequest {
             },
             redirect_uri: Some(DEFAULT_REDIRECT_URI.to_string()),
             scope: Some("scope".to_string()),
+            resource: Vec::new(),
             state: None,
+            dpop_jkt: None,
+            request_uri: None,
+            openid: Default::default(),
         }
     }
 }
@@ -169,12 +246,17 @@ impl Default for TokenRequest {
         Self {
             client_id: DEFAULT_CLIENT_ID.to_string(),
             client_secret: Some(DEFAULT_CLIENT_SECRET.to_string()),
+            client_secret_basic: true,
+            client_assertion: None,
+            client_certificate: None,
             grant_type: RequestedGrantType::AuthorizationCode {
                 code: DEFAULT_AUTHORIZATION_CODE.to_string(),
                 code_verifier: DEFAULT_CODE_VERIFIER.to_string(),
             },
             redirect_uri: Some(DEFAULT_REDIRECT_URI.to_string()),
             scope: Some(vec!["scope".to_string()]),
+            resource: Vec::new(),
+            dpop_proof: None,
         }
     }
 }
@@ -185,10 +267,15 @@ impl Default for Grant<u32> {
             owner_id: 1,
             client_id: DEFAULT_CLIENT_ID.to_string(),
             scope: vec!["scope".to_string()],
+            resource: Vec::new(),
             redirect_uri: DEFAULT_REDIRECT_URI.parse().unwrap(),
             code_challenge: CodeChallenge::Plain {
                 code_challenge: DEFAULT_CODE_VERIFIER.to_string(),
             },
+            code_id: DEFAULT_CODE_ID.to_string(),
+            dpop_jkt: None,
+            openid: Default::default(),
+            auth_time: None,
         }
     }
 }
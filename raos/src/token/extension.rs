@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use crate::{
+    common::{
+        frontend::{OAuthError, OAuthValidationError},
+        model::Client,
+    },
+    manager::OAuthManager,
+    token::GrantType,
+};
+
+/// Grant type handler trait.
+/// This is an optional trait that can be implemented by the end user, to support a custom grant type
+/// as described in RFC 6749 section 4.5.
+///
+/// A handler is registered for a single grant type URI through
+/// [grant_type_handler](crate::builder::OAuthManagerBuilder::grant_type_handler), and is used to exchange
+/// the parameters of a token request with that grant type for a resource owner and scope.
+#[async_trait]
+pub trait GrantTypeHandler: 'static + Send + Sync {
+    /// This is the type of the owner id that is used to identify the resource owner.
+    /// This type will need to match the OwnerId used in [AuthorizationProvider](crate::authorize::AuthorizationProvider).
+    type OwnerId;
+    /// This is the error type that can be returned by the grant type handler implementing this trait.
+    /// This type will need to match the Error used in [ClientProvider](crate::common::model::ClientProvider).
+    type Error;
+
+    /// Handle a token request with the grant type this handler is registered for.
+    ///
+    /// # Implementation notes
+    /// The client has already been authenticated, and is allowed to use the grant type.
+    /// The requested scope is passed as the `scope` parameter, and should be checked against the scopes
+    /// the client is allowed to request. The client credentials are not passed to the handler.
+    ///
+    /// # Arguments
+    /// * `client` - The authenticated client that sent the token request.
+    /// * `parameters` - The body parameters of the token request, including `grant_type`.
+    ///
+    /// # Returns
+    /// An [Option] containing the [ExtensionGrant] to issue a token for, or [None] if the grant is invalid.
+    ///
+    /// # Errors
+    /// If the handler fails to handle the grant, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn handle_grant(
+        &self,
+        client: &Client,
+        parameters: &HashMap<String, String>,
+    ) -> Result<Option<ExtensionGrant<Self::OwnerId>>, Self::Error>;
+}
+
+/// The grant a [GrantTypeHandler] returns for a valid token request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtensionGrant<OwnerId> {
+    /// The resource owner the token is issued for.
+    pub resource_owner: OwnerId,
+    /// The granted scope.
+    pub scope: Vec<String>,
+}
+
+impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
+    pub(crate) async fn validate_extension_grant(
+        &self,
+        client: &Client,
+        grant_type: String,
+        parameters: HashMap<String, String>,
+    ) -> Result<GrantType<U>, OAuthError<E>> {
+        let Some(handler) = self.grant_type_handlers.get(&grant_type) else {
+            return Err(OAuthValidationError::InvalidGrantType { requested: grant_type }.into());
+        };
+
+        let Some(grant) = handler
+            .handle_grant(client, &parameters)
+            .await
+            .map_err(OAuthError::ProviderImplementationError)?
+        else {
+            return Err(OAuthValidationError::InvalidExtensionGrant.into());
+        };
+
+        Ok(GrantType::Extension {
+            grant_type,
+            resource_owner: grant.resource_owner,
+            scope: grant.scope,
+        })
+    }
+}
@@ -1,8 +1,12 @@
 use std::time::Instant;
 
 use async_trait::async_trait;
+use serde::Serialize;
 
-use crate::{common::model::Client, token::GrantType};
+use crate::{
+    common::model::Client,
+    token::{GrantType, IssuedToken, TokenTypeHint},
+};
 
 /// Token provider trait.
 /// This is one of the traits that has to be implemented by the end user, for the oauth manager to work.
@@ -23,9 +27,24 @@ pub trait TokenProvider: 'static + Send + Sync {
     /// It is recommended to ensure that the grant is fully recovered from the token, so that the token can be verified later.
     /// This can be done by either storing the grant in the token (e.g. JWT), or by storing the grant in a database and storing the id in the token.
     ///
+    /// Refresh tokens belong to a family, which starts with the first refresh token issued for an authorization.
+    /// When the grant is a [GrantType::RefreshToken], a new refresh token MUST be issued in the same family as
+    /// [RefreshGrant::family_id] for public clients, as their previous refresh token can no longer be used.
+    /// For any other grant, a refresh token starts a new family.
+    ///
+    /// If the confirmation contains a key, the access token MUST be bound to it, for example through the `cnf` claim
+    /// of a JWT. Refresh tokens issued to public clients MUST be bound to the same key, and it should be returned
+    /// in [RefreshGrant::dpop_jkt] when the refresh token is exchanged.
+    ///
+    /// If the audience is not empty, the access token MUST be restricted to those resources, for example through
+    /// the `aud` claim of a JWT. The audience should be stored with the refresh token, and returned in
+    /// [RefreshGrant::resource] when the refresh token is exchanged.
+    ///
     /// # Arguments
     /// * `client` - The client to generate the token for.
     /// * `grant` - The grant to generate the token for.
+    /// * `confirmation` - The key the token should be bound to, if the client proved possession of one.
+    /// * `audience` - The resources the token is restricted to, as requested through the `resource` parameter of RFC 8707.
     ///
     /// # Returns
     /// A [Token] that contains the access token, a refresh token if this behaviour is supported and the instant at which the access token expires.
@@ -37,11 +56,15 @@ pub trait TokenProvider: 'static + Send + Sync {
         &self,
         client: &Client,
         grant: GrantType<Self::OwnerId>,
+        confirmation: TokenConfirmation,
+        audience: &[String],
     ) -> Result<Token, Self::Error>;
 
     /// Exchange a refresh token for a new token.
     ///
     /// # Implementation notes
+    /// Refresh tokens that were already used MUST still be returned, as long as their family was not revoked,
+    /// so that a replayed refresh token can be detected through [mark_refresh_token_used](TokenProvider::mark_refresh_token_used).
     ///
     /// # Arguments
     /// * `refresh_token` - The refresh token to exchange.
@@ -56,6 +79,94 @@ pub trait TokenProvider: 'static + Send + Sync {
         &self,
         refresh_token: String,
     ) -> Result<Option<RefreshGrant<Self::OwnerId>>, Self::Error>;
+
+    /// Mark a refresh token as used, as part of refresh token rotation for public clients.
+    ///
+    /// # Implementation notes
+    /// Checking and marking the refresh token MUST happen atomically, so that two concurrent requests
+    /// cannot both use the same refresh token.
+    /// When this function returns `false`, the library will revoke the family through
+    /// [revoke_refresh_token_family](TokenProvider::revoke_refresh_token_family).
+    ///
+    /// # Arguments
+    /// * `refresh_token` - The refresh token that is being exchanged.
+    ///
+    /// # Returns
+    /// `true` if this is the first use of the refresh token, or `false` if the refresh token was already used.
+    ///
+    /// # Errors
+    /// If the token provider fails to mark the refresh token, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn mark_refresh_token_used(&self, refresh_token: &str) -> Result<bool, Self::Error>;
+
+    /// Revoke all refresh tokens in a family, and the access tokens issued through them.
+    /// This is called when a refresh token is used more than once, which indicates that it was stolen.
+    ///
+    /// # Arguments
+    /// * `family_id` - The family id of the replayed refresh token, as returned in [RefreshGrant::family_id].
+    ///
+    /// # Errors
+    /// If the token provider fails to revoke the family, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn revoke_refresh_token_family(&self, family_id: &str) -> Result<(), Self::Error>;
+
+    /// Revoke all access tokens and refresh tokens that were issued from an authorization code.
+    /// This is called when an authorization code is used more than once, which indicates that it was stolen.
+    ///
+    /// # Arguments
+    /// * `code_id` - The identifier of the replayed code, as passed in [GrantType::AuthorizationCode] when the tokens were issued.
+    ///
+    /// # Errors
+    /// If the token provider fails to revoke the tokens, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn revoke_code_tokens(&self, code_id: &str) -> Result<(), Self::Error>;
+
+    /// Look up an issued access token or refresh token, recovering the grant it was issued for.
+    ///
+    /// # Implementation notes
+    /// This function is used to introspect tokens on behalf of protected resources.
+    /// Tokens that are unknown or revoked should return [None].
+    /// Expired tokens may be returned, as the library will check the expiry itself.
+    /// The confirmation the token was issued with must be returned as well, so bound access tokens
+    /// are only accepted along with proof of possession of their key.
+    ///
+    /// # Arguments
+    /// * `token` - The token to look up.
+    /// * `token_type_hint` - An optional hint about the type of the token, which can be used to optimize the lookup.
+    ///
+    /// # Returns
+    /// An [Option] containing the [IssuedToken] if the token was found, or [None] if the token is unknown or revoked.
+    ///
+    /// # Errors
+    /// If the token provider fails to look up the token, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn lookup_token(
+        &self,
+        token: String,
+        token_type_hint: Option<TokenTypeHint>,
+    ) -> Result<Option<IssuedToken<Self::OwnerId>>, Self::Error>;
+
+    /// Revoke an access token or refresh token.
+    ///
+    /// # Implementation notes
+    /// The token MUST only be revoked if it was issued to the given client.
+    /// If the token is a refresh token, any access tokens issued from the same grant SHOULD be revoked as well.
+    /// Unknown or already revoked tokens should not result in an error, as the client cannot act on it.
+    ///
+    /// # Arguments
+    /// * `client` - The authenticated client that requested the revocation.
+    /// * `token` - The token to revoke.
+    /// * `token_type_hint` - An optional hint about the type of the token, which can be used to optimize the lookup.
+    ///
+    /// # Errors
+    /// If the token provider fails to revoke the token, through whatever error.
+    /// This error will later be returned through [OAuthError::ProviderImplementationError](crate::common::frontend::OAuthError::ProviderImplementationError).
+    async fn revoke_token(
+        &self,
+        client: &Client,
+        token: String,
+        token_type_hint: Option<TokenTypeHint>,
+    ) -> Result<(), Self::Error>;
 }
 
 /// A token returned by the [TokenProvider].
@@ -77,4 +188,31 @@ pub struct RefreshGrant<OwnerId> {
     pub resource_owner: OwnerId,
     /// The requested scope.
     pub scope: Vec<String>,
+    /// The resources the refresh token was issued for, as passed to [TokenProvider::token] in the `audience`.
+    /// Refreshed tokens are restricted to these resources, unless the client narrows them down.
+    pub resource: Vec<String>,
+    /// The family the refresh token belongs to, shared by all refresh tokens rotated from the same authorization.
+    pub family_id: String,
+    /// The JWK thumbprint of the DPoP key the refresh token is bound to, if any.
+    /// Refreshing a bound refresh token requires a DPoP proof signed by the same key.
+    pub dpop_jkt: Option<String>,
+}
+
+/// The key an issued token is bound to, modelled after the `cnf` (confirmation) claim of RFC 7800.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TokenConfirmation {
+    /// The JWK SHA-256 thumbprint of the DPoP key the client proved possession of, as described in RFC 9449.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jkt: Option<String>,
+    /// The SHA-256 thumbprint of the certificate the client presented for mutual TLS, as described in RFC 8705.
+    /// This is serialized as `x5t#S256` in the confirmation claim.
+    #[serde(rename = "x5t#S256", skip_serializing_if = "Option::is_none")]
+    pub x5t_s256: Option<String>,
+}
+
+impl TokenConfirmation {
+    /// Whether the token is not bound to any key.
+    pub fn is_empty(&self) -> bool {
+        self.jkt.is_none() && self.x5t_s256.is_none()
+    }
 }
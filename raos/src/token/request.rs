@@ -1,9 +1,14 @@
-use base64::{prelude::BASE64_STANDARD, Engine};
-
-use crate::common::{
-    frontend::{FrontendRequest, FrontendRequestMethod, OAuthValidationError},
-    syntax::{ValidateSyntax, CLIENT_ID_SYNTAX},
-    util::NoneIfEmpty,
+use std::collections::HashMap;
+
+use crate::{
+    assertion::JWT_BEARER_GRANT_TYPE,
+    common::{
+        auth::{client_credentials_from_request, ClientCredentials},
+        frontend::{FrontendRequest, FrontendRequestMethod, OAuthValidationError},
+        util::NoneIfEmpty,
+    },
+    device::DEVICE_CODE_GRANT_TYPE,
+    exchange::{TokenExchangeParameters, TOKEN_EXCHANGE_GRANT_TYPE},
 };
 
 /// A parsed request to exchange an authorization code, refresh code or client credentials for an access token.
@@ -13,12 +18,22 @@ pub struct TokenRequest {
     pub client_id: String,
     /// The client secret.
     pub client_secret: Option<String>,
+    /// Whether the client secret was sent using HTTP Basic authentication, instead of in the request body.
+    pub client_secret_basic: bool,
+    /// The client assertion, a JWT used to authenticate the client as described in RFC 7523.
+    pub client_assertion: Option<String>,
+    /// The DER encoded certificate the client presented for mutual TLS, as described in RFC 8705.
+    pub client_certificate: Option<Vec<u8>>,
     /// The type of grant requested by the client.
     pub grant_type: RequestedGrantType,
     /// The redirect_uri that is repeated in the token request, for compatibility with OAuth 2.0.
     pub redirect_uri: Option<String>,
     /// The requested scope, used when refreshing a token using the refresh token grant type.
     pub scope: Option<Vec<String>>,
+    /// The resources the issued token should be restricted to, as described in RFC 8707.
+    pub resource: Vec<String>,
+    /// The DPoP proof sent in the `DPoP` header, used to bind the issued tokens to the key of the client.
+    pub dpop_proof: Option<String>,
 }
 
 /// The type of grant requested by the client.
@@ -38,6 +53,41 @@ pub enum RequestedGrantType {
         /// The refresh token.
         refresh_token: String,
     },
+    /// The device is polling for an access token using a device code.
+    DeviceCode {
+        /// The device code.
+        device_code: String,
+    },
+    /// The client is requesting a token in exchange for another security token.
+    TokenExchange(Box<TokenExchangeParameters>),
+    /// The client is requesting an access token using a JWT assertion.
+    JwtBearer {
+        /// The JWT assertion, signed by a trusted issuer.
+        assertion: String,
+    },
+    /// The client is requesting an access token using a custom grant type,
+    /// which is handled by the [GrantTypeHandler](crate::token::GrantTypeHandler) registered for it.
+    Extension {
+        /// The URI of the custom grant type.
+        grant_type: String,
+        /// The body parameters of the request, without the client credentials.
+        parameters: HashMap<String, String>,
+    },
+}
+
+impl RequestedGrantType {
+    /// The value of the `grant_type` parameter this grant type is requested with.
+    pub fn as_str(&self) -> &str {
+        match self {
+            RequestedGrantType::ClientCredentials => "client_credentials",
+            RequestedGrantType::AuthorizationCode { .. } => "authorization_code",
+            RequestedGrantType::RefreshToken { .. } => "refresh_token",
+            RequestedGrantType::DeviceCode { .. } => DEVICE_CODE_GRANT_TYPE,
+            RequestedGrantType::TokenExchange(_) => TOKEN_EXCHANGE_GRANT_TYPE,
+            RequestedGrantType::JwtBearer { .. } => JWT_BEARER_GRANT_TYPE,
+            RequestedGrantType::Extension { grant_type, .. } => grant_type,
+        }
+    }
 }
 
 impl TryFrom<&dyn FrontendRequest> for TokenRequest {
@@ -54,28 +104,13 @@ impl TryFrom<&dyn FrontendRequest> for TokenRequest {
         // We should treat empty values as if they were omitted from the request
         let body_param = |key| request.body_param(key).none_if_empty();
 
-        let header_credentials = get_credentials_from_header(request)?;
-
-        let (mut client_id, mut client_secret) = header_credentials.unzip();
-        if let Some(body_client_id) = body_param("client_id") {
-            if let Some(header_client_id) = client_id {
-                if body_client_id != header_client_id {
-                    return Err(OAuthValidationError::MismatchedClientCredentials);
-                }
-            }
-
-            client_id = Some(body_client_id);
-        };
-        if let Some(body_client_secret) = body_param("client_secret") {
-            if client_secret.is_none() {
-                client_secret = Some(body_client_secret);
-            }
-        };
-
-        let Some(client_id) = client_id else {
-            return Err(OAuthValidationError::MissingRequiredParameter("client_id"));
-        };
-        client_id.validate_syntax("client_id", &CLIENT_ID_SYNTAX)?;
+        let ClientCredentials {
+            client_id,
+            client_secret,
+            client_secret_basic,
+            client_assertion,
+            client_certificate,
+        } = client_credentials_from_request(request)?;
 
         let Some(grant_type_str) = body_param("grant_type") else {
             return Err(OAuthValidationError::MissingRequiredParameter("grant_type"));
@@ -105,10 +140,27 @@ impl TryFrom<&dyn FrontendRequest> for TokenRequest {
                 };
                 RequestedGrantType::RefreshToken { refresh_token }
             }
+            DEVICE_CODE_GRANT_TYPE => {
+                let Some(device_code) = body_param("device_code") else {
+                    return Err(OAuthValidationError::MissingRequiredParameter("device_code"));
+                };
+                RequestedGrantType::DeviceCode { device_code }
+            }
+            TOKEN_EXCHANGE_GRANT_TYPE => RequestedGrantType::TokenExchange(Box::new(
+                TokenExchangeParameters::from_request(request)?,
+            )),
+            JWT_BEARER_GRANT_TYPE => {
+                let Some(assertion) = body_param("assertion") else {
+                    return Err(OAuthValidationError::MissingRequiredParameter("assertion"));
+                };
+                RequestedGrantType::JwtBearer { assertion }
+            }
+            // Whether a handler is registered for the grant type is checked during validation
             _ => {
-                return Err(OAuthValidationError::InvalidGrantType {
-                    requested: grant_type_str.to_string(),
-                });
+                let mut parameters = request.body_params();
+                parameters.remove("client_secret");
+                parameters.remove("client_assertion");
+                RequestedGrantType::Extension { grant_type: grant_type_str, parameters }
             }
         };
 
@@ -117,48 +169,40 @@ impl TryFrom<&dyn FrontendRequest> for TokenRequest {
         Ok(TokenRequest {
             client_id,
             client_secret,
+            client_secret_basic,
+            client_assertion,
+            client_certificate,
             grant_type,
             scope,
+            // We should treat empty values as if they were omitted from the request
+            resource: request
+                .body_param_values("resource")
+                .into_iter()
+                .filter(|resource| !resource.is_empty())
+                .collect(),
             redirect_uri: body_param("redirect_uri"),
+            dpop_proof: request.header_param("dpop").none_if_empty(),
         })
     }
 }
 
-fn get_credentials_from_header(
-    request: &dyn FrontendRequest,
-) -> Result<Option<(String, String)>, OAuthValidationError> {
-    static MASKED: &str = "<masked>";
+/// A hint about the type of token submitted for revocation or introspection.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenTypeHint {
+    /// The token is an access token.
+    AccessToken,
+    /// The token is a refresh token.
+    RefreshToken,
+}
 
-    if let Some(authorization_header) = request.header_param("authorization") {
-        // Parse the authorization header
-        let parts: Vec<&str> = authorization_header.split_whitespace().collect();
-        if parts.len() != 2 {
-            return Err(OAuthValidationError::InvalidParameterValue(
-                "authorization",
-                MASKED.to_string(),
-            ));
+impl TokenTypeHint {
+    /// Parse a token_type_hint parameter.
+    /// Unknown hints are ignored, as the server is expected to extend its search across all supported token types.
+    pub(crate) fn from_param(value: Option<String>) -> Option<Self> {
+        match value?.as_str() {
+            "access_token" => Some(Self::AccessToken),
+            "refresh_token" => Some(Self::RefreshToken),
+            _ => None,
         }
-        if parts[0].to_lowercase() != "Basic" {
-            return Err(OAuthValidationError::InvalidParameterValue(
-                "authorization",
-                MASKED.to_string(),
-            ));
-        }
-        let decoded = BASE64_STANDARD.decode(parts[1]).map_err(|_| {
-            OAuthValidationError::InvalidParameterValue("authorization", MASKED.to_string())
-        })?;
-        let decoded_str = std::str::from_utf8(&decoded).map_err(|_| {
-            OAuthValidationError::InvalidParameterValue("authorization", MASKED.to_string())
-        })?;
-        let parts: Vec<&str> = decoded_str.split(':').collect();
-        if parts.len() != 2 {
-            return Err(OAuthValidationError::InvalidParameterValue(
-                "authorization",
-                MASKED.to_string(),
-            ));
-        }
-        return Ok(Some((parts[0].to_string(), parts[1].to_string())));
     }
-
-    Ok(None)
 }
@@ -0,0 +1,369 @@
+use crate::{
+    common::{
+        frontend::{FrontendRequest, OAuthError, OAuthValidationError},
+        jose::{hmac::sign_hmac, Jwk},
+        model::{Client, ClientAssertionKeys, TokenEndpointAuthMethod},
+    },
+    manager::OAuthManager,
+    test::{
+        mock::request_from_raw_http, TestEnvironment, DEFAULT_CLIENT_ID, DEFAULT_CLIENT_SECRET,
+        DEFAULT_TOKEN,
+    },
+    token::TokenRequest,
+};
+use base64::{prelude::BASE64_URL_SAFE_NO_PAD, Engine};
+use mockall::predicate::{always, eq};
+use serde_json::{json, Value};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+static SHARED_SECRET: &str = "a shared secret of at least thirty-two bytes";
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
+
+fn assertion_claims() -> Value {
+    json!({
+        "iss": DEFAULT_CLIENT_ID,
+        "sub": DEFAULT_CLIENT_ID,
+        "aud": "https://example.com/token",
+        "exp": now() + 60,
+        "jti": "assertion_id"
+    })
+}
+
+fn encode(header: Value, claims: Value) -> String {
+    let encode = |value: Value| BASE64_URL_SAFE_NO_PAD.encode(value.to_string());
+    format!("{}.{}", encode(header), encode(claims))
+}
+
+/// Create a client_secret_jwt assertion, signed with the given secret.
+fn hmac_assertion(secret: &str, claims: Value) -> String {
+    let signing_input = encode(json!({ "alg": "HS256" }), claims);
+    let signature = sign_hmac("HS256", secret.as_bytes(), signing_input.as_bytes()).unwrap();
+    format!("{signing_input}.{}", BASE64_URL_SAFE_NO_PAD.encode(signature))
+}
+
+fn assertion_request(client_assertion: String) -> TokenRequest {
+    TokenRequest {
+        client_secret: None,
+        client_assertion: Some(client_assertion),
+        ..Default::default()
+    }
+}
+
+/// A test environment where the default client authenticates using client_secret_jwt.
+fn assertion_environment() -> TestEnvironment {
+    let mut test = TestEnvironment::new();
+    test.register_client(
+        Client {
+            token_endpoint_auth_method: TokenEndpointAuthMethod::ClientSecretJwt,
+            ..Default::default()
+        },
+        DEFAULT_CLIENT_SECRET.to_string(),
+    );
+    test.default_grant();
+    test.client_provider
+        .expect_get_client_assertion_keys()
+        .returning(|_| Ok(Some(ClientAssertionKeys::Secret(SHARED_SECRET.to_string()))));
+    test
+}
+
+fn build(test: TestEnvironment) -> OAuthManager<u32, (), ()> {
+    let mut manager = test.build();
+    manager.config.authorization_server_identifier = Some("https://example.com".to_string());
+    manager
+}
+
+#[test]
+fn test_client_assertion_request_identifies_client() {
+    // The "client_id" is unnecessary for client assertion authentication because the client is identified by the subject of the JWT.
+
+    // Arrange
+    let request = request_from_raw_http(&format!(
+        r#"
+            POST /token HTTP/1.1
+            Content-Type: application/x-www-form-urlencoded
+
+            grant_type=client_credentials&client_assertion_type=urn%3Aietf%3Aparams%3Aoauth%3Aclient-assertion-type%3Ajwt-bearer&client_assertion={}
+        "#,
+        hmac_assertion(SHARED_SECRET, assertion_claims())
+    ));
+
+    // Act
+    let result = TokenRequest::try_from(&request as &dyn FrontendRequest);
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    let result = result.unwrap();
+    assert_eq!(DEFAULT_CLIENT_ID, result.client_id);
+    assert_eq!(None, result.client_secret);
+    assert!(result.client_assertion.is_some());
+}
+
+#[test]
+fn test_client_assertion_request_rejects_unknown_assertion_type() {
+    // Arrange
+    let request = request_from_raw_http(&format!(
+        r#"
+            POST /token HTTP/1.1
+            Content-Type: application/x-www-form-urlencoded
+
+            grant_type=client_credentials&client_assertion_type=urn%3Aexample&client_assertion={}
+        "#,
+        hmac_assertion(SHARED_SECRET, assertion_claims())
+    ));
+
+    // Act
+    let result = TokenRequest::try_from(&request as &dyn FrontendRequest);
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthValidationError::InvalidParameterValue(
+            "client_assertion_type",
+            "urn:example".to_string()
+        ),
+        result.unwrap_err()
+    );
+}
+
+#[test]
+fn test_client_assertion_request_rejects_multiple_methods() {
+    // The client MUST NOT use more than one authentication method in each request.
+
+    // Arrange
+    let request = request_from_raw_http(&format!(
+        r#"
+            POST /token HTTP/1.1
+            Content-Type: application/x-www-form-urlencoded
+
+            grant_type=client_credentials&client_secret=secret&client_assertion_type=urn%3Aietf%3Aparams%3Aoauth%3Aclient-assertion-type%3Ajwt-bearer&client_assertion={}
+        "#,
+        hmac_assertion(SHARED_SECRET, assertion_claims())
+    ));
+
+    // Act
+    let result = TokenRequest::try_from(&request as &dyn FrontendRequest);
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(OAuthValidationError::MultipleClientAuthenticationMethods, result.unwrap_err());
+}
+
+#[tokio::test]
+async fn test_client_secret_jwt_authenticates_client() {
+    // Arrange
+    let mut test = assertion_environment();
+    test.client_provider
+        .expect_register_client_assertion_id()
+        .with(always(), eq("assertion_id"), always())
+        .times(1)
+        .returning(|_, _, _| Ok(true));
+    test.client_provider.expect_verify_client_secret().never();
+    let manager = build(test);
+
+    let request = assertion_request(hmac_assertion(SHARED_SECRET, assertion_claims()));
+
+    // Act
+    let result = manager.handle_token(request).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    assert_eq!(DEFAULT_TOKEN, result.unwrap().access_token);
+}
+
+#[tokio::test]
+async fn test_client_assertion_with_invalid_signature_is_rejected() {
+    // The JWT MUST be digitally signed or have a Message Authentication Code applied by the issuer.
+
+    // Arrange
+    let mut test = assertion_environment();
+    test.client_provider.expect_register_client_assertion_id().never();
+    let manager = build(test);
+
+    let request = assertion_request(hmac_assertion("another secret", assertion_claims()));
+
+    // Act
+    let result = manager.handle_token(request).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::InvalidClientAssertion(
+            "the signature is invalid"
+        )),
+        result.unwrap_err()
+    );
+}
+
+#[tokio::test]
+async fn test_client_assertion_must_identify_authorization_server() {
+    // The JWT MUST contain an "aud" (audience) claim containing a value that identifies the authorization server as an intended audience.
+
+    // Arrange
+    let manager = build(assertion_environment());
+
+    let mut claims = assertion_claims();
+    claims["aud"] = json!(["https://other.example.com"]);
+    let request = assertion_request(hmac_assertion(SHARED_SECRET, claims));
+
+    // Act
+    let result = manager.handle_token(request).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::InvalidClientAssertion(
+            "the audience does not identify this authorization server"
+        )),
+        result.unwrap_err()
+    );
+}
+
+#[tokio::test]
+async fn test_client_assertion_subject_must_be_client() {
+    // For client authentication, the subject MUST be the "client_id" of the OAuth client.
+
+    // Arrange
+    let manager = build(assertion_environment());
+
+    let mut claims = assertion_claims();
+    claims["iss"] = json!("other_client");
+    let request = assertion_request(hmac_assertion(SHARED_SECRET, claims));
+
+    // Act
+    let result = manager.handle_token(request).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::InvalidClientAssertion(
+            "the issuer and subject must be the client id"
+        )),
+        result.unwrap_err()
+    );
+}
+
+#[tokio::test]
+async fn test_expired_client_assertion_is_rejected() {
+    // The authorization server MUST reject any JWT with an expiration time that has passed.
+
+    // Arrange
+    let manager = build(assertion_environment());
+
+    let mut claims = assertion_claims();
+    claims["exp"] = json!(now() - 1);
+    let request = assertion_request(hmac_assertion(SHARED_SECRET, claims));
+
+    // Act
+    let result = manager.handle_token(request).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::InvalidClientAssertion(
+            "the assertion has expired"
+        )),
+        result.unwrap_err()
+    );
+}
+
+#[tokio::test]
+async fn test_replayed_client_assertion_is_rejected() {
+    // The authorization server MAY ensure that JWTs are not replayed by maintaining the set of used "jti" values.
+
+    // Arrange
+    let mut test = assertion_environment();
+    let exp = now() + 60;
+    test.client_provider
+        .expect_register_client_assertion_id()
+        .with(always(), eq("assertion_id"), eq(UNIX_EPOCH + Duration::from_secs(exp)))
+        .returning(|_, _, _| Ok(false));
+    let manager = build(test);
+
+    let mut claims = assertion_claims();
+    claims["exp"] = json!(exp);
+    let request = assertion_request(hmac_assertion(SHARED_SECRET, claims));
+
+    // Act
+    let result = manager.handle_token(request).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::InvalidClientAssertion(
+            "the assertion was already used"
+        )),
+        result.unwrap_err()
+    );
+}
+
+#[tokio::test]
+async fn test_client_without_assertion_keys_is_rejected() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.client_provider.expect_get_client_assertion_keys().returning(|_| Ok(None));
+    let manager = build(test);
+
+    let request = assertion_request(hmac_assertion(SHARED_SECRET, assertion_claims()));
+
+    // Act
+    let result = manager.handle_token(request).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::InvalidClientAssertion(
+            "the client does not authenticate using assertions"
+        )),
+        result.unwrap_err()
+    );
+}
+
+#[tokio::test]
+async fn test_private_key_jwt_uses_key_matching_kid() {
+    // Arrange
+    let key = |kid: &str| -> Jwk {
+        serde_json::from_value(
+            json!({ "kty": "EC", "kid": kid, "crv": "P-256", "x": "x", "y": "y" }),
+        )
+        .unwrap()
+    };
+
+    let mut test = TestEnvironment::new();
+    test.register_client(
+        Client {
+            token_endpoint_auth_method: TokenEndpointAuthMethod::PrivateKeyJwt,
+            ..Default::default()
+        },
+        DEFAULT_CLIENT_SECRET.to_string(),
+    );
+    test.default_grant();
+    test.client_provider
+        .expect_get_client_assertion_keys()
+        .returning(move |_| Ok(Some(ClientAssertionKeys::Jwks(vec![key("old"), key("new")]))));
+    test.client_provider.expect_register_client_assertion_id().returning(|_, _, _| Ok(true));
+    test.jws_verifier
+        .expect_verify()
+        .withf(|alg, key, _, signature| {
+            alg == "ES256" && key.kid.as_deref() == Some("new") && signature == b"signature"
+        })
+        .times(1)
+        .returning(|_, _, _, _| true);
+    let manager = build(test);
+
+    let assertion = format!(
+        "{}.{}",
+        encode(json!({ "alg": "ES256", "kid": "new" }), assertion_claims()),
+        BASE64_URL_SAFE_NO_PAD.encode("signature")
+    );
+    let request = assertion_request(assertion);
+
+    // Act
+    let result = manager.handle_token(request).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+}
@@ -0,0 +1,181 @@
+use mockall::predicate::{always, eq};
+use std::{collections::HashMap, time::Instant};
+
+use crate::{
+    common::{
+        frontend::{FrontendRequest, OAuthError, OAuthValidationError, PublicOAuthError},
+        model::Client,
+    },
+    test::{
+        mock::request_from_raw_http, TestEnvironment, DEFAULT_CLIENT_SECRET, DEFAULT_TOKEN,
+        EXTENSION_GRANT_TYPE,
+    },
+    token::{ExtensionGrant, GrantType, RequestedGrantType, Token, TokenRequest},
+};
+
+fn extension_request(grant_type: &str) -> TokenRequest {
+    TokenRequest {
+        grant_type: RequestedGrantType::Extension {
+            grant_type: grant_type.to_string(),
+            parameters: HashMap::from([
+                ("grant_type".to_string(), grant_type.to_string()),
+                ("ticket".to_string(), "ticket".to_string()),
+            ]),
+        },
+        redirect_uri: None,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_unknown_grant_type_parsed_as_extension() {
+    // Arrange
+    let request = request_from_raw_http(
+        r#"
+        POST /token HTTP/1.1
+        Content-Type: application/x-www-form-urlencoded
+
+        grant_type=urn%3Aexample%3Agrant-type%3Asso-ticket&client_id=client&client_secret=secret&ticket=ticket
+    "#,
+    );
+
+    // Act
+    let result = TokenRequest::try_from(&request as &dyn FrontendRequest);
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    let RequestedGrantType::Extension { grant_type, parameters } = result.unwrap().grant_type
+    else {
+        panic!("grant type is not an extension grant type");
+    };
+    assert_eq!(EXTENSION_GRANT_TYPE, grant_type);
+    assert_eq!(Some(&"ticket".to_string()), parameters.get("ticket"));
+    assert_eq!(Some(&"client".to_string()), parameters.get("client_id"));
+    assert!(!parameters.contains_key("client_secret"), "client secret is passed to the handler");
+}
+
+#[tokio::test]
+async fn test_unregistered_grant_type_is_unsupported() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.register_client(
+        Client {
+            grant_types: vec!["urn:example:grant-type:unknown".to_string()],
+            ..Default::default()
+        },
+        DEFAULT_CLIENT_SECRET.to_string(),
+    );
+    test.grant_type_handler.expect_handle_grant().never();
+    let manager = test.build();
+
+    // Act
+    let result =
+        manager.validate_token_request(extension_request("urn:example:grant-type:unknown")).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    let error = result.unwrap_err();
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::InvalidGrantType {
+            requested: "urn:example:grant-type:unknown".to_string()
+        }),
+        error
+    );
+    assert_eq!(PublicOAuthError::UnsupportedGrantType, PublicOAuthError::from(error));
+}
+
+#[tokio::test]
+async fn test_extension_grant_issues_token_from_handler() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.grant_type_handler
+        .expect_handle_grant()
+        .withf(|client, parameters| {
+            client.client_id == "client"
+                && parameters.get("ticket").map(String::as_str) == Some("ticket")
+        })
+        .times(1)
+        .returning(|_, _| {
+            Ok(Some(ExtensionGrant { resource_owner: 1, scope: vec!["read".to_string()] }))
+        });
+    test.token_provider
+        .expect_token()
+        .with(
+            always(),
+            eq(GrantType::Extension {
+                grant_type: EXTENSION_GRANT_TYPE.to_string(),
+                resource_owner: 1,
+                scope: vec!["read".to_string()],
+            }),
+            always(),
+            always(),
+        )
+        .times(1)
+        .returning(|_, _, _, _| {
+            Ok(Token {
+                token: DEFAULT_TOKEN.to_string(),
+                refresh_token: None,
+                valid_until: Instant::now(),
+            })
+        });
+    let manager = test.build();
+
+    // Act
+    let result = manager.handle_token(extension_request(EXTENSION_GRANT_TYPE)).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    assert_eq!(Some("read".to_string()), result.unwrap().scope);
+}
+
+#[tokio::test]
+async fn test_extension_grant_rejected_by_handler() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.grant_type_handler.expect_handle_grant().returning(|_, _| Ok(None));
+    let manager = test.build();
+
+    // Act
+    let result = manager.validate_token_request(extension_request(EXTENSION_GRANT_TYPE)).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    let error = result.unwrap_err();
+    assert_eq!(OAuthError::ValidationFailed(OAuthValidationError::InvalidExtensionGrant), error);
+    assert_eq!(PublicOAuthError::InvalidGrant, PublicOAuthError::from(error));
+}
+
+#[tokio::test]
+async fn test_extension_grant_requires_client_grant_type() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.register_client(
+        Client { grant_types: vec!["authorization_code".to_string()], ..Default::default() },
+        DEFAULT_CLIENT_SECRET.to_string(),
+    );
+    test.grant_type_handler.expect_handle_grant().never();
+    let manager = test.build();
+
+    // Act
+    let result = manager.validate_token_request(extension_request(EXTENSION_GRANT_TYPE)).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(PublicOAuthError::UnauthorizedClient, PublicOAuthError::from(result.unwrap_err()));
+}
+
+#[test]
+fn test_metadata_lists_extension_grant_types() {
+    // Arrange
+    let mut manager = TestEnvironment::new().build();
+    manager.config.authorization_server_identifier = Some("https://example.com/".to_string());
+
+    // Act
+    let result = manager.authorization_server_metadata();
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    assert!(result.unwrap().grant_types_supported.contains(&EXTENSION_GRANT_TYPE.to_string()));
+}
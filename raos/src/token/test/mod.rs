@@ -1,19 +1,26 @@
+mod client_assertion;
+mod extension;
+mod introspect;
+mod mutual_tls;
 mod refresh_tokens;
 mod request;
+mod resource;
 mod response;
+mod revoke;
 mod validate;
 
 use crate::{
     common::{
-        frontend::{OAuthError, OAuthValidationError},
+        frontend::{OAuthError, OAuthValidationError, PublicOAuthError},
         model::{Client, CodeChallenge, Grant},
     },
     test::{
-        TestEnvironment, DEFAULT_AUTHORIZATION_CODE, DEFAULT_CLIENT_SECRET, DEFAULT_CODE_VERIFIER,
-        DEFAULT_TOKEN,
+        TestEnvironment, DEFAULT_AUTHORIZATION_CODE, DEFAULT_CLIENT_SECRET, DEFAULT_CODE_ID,
+        DEFAULT_CODE_VERIFIER, DEFAULT_TOKEN,
     },
     token::{RequestedGrantType, Token, TokenRequest},
 };
+use mockall::predicate::eq;
 use std::time::Instant;
 
 #[tokio::test]
@@ -82,7 +89,8 @@ async fn test_authorization_code_invalid() {
             Ok(None)
         }
     });
-    test.token_provider.expect_token().times(1).returning(|_, _| {
+    test.authorization_provider.expect_mark_code_used().returning(|_| Ok(true));
+    test.token_provider.expect_token().times(1).returning(|_, _, _, _| {
         Ok(Token { token: "".to_string(), refresh_token: None, valid_until: Instant::now() })
     });
     let manager = test.build();
@@ -109,10 +117,9 @@ async fn test_authorization_code_invalid() {
     // Assert
     assert!(result_valid.is_ok(), "result is not Ok, result is {:?}", result_valid);
     assert!(result_invalid.is_err(), "result is not Err, result is {:?}", result_invalid);
-    assert_eq!(
-        OAuthError::ValidationFailed(OAuthValidationError::InvalidAuthorizationCode),
-        result_invalid.unwrap_err()
-    );
+    let error = result_invalid.unwrap_err();
+    assert_eq!(OAuthError::ValidationFailed(OAuthValidationError::InvalidAuthorizationCode), error);
+    assert_eq!(PublicOAuthError::InvalidGrant, PublicOAuthError::from(error));
 }
 
 #[tokio::test]
@@ -217,7 +224,61 @@ async fn test_server_verifies_client_credentials_if_included() {
     // Assert
     assert!(result.is_err(), "result is not Err, result is {:?}", result);
     assert_eq!(
-        OAuthError::ValidationFailed(OAuthValidationError::InvalidClientSecret),
+        OAuthError::ValidationFailed(OAuthValidationError::InvalidBasicAuthentication(Box::new(
+            OAuthValidationError::InvalidClientSecret
+        ))),
+        result.unwrap_err()
+    );
+}
+
+#[tokio::test]
+async fn test_authorization_code_replay_revokes_tokens() {
+    // If an authorization code is used more than once, the authorization server SHOULD revoke all tokens previously issued based on that authorization code.
+
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.authorization_provider.expect_mark_code_used().returning(|_| Ok(false));
+    test.default_grant();
+    test.token_provider
+        .expect_revoke_code_tokens()
+        .with(eq(DEFAULT_CODE_ID))
+        .times(1)
+        .returning(|_| Ok(()));
+    let manager = test.build();
+
+    let request = TokenRequest::default();
+
+    // Act
+    let result = manager.handle_token(request).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::AuthorizationCodeReplayed),
         result.unwrap_err()
     );
 }
+
+#[tokio::test]
+async fn test_authorization_code_is_marked_used() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.authorization_provider
+        .expect_mark_code_used()
+        .with(eq(DEFAULT_AUTHORIZATION_CODE))
+        .times(1)
+        .returning(|_| Ok(true));
+    test.token_provider.expect_revoke_code_tokens().never();
+    test.default_grant();
+    let manager = test.build();
+
+    let request = TokenRequest::default();
+
+    // Act
+    let result = manager.handle_token(request).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+}
@@ -0,0 +1,252 @@
+use crate::{
+    common::{
+        frontend::{FrontendRequest, OAuthError, OAuthValidationError},
+        jose::Jwk,
+        model::{Client, TlsClientAuthentication, TokenEndpointAuthMethod},
+        x509::{certificate_thumbprint, ClientCertificate},
+    },
+    manager::OAuthManager,
+    test::{mock::request_from_raw_http, TestEnvironment, DEFAULT_CLIENT_SECRET, DEFAULT_TOKEN},
+    token::{Token, TokenConfirmation, TokenRequest},
+};
+use base64::{prelude::BASE64_STANDARD, Engine};
+use mockall::predicate::{always, eq};
+use serde_json::json;
+use std::{net::IpAddr, time::Instant};
+
+// A self-signed certificate generated with:
+// openssl req -x509 -newkey ec -pkeyopt ec_paramgen_curve:P-256 -nodes -days 36500 \
+//   -subj "/C=NL/O=Example, Inc./CN=client" \
+//   -addext "subjectAltName=DNS:client.example.com,URI:https://client.example.com,IP:127.0.0.1,email:client@example.com"
+static CERTIFICATE: &str = "MIICGDCCAcCgAwIBAgIUFb/SKWDishoDzq/Axblt73YcDW0wCgYIKoZIzj0EAwIwNjELMAkGA1UEBhMCTkwxFjAUBgNVBAoMDUV4YW1wbGUsIEluYy4xDzANBgNVBAMMBmNsaWVudDAgFw0yNjEwMTYyMzIwMTNaGA8yMTI2MDkyMjIzMjAxM1owNjELMAkGA1UEBhMCTkwxFjAUBgNVBAoMDUV4YW1wbGUsIEluYy4xDzANBgNVBAMMBmNsaWVudDBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IABLVYezI6bfQCzzUg/QtdARtyohjhCLn79NWWdTXVRBCTSyfFRtdWdZD8nYc3W3IX6i/TGFRrlNmiRg46Mb0Vn9GjgakwgaYwHQYDVR0OBBYEFKwhBrLCLwfdSV7fcItBMIuP7N6EMB8GA1UdIwQYMBaAFKwhBrLCLwfdSV7fcItBMIuP7N6EMA8GA1UdEwEB/wQFMAMBAf8wUwYDVR0RBEwwSoISY2xpZW50LmV4YW1wbGUuY29thhpodHRwczovL2NsaWVudC5leGFtcGxlLmNvbYcEfwAAAYESY2xpZW50QGV4YW1wbGUuY29tMAoGCCqGSM49BAMCA0YAMEMCHy5reFcqONpEu7oNjTjT/l4uLm/FdJGVdQ0Da7Wt1NgCIHgEpNePAOs5QTcjNEB7VJ4xo2s2SNDd2HoN3KQVCDPu";
+// The output of: openssl x509 -noout -subject -nameopt RFC2253
+static SUBJECT_DN: &str = r"CN=client,O=Example\, Inc.,C=NL";
+static THUMBPRINT: &str = "OrSK9PQ5bo9fM9FYuIusTUvZtay-tl5WbTWIe8OXHmw";
+
+fn certificate() -> Vec<u8> {
+    BASE64_STANDARD.decode(CERTIFICATE).unwrap()
+}
+
+fn certificate_request() -> TokenRequest {
+    TokenRequest {
+        client_secret: None,
+        client_certificate: Some(certificate()),
+        ..Default::default()
+    }
+}
+
+/// A test environment with a confidential client that authenticates using the given mutual TLS registration.
+fn mutual_tls_environment(tls_client_authentication: TlsClientAuthentication) -> TestEnvironment {
+    let token_endpoint_auth_method = match tls_client_authentication {
+        TlsClientAuthentication::SelfSigned(_) => TokenEndpointAuthMethod::SelfSignedTlsClientAuth,
+        _ => TokenEndpointAuthMethod::TlsClientAuth,
+    };
+    let mut test = TestEnvironment::new();
+    test.register_client(
+        Client { confidential: true, token_endpoint_auth_method, ..Default::default() },
+        DEFAULT_CLIENT_SECRET.to_string(),
+    );
+    test.client_provider
+        .expect_get_tls_client_authentication()
+        .returning(move |_| Ok(Some(tls_client_authentication.clone())));
+    test
+}
+
+fn build(test: TestEnvironment) -> OAuthManager<u32, (), ()> {
+    let mut manager = test.build();
+    manager.config.mutual_tls = true;
+    manager
+}
+
+#[test]
+fn test_client_certificate_is_parsed() {
+    // Act
+    let result = ClientCertificate::parse(&certificate());
+
+    // Assert
+    assert!(result.is_some(), "result is not Some");
+    let result = result.unwrap();
+    assert_eq!(SUBJECT_DN, result.subject_dn);
+    assert_eq!(vec!["client.example.com".to_string()], result.san_dns);
+    assert_eq!(vec!["https://client.example.com".to_string()], result.san_uri);
+    assert_eq!(vec!["127.0.0.1".parse::<IpAddr>().unwrap()], result.san_ip);
+    assert_eq!(vec!["client@example.com".to_string()], result.san_email);
+    assert_eq!(THUMBPRINT, certificate_thumbprint(&certificate()));
+}
+
+#[test]
+fn test_malformed_client_certificate_is_rejected() {
+    // Act
+    let result = ClientCertificate::parse(&certificate()[..100]);
+
+    // Assert
+    assert!(result.is_none(), "result is not None, result is {:?}", result);
+}
+
+#[test]
+fn test_request_reads_peer_certificate() {
+    // Arrange
+    let mut request = request_from_raw_http(
+        r#"
+            POST /token HTTP/1.1
+            Content-Type: application/x-www-form-urlencoded
+
+            grant_type=client_credentials&client_id=client
+        "#,
+    );
+    request.peer_certificate = Some(certificate());
+
+    // Act
+    let result = TokenRequest::try_from(&request as &dyn FrontendRequest);
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    assert_eq!(Some(certificate()), result.unwrap().client_certificate);
+}
+
+#[tokio::test]
+async fn test_tls_client_auth_binds_token_to_certificate() {
+    // The authorization server obtains the client certificate from the TLS layer and associates it with the access token.
+
+    // Arrange
+    let mut test =
+        mutual_tls_environment(TlsClientAuthentication::SubjectDn(SUBJECT_DN.to_string()));
+    test.client_provider.expect_verify_client_secret().never();
+    test.token_provider
+        .expect_token()
+        .with(
+            always(),
+            always(),
+            eq(TokenConfirmation { x5t_s256: Some(THUMBPRINT.to_string()), ..Default::default() }),
+            always(),
+        )
+        .times(1)
+        .returning(|_, _, _, _| {
+            Ok(Token {
+                token: DEFAULT_TOKEN.to_string(),
+                refresh_token: None,
+                valid_until: Instant::now(),
+            })
+        });
+    test.default_grant();
+    let manager = build(test);
+
+    // Act
+    let result = manager.handle_token(certificate_request()).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    assert_eq!("Bearer", result.unwrap().token_type);
+}
+
+#[tokio::test]
+async fn test_tls_client_auth_matches_subject_alt_name() {
+    // Arrange
+    let registrations = [
+        TlsClientAuthentication::SanDns("CLIENT.example.com".to_string()),
+        TlsClientAuthentication::SanUri("https://client.example.com".to_string()),
+        TlsClientAuthentication::SanIp("127.0.0.1".parse().unwrap()),
+        TlsClientAuthentication::SanEmail("client@example.com".to_string()),
+    ];
+
+    for registration in registrations {
+        let mut test = mutual_tls_environment(registration.clone());
+        test.default_grant();
+        let manager = build(test);
+
+        // Act
+        let result = manager.handle_token(certificate_request()).await;
+
+        // Assert
+        assert!(result.is_ok(), "result is not Ok for {:?}, result is {:?}", registration, result);
+    }
+}
+
+#[tokio::test]
+async fn test_tls_client_auth_rejects_other_certificate() {
+    // Arrange
+    let mut test =
+        mutual_tls_environment(TlsClientAuthentication::SanDns("other.example.com".to_string()));
+    test.default_grant();
+    let manager = build(test);
+
+    // Act
+    let result = manager.handle_token(certificate_request()).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::InvalidClientCertificate(
+            "the certificate does not match the registration of the client"
+        )),
+        result.unwrap_err()
+    );
+}
+
+#[tokio::test]
+async fn test_tls_client_auth_requires_certificate() {
+    // Arrange
+    let mut test =
+        mutual_tls_environment(TlsClientAuthentication::SubjectDn(SUBJECT_DN.to_string()));
+    test.default_grant();
+    let manager = build(test);
+
+    let request = TokenRequest { client_secret: None, ..Default::default() };
+
+    // Act
+    let result = manager.handle_token(request).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::InvalidClientCertificate(
+            "no certificate was presented"
+        )),
+        result.unwrap_err()
+    );
+}
+
+#[tokio::test]
+async fn test_self_signed_tls_client_auth_matches_registered_certificate() {
+    // The client registers its certificate using the "x5c" parameter of a JWK.
+
+    // Arrange
+    let key: Jwk = serde_json::from_value(
+        json!({ "kty": "EC", "crv": "P-256", "x": "x", "y": "y", "x5c": [CERTIFICATE] }),
+    )
+    .unwrap();
+    let mut test = mutual_tls_environment(TlsClientAuthentication::SelfSigned(vec![key]));
+    test.default_grant();
+    let manager = build(test);
+
+    // Act
+    let result = manager.handle_token(certificate_request()).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+}
+
+#[tokio::test]
+async fn test_certificate_is_ignored_without_mutual_tls() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.register_client(
+        Client { confidential: true, ..Default::default() },
+        DEFAULT_CLIENT_SECRET.to_string(),
+    );
+    test.client_provider.expect_get_tls_client_authentication().never();
+    test.default_grant();
+    let manager = test.build();
+
+    // Act
+    let result = manager.handle_token(certificate_request()).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::MissingRequiredParameter(
+            "client_secret"
+        )),
+        result.unwrap_err()
+    );
+}
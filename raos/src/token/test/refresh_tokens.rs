@@ -1,7 +1,7 @@
-use crate::test::DEFAULT_TOKEN;
+use crate::test::{DEFAULT_REFRESH_TOKEN_FAMILY, DEFAULT_TOKEN};
 use crate::{
     common::{
-        frontend::{OAuthError, OAuthValidationError},
+        frontend::{OAuthError, OAuthValidationError, PublicOAuthError},
         model::{Client, Grant},
     },
     test::{
@@ -9,7 +9,7 @@ use crate::{
     },
     token::{RequestedGrantType, TokenRequest},
 };
-use mockall::predicate::always;
+use mockall::predicate::{always, eq};
 
 #[tokio::test]
 async fn test_refresh_token_enforce_same_scopes() {
@@ -154,10 +154,102 @@ async fn test_refresh_token_invalid() {
     // Act
     let result = manager.handle_token(request).await;
 
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    let error = result.unwrap_err();
+    assert_eq!(OAuthError::ValidationFailed(OAuthValidationError::InvalidRefreshToken), error);
+    assert_eq!(PublicOAuthError::InvalidGrant, PublicOAuthError::from(error));
+}
+
+#[tokio::test]
+async fn test_refresh_token_public_client_is_rotated() {
+    // Refresh tokens for public clients MUST be sender-constrained or use refresh token rotation.
+
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.token_provider
+        .expect_mark_refresh_token_used()
+        .with(eq(DEFAULT_REFRESH_TOKEN))
+        .times(1)
+        .returning(|_| Ok(true));
+    test.token_provider.expect_revoke_refresh_token_family().never();
+    test.default_refresh_token();
+    let manager = test.build();
+
+    let request = TokenRequest {
+        grant_type: RequestedGrantType::RefreshToken {
+            refresh_token: DEFAULT_REFRESH_TOKEN.to_string(),
+        },
+        client_secret: None,
+        ..Default::default()
+    };
+
+    // Act
+    let result = manager.handle_token(request).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+}
+
+#[tokio::test]
+async fn test_refresh_token_replay_revokes_family() {
+    // If a refresh token is compromised and subsequently used by both the attacker and the legitimate client,
+    // one of them will present an invalidated refresh token, which will inform the authorization server of the breach.
+
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.token_provider.expect_mark_refresh_token_used().returning(|_| Ok(false));
+    test.default_refresh_token();
+    test.token_provider
+        .expect_revoke_refresh_token_family()
+        .with(eq(DEFAULT_REFRESH_TOKEN_FAMILY))
+        .times(1)
+        .returning(|_| Ok(()));
+    let manager = test.build();
+
+    let request = TokenRequest {
+        grant_type: RequestedGrantType::RefreshToken {
+            refresh_token: DEFAULT_REFRESH_TOKEN.to_string(),
+        },
+        client_secret: None,
+        ..Default::default()
+    };
+
+    // Act
+    let result = manager.handle_token(request).await;
+
     // Assert
     assert!(result.is_err(), "result is not Err, result is {:?}", result);
     assert_eq!(
-        OAuthError::ValidationFailed(OAuthValidationError::InvalidRefreshToken),
+        OAuthError::ValidationFailed(OAuthValidationError::RefreshTokenReplayed),
         result.unwrap_err()
     );
 }
+
+#[tokio::test]
+async fn test_refresh_token_confidential_client_is_not_rotated() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.register_client(
+        Client { confidential: true, ..Default::default() },
+        DEFAULT_CLIENT_SECRET.to_string(),
+    );
+    test.token_provider.expect_mark_refresh_token_used().never();
+    test.default_refresh_token();
+    let manager = test.build();
+
+    let request = TokenRequest {
+        grant_type: RequestedGrantType::RefreshToken {
+            refresh_token: DEFAULT_REFRESH_TOKEN.to_string(),
+        },
+        ..Default::default()
+    };
+
+    // Act
+    let result = manager.handle_token(request).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+}
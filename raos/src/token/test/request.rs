@@ -75,6 +75,31 @@ fn test_endpoint_supports_client_credentials_in_body() {
     assert_eq!(Some("5678".to_string()), result.client_secret);
 }
 
+#[test]
+fn test_endpoint_supports_client_credentials_in_header() {
+    // The authorization server MUST support the HTTP Basic authentication scheme for authenticating clients that were issued a client password.
+
+    // Arrange
+    let request = request_from_raw_http(
+        r#"
+            POST /token HTTP/1.1
+            Content-Type: application/x-www-form-urlencoded
+            authorization: Basic MTIzNDo1Njc4
+
+            grant_type=client_credentials
+        "#,
+    );
+
+    // Act
+    let result = TokenRequest::try_from(&request as &dyn FrontendRequest);
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    let result = result.unwrap();
+    assert_eq!("1234", result.client_id);
+    assert_eq!(Some("5678".to_string()), result.client_secret);
+}
+
 #[test]
 fn test_request_with_empty_values_must_be_omitted() {
     // Parameters sent without a value MUST be treated as if they were omitted from the request.
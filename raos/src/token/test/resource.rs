@@ -0,0 +1,123 @@
+use std::time::Instant;
+
+use crate::{
+    common::{
+        frontend::{FrontendRequest, OAuthError, OAuthValidationError, PublicOAuthError},
+        model::Grant,
+    },
+    test::{mock::request_from_raw_http, TestEnvironment, DEFAULT_TOKEN},
+    token::{Token, TokenRequest},
+};
+
+const ONE: &str = "https://one.example.com";
+const TWO: &str = "https://two.example.com";
+
+/// A test environment with an authorization code that was granted for both resources,
+/// where the token provider only issues a token for the expected audience.
+fn granted_resources_environment(expected_audience: &'static [&'static str]) -> TestEnvironment {
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.client_provider.expect_allow_client_resources().returning(|_, _| Ok(true));
+    test.authorization_provider.expect_exchange_code_for_grant().returning(|_| {
+        Ok(Some(Grant { resource: vec![ONE.to_string(), TWO.to_string()], ..Default::default() }))
+    });
+    test.authorization_provider.expect_mark_code_used().returning(|_| Ok(true));
+    test.token_provider
+        .expect_token()
+        .withf(move |_, _, _, audience| audience == expected_audience)
+        .returning(|_, _, _, _| {
+            Ok(Token {
+                token: DEFAULT_TOKEN.to_string(),
+                refresh_token: None,
+                valid_until: Instant::now(),
+            })
+        });
+    test
+}
+
+#[test]
+fn test_token_request_accepts_multiple_resources() {
+    // Arrange
+    let request = request_from_raw_http(
+        r#"
+        POST /token HTTP/1.1
+        Content-Type: application/x-www-form-urlencoded
+
+        grant_type=client_credentials&client_id=client&resource=https%3A%2F%2Fone.example.com&resource=https%3A%2F%2Ftwo.example.com
+    "#,
+    );
+
+    // Act
+    let result = TokenRequest::try_from(&request as &dyn FrontendRequest);
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+    assert_eq!(vec![ONE.to_string(), TWO.to_string()], result.unwrap().resource);
+}
+
+#[tokio::test]
+async fn test_token_restricted_to_granted_resources() {
+    // Arrange
+    let manager = granted_resources_environment(&[ONE, TWO]).build();
+
+    // Act
+    let result = manager.handle_token(TokenRequest::default()).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+}
+
+#[tokio::test]
+async fn test_token_request_narrows_down_granted_resources() {
+    // Arrange
+    let manager = granted_resources_environment(&[TWO]).build();
+    let request = TokenRequest { resource: vec![TWO.to_string()], ..Default::default() };
+
+    // Act
+    let result = manager.handle_token(request).await;
+
+    // Assert
+    assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
+}
+
+#[tokio::test]
+async fn test_token_request_cannot_extend_granted_resources() {
+    // Arrange
+    let manager = granted_resources_environment(&[]).build();
+    let request = TokenRequest {
+        resource: vec!["https://three.example.com".to_string()],
+        ..Default::default()
+    };
+
+    // Act
+    let result = manager.validate_token_request(request).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    let error = result.unwrap_err();
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::InvalidTarget("not granted")),
+        error
+    );
+    assert_eq!(PublicOAuthError::InvalidTarget, PublicOAuthError::from(error));
+}
+
+#[tokio::test]
+async fn test_disallowed_resource_rejected_before_grant_is_used() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.client_provider.expect_allow_client_resources().returning(|_, _| Ok(false));
+    test.authorization_provider.expect_exchange_code_for_grant().never();
+    test.token_provider.expect_token().never();
+    let manager = test.build();
+
+    let request = TokenRequest { resource: vec![ONE.to_string()], ..Default::default() };
+
+    // Act
+    let result = manager.handle_token(request).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(PublicOAuthError::InvalidTarget, PublicOAuthError::from(result.unwrap_err()));
+}
@@ -1,6 +1,9 @@
 use crate::{
-    common::model::Grant,
-    test::{TestEnvironment, DEFAULT_AUTHORIZATION_CODE},
+    common::{
+        frontend::{FrontendResponse, FrontendResponseExt, PublicOAuthError},
+        model::{Client, Grant, TokenEndpointAuthMethod},
+    },
+    test::{TestEnvironment, DEFAULT_AUTHORIZATION_CODE, DEFAULT_CLIENT_SECRET},
     token::TokenRequest,
 };
 
@@ -27,3 +30,127 @@ async fn test_response_must_contain_identical_scope() {
     assert!(result.is_ok(), "result is not Ok, result is {:?}", result);
     assert_eq!("some scope field", result.unwrap().scope.unwrap());
 }
+
+#[tokio::test]
+async fn test_invalid_client_response_is_unauthorized() {
+    // If the client attempted to authenticate via the Authorization request header field,
+    // the authorization server MUST respond with an HTTP 401 (Unauthorized) status code
+    // and include the WWW-Authenticate response header field matching the authentication scheme used by the client.
+
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    let manager = test.build();
+
+    let request = TokenRequest { client_secret: Some("wrong".to_string()), ..Default::default() };
+
+    // Act
+    let response = manager.handle_token(request).await.into_frontend_response();
+
+    // Assert
+    let FrontendResponse::Error { status, headers, error } = response else {
+        panic!("response is not Error, response is {:?}", response);
+    };
+    assert_eq!(401, status);
+    assert_eq!("invalid_client", error.error);
+    assert!(
+        headers.iter().any(|(name, _)| *name == "WWW-Authenticate"),
+        "headers do not contain WWW-Authenticate, headers are {:?}",
+        headers
+    );
+}
+
+#[tokio::test]
+async fn test_invalid_client_secret_post_has_no_basic_challenge() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.register_client(
+        Client {
+            token_endpoint_auth_method: TokenEndpointAuthMethod::ClientSecretPost,
+            ..Default::default()
+        },
+        DEFAULT_CLIENT_SECRET.to_string(),
+    );
+    let manager = test.build();
+
+    let request = TokenRequest {
+        client_secret: Some("wrong".to_string()),
+        client_secret_basic: false,
+        ..Default::default()
+    };
+
+    // Act
+    let response = manager.handle_token(request).await.into_frontend_response();
+
+    // Assert
+    let FrontendResponse::Error { status, headers, error } = response else {
+        panic!("response is not Error, response is {:?}", response);
+    };
+    assert_eq!(400, status);
+    assert_eq!("invalid_client", error.error);
+    assert!(headers.is_empty(), "headers are not empty, headers are {:?}", headers);
+}
+
+#[test]
+fn test_unauthorized_response_always_has_challenge() {
+    // The server generating a 401 response MUST send a WWW-Authenticate header field containing
+    // at least one challenge applicable to the target resource.
+
+    // Arrange
+    let errors = [
+        PublicOAuthError::AccessDenied,
+        PublicOAuthError::InvalidRequest,
+        PublicOAuthError::InvalidScope,
+        PublicOAuthError::InvalidTarget,
+        PublicOAuthError::InvalidClient { basic_authentication: false },
+        PublicOAuthError::InvalidClient { basic_authentication: true },
+        PublicOAuthError::UnsupportedGrantType,
+        PublicOAuthError::UnauthorizedClient,
+        PublicOAuthError::InvalidGrant,
+        PublicOAuthError::AuthorizationPending,
+        PublicOAuthError::SlowDown,
+        PublicOAuthError::ExpiredToken,
+        PublicOAuthError::InvalidDPoPProof,
+        PublicOAuthError::UseDPoPNonce("nonce".to_string()),
+        PublicOAuthError::LoginRequired,
+        PublicOAuthError::ConsentRequired,
+        PublicOAuthError::InvalidRedirectUri,
+        PublicOAuthError::InvalidClientMetadata,
+        PublicOAuthError::MissingToken,
+        PublicOAuthError::InvalidToken,
+        PublicOAuthError::InsufficientScope("scope".to_string()),
+        PublicOAuthError::ServerError,
+    ];
+
+    for error in errors.iter().filter(|error| error.status_code() == 401) {
+        // Act
+        let headers = error.headers();
+
+        // Assert
+        assert!(
+            headers.iter().any(|(name, _)| *name == "WWW-Authenticate"),
+            "headers of {error:?} do not contain WWW-Authenticate, headers are {:?}",
+            headers
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_provider_error_response_is_server_error() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.client_provider.expect_get_client_by_id().returning(|_| Err(()));
+    let manager = test.build();
+
+    let request = TokenRequest::default();
+
+    // Act
+    let response = manager.handle_token(request).await.into_frontend_response();
+
+    // Assert
+    let FrontendResponse::Error { status, error, .. } = response else {
+        panic!("response is not Error, response is {:?}", response);
+    };
+    assert_eq!(500, status);
+    assert_eq!("server_error", error.error);
+}
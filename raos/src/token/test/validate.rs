@@ -1,7 +1,7 @@
 use crate::{
     common::{
-        frontend::{OAuthError, OAuthValidationError},
-        model::{Client, CodeChallenge, Grant},
+        frontend::{OAuthError, OAuthValidationError, PublicOAuthError},
+        model::{Client, CodeChallenge, Grant, TokenEndpointAuthMethod},
     },
     test::{
         TestEnvironment, DEFAULT_AUTHORIZATION_CODE, DEFAULT_CLIENT_SECRET, DEFAULT_CODE_VERIFIER,
@@ -89,7 +89,7 @@ async fn test_client_credentials_only_allowed_by_confidential_clients() {
     assert!(regular_result.is_err(), "result is not Err, result is {:?}", regular_result);
     assert_eq!(
         OAuthError::ValidationFailed(OAuthValidationError::ClientNotAllowedToUseGrantType {
-            requested: "client_credentials"
+            requested: "client_credentials".to_string()
         }),
         regular_result.unwrap_err()
     );
@@ -187,3 +187,118 @@ async fn test_token_request_that_includes_redirect_uri_should_be_the_same_as_the
         result.unwrap_err()
     );
 }
+
+#[tokio::test]
+async fn test_grant_type_must_be_registered_for_client() {
+    // The authenticated client is not authorized to use this authorization grant type: unauthorized_client.
+
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.register_client(
+        Client {
+            confidential: true,
+            grant_types: vec!["authorization_code".to_string()],
+            ..Default::default()
+        },
+        DEFAULT_CLIENT_SECRET.to_string(),
+    );
+    let manager = test.build();
+
+    let request =
+        TokenRequest { grant_type: RequestedGrantType::ClientCredentials, ..Default::default() };
+
+    // Act
+    let result = manager.validate_token_request(request).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    let error = result.unwrap_err();
+    assert_eq!(
+        OAuthError::ValidationFailed(OAuthValidationError::ClientNotAllowedToUseGrantType {
+            requested: "client_credentials".to_string()
+        }),
+        error
+    );
+    assert_eq!(PublicOAuthError::UnauthorizedClient, PublicOAuthError::from(error));
+}
+
+#[tokio::test]
+async fn test_authentication_method_must_be_registered_for_client() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.register_client(
+        Client {
+            confidential: true,
+            token_endpoint_auth_method: TokenEndpointAuthMethod::PrivateKeyJwt,
+            ..Default::default()
+        },
+        DEFAULT_CLIENT_SECRET.to_string(),
+    );
+    test.default_grant();
+    let manager = test.build();
+
+    // Act
+    let result = manager.validate_token_request(TokenRequest::default()).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(
+            OAuthValidationError::ClientNotAllowedToUseAuthenticationMethod {
+                used: "client_secret_basic"
+            }
+        ),
+        result.unwrap_err()
+    );
+}
+
+#[tokio::test]
+async fn test_client_secret_post_rejected_for_client_secret_basic_client() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.default_client();
+    test.default_grant();
+    let manager = test.build();
+
+    let request = TokenRequest { client_secret_basic: false, ..Default::default() };
+
+    // Act
+    let result = manager.validate_token_request(request).await;
+
+    // Assert
+    assert!(result.is_err(), "result is not Err, result is {:?}", result);
+    assert_eq!(
+        OAuthError::ValidationFailed(
+            OAuthValidationError::ClientNotAllowedToUseAuthenticationMethod {
+                used: "client_secret_post"
+            }
+        ),
+        result.unwrap_err()
+    );
+}
+
+#[tokio::test]
+async fn test_client_secret_post_accepted_for_client_secret_post_client() {
+    // Arrange
+    let mut test = TestEnvironment::new();
+    test.register_client(
+        Client {
+            token_endpoint_auth_method: TokenEndpointAuthMethod::ClientSecretPost,
+            ..Default::default()
+        },
+        DEFAULT_CLIENT_SECRET.to_string(),
+    );
+    test.default_grant();
+    let manager = test.build();
+
+    let basic_request = TokenRequest::default();
+    let post_request = TokenRequest { client_secret_basic: false, ..Default::default() };
+
+    // Act
+    let basic_result = manager.validate_token_request(basic_request).await;
+    let post_result = manager.validate_token_request(post_request).await;
+
+    // Assert
+    assert!(basic_result.is_err(), "result is not Err, result is {:?}", basic_result);
+    assert!(post_result.is_ok(), "result is not Ok, result is {:?}", post_result);
+}
@@ -1,9 +1,17 @@
+use std::time::SystemTime;
+
+use url::Url;
+
 use crate::{
     common::{
         frontend::{OAuthError, OAuthValidationError},
         model::Client,
+        x509::certificate_thumbprint,
     },
+    device::{DeviceCodeStatus, DEVICE_CODE_GRANT_TYPE},
+    exchange::TokenExchangeGrant,
     manager::OAuthManager,
+    metadata::Endpoint,
     token::{RefreshGrant, RequestedGrantType, TokenRequest},
 };
 
@@ -14,6 +22,16 @@ pub struct ValidatedTokenRequest<OwnerId> {
     pub client: Client,
     /// The type of grant requested by the client.
     pub grant_type: GrantType<OwnerId>,
+    /// The JWK thumbprint of the DPoP key the client proved possession of, if it sent a DPoP proof.
+    pub dpop_jkt: Option<String>,
+    /// The SHA-256 thumbprint of the certificate the client presented for mutual TLS, if mutual TLS is enabled.
+    pub x5t_s256: Option<String>,
+    /// The nonce of the OpenID Connect authentication request the authorization code was issued for.
+    pub nonce: Option<String>,
+    /// The time the resource owner authenticated, if the authorization code was issued for an OpenID Connect authentication request.
+    pub auth_time: Option<SystemTime>,
+    /// The resources the issued token is restricted to, as described in RFC 8707.
+    pub audience: Vec<String>,
 }
 
 /// The type of grant requested by the client.
@@ -27,9 +45,69 @@ pub enum GrantType<OwnerId> {
         resource_owner: OwnerId,
         /// The requested scope.
         scope: Vec<String>,
+        /// The identifier of the exchanged code, which should be stored with the issued tokens
+        /// so they can be revoked if the code is replayed.
+        code_id: String,
     },
     /// The client is requesting an access token using a refresh token.
     RefreshToken(RefreshGrant<OwnerId>),
+    /// The device is requesting an access token using an approved device code.
+    DeviceCode {
+        /// The resource owner that approved the device authorization.
+        resource_owner: OwnerId,
+        /// The approved scope.
+        scope: Vec<String>,
+    },
+    /// The client is requesting a token in exchange for another security token.
+    TokenExchange(TokenExchangeGrant<OwnerId>),
+    /// The client is requesting an access token using a JWT assertion from a trusted issuer.
+    JwtBearer {
+        /// The resource owner the subject of the assertion was mapped to.
+        resource_owner: OwnerId,
+        /// The allowed scope.
+        scope: Vec<String>,
+        /// The issuer that signed the assertion.
+        issuer: String,
+    },
+    /// The client is requesting an access token using a custom grant type.
+    Extension {
+        /// The URI of the custom grant type.
+        grant_type: String,
+        /// The resource owner returned by the [GrantTypeHandler](crate::token::GrantTypeHandler).
+        resource_owner: OwnerId,
+        /// The scope returned by the [GrantTypeHandler](crate::token::GrantTypeHandler).
+        scope: Vec<String>,
+    },
+}
+
+impl<OwnerId> GrantType<OwnerId> {
+    /// The resource owner the grant was authorized by, or [None] for the client credentials grant.
+    pub fn resource_owner(&self) -> Option<&OwnerId> {
+        match self {
+            GrantType::AuthorizationCode { resource_owner, .. }
+            | GrantType::RefreshToken(RefreshGrant { resource_owner, .. })
+            | GrantType::DeviceCode { resource_owner, .. }
+            | GrantType::JwtBearer { resource_owner, .. }
+            | GrantType::Extension { resource_owner, .. }
+            | GrantType::TokenExchange(TokenExchangeGrant { resource_owner, .. }) => {
+                Some(resource_owner)
+            }
+            GrantType::ClientCredentials => None,
+        }
+    }
+
+    /// The scope of the grant, or [None] for the client credentials grant.
+    pub fn scope(&self) -> Option<&[String]> {
+        match self {
+            GrantType::AuthorizationCode { scope, .. }
+            | GrantType::RefreshToken(RefreshGrant { scope, .. })
+            | GrantType::DeviceCode { scope, .. }
+            | GrantType::JwtBearer { scope, .. }
+            | GrantType::Extension { scope, .. }
+            | GrantType::TokenExchange(TokenExchangeGrant { scope, .. }) => Some(scope),
+            GrantType::ClientCredentials => None,
+        }
+    }
 }
 
 impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
@@ -59,12 +137,17 @@ impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
     /// let req = TokenRequest {
     ///     client_id: "CLIENT_ID".to_string(),
     ///     client_secret: Some("CLIENT_SECRET".to_string()),
+    ///     client_secret_basic: true,
+    ///     client_assertion: None,
+    ///     client_certificate: None,
     ///     grant_type: RequestedGrantType::AuthorizationCode {
     ///         code: "AUTHORIZATION_CODE".to_string(),
     ///         code_verifier: "CODE_CHALLENGE".to_string(),
     ///     },
     ///     redirect_uri: None, // OAuth 2.0 compatibility, not required in OAuth v2.1
-    ///     scope: None
+    ///     scope: None,
+    ///     resource: Vec::new(),
+    ///     dpop_proof: None,
     /// };
     ///
     /// # tokio_test::block_on(async {
@@ -75,31 +158,41 @@ impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
         &self,
         req: TokenRequest,
     ) -> Result<ValidatedTokenRequest<U>, OAuthError<E>> {
-        let Some(client) = self
-            .client_provider
-            .get_client_by_id(&req.client_id)
-            .await
-            .map_err(OAuthError::ProviderImplementationError)?
-        else {
-            return Err(OAuthValidationError::ClientDoesNotExist.into());
-        };
-        if !client.is_valid() {
-            return Err(OAuthValidationError::InvalidClient.into());
+        // Custom grant types are only supported when a handler is registered for them
+        if let RequestedGrantType::Extension { grant_type, .. } = &req.grant_type {
+            if !self.grant_type_handlers.contains_key(grant_type) {
+                return Err(OAuthValidationError::InvalidGrantType {
+                    requested: grant_type.clone(),
+                }
+                .into());
+            }
         }
 
-        if let Some(client_secret) = req.client_secret {
-            let secret_valid = self
-                .client_provider
-                .verify_client_secret(&client, &client_secret)
-                .await
-                .map_err(OAuthError::ProviderImplementationError)?;
-            if !secret_valid {
-                return Err(OAuthValidationError::InvalidClientSecret.into());
+        // Public clients may only omit their credentials when exchanging an authorization code, refresh token,
+        // device code or assertion
+        let require_secret = !matches!(
+            req.grant_type,
+            RequestedGrantType::AuthorizationCode { .. }
+                | RequestedGrantType::RefreshToken { .. }
+                | RequestedGrantType::DeviceCode { .. }
+                | RequestedGrantType::JwtBearer { .. }
+        );
+        let client = self
+            .authenticate_client(
+                &req.client_id,
+                req.client_secret,
+                req.client_secret_basic,
+                req.client_assertion,
+                req.client_certificate.as_deref(),
+                require_secret,
+            )
+            .await?;
+
+        if !client.has_grant_type(req.grant_type.as_str()) {
+            return Err(OAuthValidationError::ClientNotAllowedToUseGrantType {
+                requested: req.grant_type.as_str().to_string(),
             }
-        } else if client.confidential
-            || !matches!(req.grant_type, RequestedGrantType::AuthorizationCode { .. })
-        {
-            return Err(OAuthValidationError::MissingRequiredParameter("client_secret").into());
+            .into());
         }
 
         if let Some(ref redirect_uri) = req.redirect_uri {
@@ -108,11 +201,35 @@ impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
             }
         }
 
+        // A DPoP proof binds the issued tokens to the key of the client, it is ignored if DPoP is not enabled
+        let dpop_jkt = match (&req.dpop_proof, &self.dpop_provider) {
+            (Some(proof), Some(_)) => {
+                let Some(token_endpoint) = self.config.endpoint_url(Endpoint::Token) else {
+                    return Err(OAuthValidationError::NoAuthorizationServerIdentifier.into());
+                };
+                Some(self.verify_dpop_proof(proof, "POST", &token_endpoint, None).await?)
+            }
+            _ => None,
+        };
+
+        // A TLS client certificate binds the issued tokens to the certificate, it is ignored if mutual TLS is not enabled
+        let x5t_s256 = req
+            .client_certificate
+            .as_deref()
+            .filter(|_| self.config.mutual_tls)
+            .map(certificate_thumbprint);
+
+        // Resources the client may not target are rejected up front, before the grant is used
+        self.validate_resources(&client, &req.resource).await?;
+
+        let mut nonce = None;
+        let mut auth_time = None;
+        let mut granted_resource = Vec::new();
         let grant_type = match req.grant_type {
             RequestedGrantType::ClientCredentials => {
                 if !client.confidential {
                     return Err(OAuthValidationError::ClientNotAllowedToUseGrantType {
-                        requested: "client_credentials",
+                        requested: "client_credentials".to_string(),
                     }
                     .into());
                 }
@@ -121,7 +238,7 @@ impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
             RequestedGrantType::RefreshToken { refresh_token } => {
                 let Some(refresh_grant) = self
                     .token_provider
-                    .exchange_refresh_token(refresh_token)
+                    .exchange_refresh_token(refresh_token.clone())
                     .await
                     .map_err(OAuthError::ProviderImplementationError)?
                 else {
@@ -138,13 +255,33 @@ impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
                         return Err(OAuthValidationError::ScopeNotConsented.into());
                     }
                 }
+                if refresh_grant.dpop_jkt.is_some() && refresh_grant.dpop_jkt != dpop_jkt {
+                    return Err(OAuthValidationError::DPoPKeyMismatch.into());
+                }
 
+                // Refresh tokens of public clients are rotated, so a reused refresh token indicates that it was stolen
+                if !client.confidential {
+                    let first_use = self
+                        .token_provider
+                        .mark_refresh_token_used(&refresh_token)
+                        .await
+                        .map_err(OAuthError::ProviderImplementationError)?;
+                    if !first_use {
+                        self.token_provider
+                            .revoke_refresh_token_family(&refresh_grant.family_id)
+                            .await
+                            .map_err(OAuthError::ProviderImplementationError)?;
+                        return Err(OAuthValidationError::RefreshTokenReplayed.into());
+                    }
+                }
+
+                granted_resource = refresh_grant.resource.clone();
                 GrantType::RefreshToken(refresh_grant)
             }
             RequestedGrantType::AuthorizationCode { code, code_verifier } => {
                 let Some(grant) = self
                     .authorization_provider
-                    .exchange_code_for_grant(code)
+                    .exchange_code_for_grant(code.clone())
                     .await
                     .map_err(OAuthError::ProviderImplementationError)?
                 else {
@@ -158,15 +295,104 @@ impl<U: 'static, E: 'static, Ex: 'static> OAuthManager<U, E, Ex> {
                     return Err(OAuthValidationError::AuthorizationCodeClientMismatch.into());
                 }
                 if let Some(redirect_uri) = req.redirect_uri {
-                    if grant.redirect_uri.to_string() != redirect_uri {
+                    if redirect_uri.parse::<Url>().ok().as_ref() != Some(&grant.redirect_uri) {
                         return Err(OAuthValidationError::InvalidRedirectUri.into());
                     }
                 }
+                if grant.dpop_jkt.is_some() && grant.dpop_jkt != dpop_jkt {
+                    return Err(OAuthValidationError::DPoPKeyMismatch.into());
+                }
+
+                // A code may only be used once, if it is used again the tokens issued from it are revoked
+                let first_use = self
+                    .authorization_provider
+                    .mark_code_used(&code)
+                    .await
+                    .map_err(OAuthError::ProviderImplementationError)?;
+                if !first_use {
+                    self.token_provider
+                        .revoke_code_tokens(&grant.code_id)
+                        .await
+                        .map_err(OAuthError::ProviderImplementationError)?;
+                    return Err(OAuthValidationError::AuthorizationCodeReplayed.into());
+                }
+
+                nonce = grant.openid.nonce;
+                auth_time = grant.auth_time;
+                granted_resource = grant.resource;
+                GrantType::AuthorizationCode {
+                    resource_owner: grant.owner_id,
+                    scope: grant.scope,
+                    code_id: grant.code_id,
+                }
+            }
+            RequestedGrantType::DeviceCode { device_code } => {
+                let Some(device_authorization_provider) = &self.device_authorization_provider
+                else {
+                    return Err(OAuthValidationError::InvalidGrantType {
+                        requested: DEVICE_CODE_GRANT_TYPE.to_string(),
+                    }
+                    .into());
+                };
+
+                let status = device_authorization_provider
+                    .poll_device_code(device_code)
+                    .await
+                    .map_err(OAuthError::ProviderImplementationError)?;
+                let device_grant = match status {
+                    DeviceCodeStatus::Approved(device_grant) => device_grant,
+                    DeviceCodeStatus::Pending => {
+                        return Err(OAuthValidationError::AuthorizationPending.into())
+                    }
+                    DeviceCodeStatus::SlowDown => return Err(OAuthValidationError::SlowDown.into()),
+                    DeviceCodeStatus::Denied => return Err(OAuthError::AccessDenied),
+                    DeviceCodeStatus::Expired => {
+                        return Err(OAuthValidationError::ExpiredDeviceCode.into())
+                    }
+                    DeviceCodeStatus::Invalid => {
+                        return Err(OAuthValidationError::InvalidDeviceCode.into())
+                    }
+                };
+
+                if device_grant.client_id != client.client_id {
+                    return Err(OAuthValidationError::DeviceCodeClientMismatch.into());
+                }
 
-                GrantType::AuthorizationCode { resource_owner: grant.owner_id, scope: grant.scope }
+                GrantType::DeviceCode {
+                    resource_owner: device_grant.resource_owner,
+                    scope: device_grant.scope,
+                }
+            }
+            RequestedGrantType::TokenExchange(params) => GrantType::TokenExchange(
+                self.validate_token_exchange(&client, *params, req.scope).await?,
+            ),
+            RequestedGrantType::JwtBearer { assertion } => {
+                self.validate_jwt_bearer(&client, assertion, req.scope).await?
+            }
+            RequestedGrantType::Extension { grant_type, parameters } => {
+                self.validate_extension_grant(&client, grant_type, parameters).await?
             }
         };
 
-        Ok(ValidatedTokenRequest { client, grant_type })
+        // Resources authorized by the resource owner may be narrowed down by the token request, but not extended
+        let audience = if req.resource.is_empty() {
+            granted_resource
+        } else if granted_resource.is_empty()
+            || req.resource.iter().all(|resource| granted_resource.contains(resource))
+        {
+            req.resource
+        } else {
+            return Err(OAuthValidationError::InvalidTarget("not granted").into());
+        };
+
+        Ok(ValidatedTokenRequest {
+            client,
+            grant_type,
+            dpop_jkt,
+            x5t_s256,
+            nonce,
+            auth_time,
+            audience,
+        })
     }
 }
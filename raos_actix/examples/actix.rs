@@ -1,10 +1,16 @@
 use actix_web::{get, post, web, App, HttpServer};
 
 use example_support::{
-    DumbTokenProvider, ExampleAuthorizationProvider, VecClient, VecClientProvider,
+    DumbTokenProvider, ExampleAuthorizationProvider, ExampleDeviceAuthorizationProvider,
+    ExamplePushedAuthorizationProvider, VecClient, VecClientProvider,
 };
-use raos::{common::model::Client, manager::OAuthManager};
-use raos_actix::{ActixOAuthRequest, ActixOAuthResponse};
+use raos::{
+    common::model::{Client, TokenEndpointAuthMethod},
+    device::DEVICE_CODE_GRANT_TYPE,
+    manager::OAuthManager,
+    resource::BearerTokenValidator,
+};
+use raos_actix::{ActixBearerToken, ActixOAuthRequest, ActixOAuthResponse};
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
@@ -15,13 +21,27 @@ async fn main() -> std::io::Result<()> {
                     client_id: "test".to_string(),
                     redirect_uris: vec!["https://oauthdebugger.com/debug".to_string()],
                     confidential: false,
+                    grant_types: vec![
+                        "authorization_code".to_string(),
+                        "refresh_token".to_string(),
+                        DEVICE_CODE_GRANT_TYPE.to_string(),
+                    ],
+                    response_types: vec!["code".to_string()],
+                    token_endpoint_auth_method: TokenEndpointAuthMethod::None,
+                    default_scopes: vec!["bla".to_string()],
+                    access_token_lifetime: None,
+                    refresh_token_lifetime: None,
+                    display: Default::default(),
                 },
                 scopes: vec!["bla"],
                 secret: "bla".to_string(),
             }]))
             .authorization_provider(ExampleAuthorizationProvider::default())
             .token_provider(DumbTokenProvider)
+            .device_authorization_provider(ExampleDeviceAuthorizationProvider::default())
+            .pushed_authorization_provider(ExamplePushedAuthorizationProvider::default())
             .disallow_plain_code_challenge()
+            .set_authorization_server_identifier("http://127.0.0.1:8080".to_string())
             .build(),
     );
     HttpServer::new(move || {
@@ -29,6 +49,17 @@ async fn main() -> std::io::Result<()> {
             .app_data(oauth.clone()) // Pass the oauth manager as data
             .service(authorize)
             .service(token)
+            .service(revoke)
+            .service(introspect)
+            .service(device_authorization)
+            .service(device)
+            .service(par)
+            .service(metadata)
+            .service(
+                web::scope("/api")
+                    .app_data(BearerTokenValidator::new().require_scope("bla"))
+                    .service(resource),
+            )
     })
     .bind(("127.0.0.1", 8080))?
     .run()
@@ -58,3 +89,73 @@ async fn token(
     }
     result.into()
 }
+
+#[post("/revoke")]
+async fn revoke(
+    req: ActixOAuthRequest,
+    oauth: web::Data<OAuthManager<u32, (), ()>>,
+) -> ActixOAuthResponse {
+    let result = oauth.handle_revocation_request(req).await;
+    if let Err(ref e) = result {
+        println!("Error: {e:#?}");
+    }
+    result.into()
+}
+
+#[post("/introspect")]
+async fn introspect(
+    req: ActixOAuthRequest,
+    oauth: web::Data<OAuthManager<u32, (), ()>>,
+) -> ActixOAuthResponse {
+    let result = oauth.handle_introspection_request(req).await;
+    if let Err(ref e) = result {
+        println!("Error: {e:#?}");
+    }
+    result.into()
+}
+
+#[post("/device_authorization")]
+async fn device_authorization(
+    req: ActixOAuthRequest,
+    oauth: web::Data<OAuthManager<u32, (), ()>>,
+) -> ActixOAuthResponse {
+    let result = oauth.handle_device_authorization_request(req).await;
+    if let Err(ref e) = result {
+        println!("Error: {e:#?}");
+    }
+    result.into()
+}
+
+#[get("/device")]
+async fn device(
+    req: ActixOAuthRequest,
+    oauth: web::Data<OAuthManager<u32, (), ()>>,
+) -> ActixOAuthResponse {
+    let result = oauth.handle_device_verification_request(req, None).await;
+    if let Err(ref e) = result {
+        println!("Error: {e:#?}");
+    }
+    result.into()
+}
+
+#[post("/par")]
+async fn par(
+    req: ActixOAuthRequest,
+    oauth: web::Data<OAuthManager<u32, (), ()>>,
+) -> ActixOAuthResponse {
+    let result = oauth.handle_pushed_authorization_request(req).await;
+    if let Err(ref e) = result {
+        println!("Error: {e:#?}");
+    }
+    result.into()
+}
+
+#[get("/.well-known/oauth-authorization-server")]
+async fn metadata(oauth: web::Data<OAuthManager<u32, (), ()>>) -> ActixOAuthResponse {
+    oauth.authorization_server_metadata().into()
+}
+
+#[get("/resource")]
+async fn resource(bearer: ActixBearerToken<u32, (), ()>) -> String {
+    format!("Hello, {}!", bearer.token.client_id)
+}
@@ -8,21 +8,27 @@
 //! **R**ust **A**sync **O**auth **S**erver
 //! Actix wrapper for RAOS.
 
-use std::{collections::HashMap, convert::Infallible};
+use std::{collections::HashMap, convert::Infallible, fmt, marker::PhantomData};
 
 use actix_web::{
     body::BoxBody,
     dev::Payload,
     http::{
         header::{CacheControl, CacheDirective},
-        Method,
+        Method, StatusCode,
     },
-    web, FromRequest, HttpRequest, HttpResponse, Responder,
+    web, FromRequest, HttpMessage, HttpRequest, HttpResponse, Responder, ResponseError,
 };
 use futures::{future::LocalBoxFuture, FutureExt};
 
-use raos::common::frontend::{
-    FrontendRequest, FrontendRequestMethod, FrontendResponse, FrontendResponseExt,
+use raos::{
+    common::frontend::{
+        FrontendRequest, FrontendRequestMethod, FrontendResponse, FrontendResponseExt,
+        PublicOAuthError,
+    },
+    manager::OAuthManager,
+    resource::BearerTokenValidator,
+    token::IssuedToken,
 };
 
 /// Actix request wrapper for RAOS.
@@ -30,15 +36,37 @@ use raos::common::frontend::{
 pub struct ActixOAuthRequest {
     method: FrontendRequestMethod,
     headers: HashMap<String, String>,
-    query: HashMap<String, String>,
-    body: HashMap<String, String>,
+    query: Vec<(String, String)>,
+    body: Vec<(String, String)>,
+    json: Option<serde_json::Value>,
+    peer_certificate: Option<Vec<u8>>,
 }
 
+/// The DER encoded certificate a client presented during the TLS handshake, used for mutual TLS.
+///
+/// Actix does not expose the peer certificate to requests, so it has to be stored as connection data
+/// using [HttpServer::on_connect](actix_web::HttpServer::on_connect), after which [ActixOAuthRequest] will pick it up.
+///
+/// ```ignore
+/// HttpServer::new(app)
+///     .on_connect(|connection, data| {
+///         if let Some(tls) = connection.downcast_ref::<actix_tls::accept::rustls_0_23::TlsStream<TcpStream>>() {
+///             if let Some(certificate) = tls.get_ref().1.peer_certificates().and_then(|c| c.first()) {
+///                 data.insert(PeerCertificate(certificate.to_vec()));
+///             }
+///         }
+///     })
+/// ```
+#[derive(Debug, Clone)]
+pub struct PeerCertificate(pub Vec<u8>);
+
 impl ActixOAuthRequest {
     async fn new(req: HttpRequest, mut payload: Payload) -> Result<Self, Infallible> {
         let method = match req.method() {
             &Method::GET => FrontendRequestMethod::GET,
             &Method::POST => FrontendRequestMethod::POST,
+            &Method::PUT => FrontendRequestMethod::PUT,
+            &Method::DELETE => FrontendRequestMethod::DELETE,
             method => FrontendRequestMethod::OtherUnsupported(method.to_string()),
         };
         let headers = req
@@ -46,15 +74,26 @@ impl ActixOAuthRequest {
             .into_iter()
             .map(|(k, v)| (k.as_str().to_string(), v.to_str().unwrap().to_string()))
             .collect();
-        let query = web::Query::<HashMap<String, String>>::extract(&req)
-            .await
-            .map(|s| s.0)
-            .unwrap_or_default();
-        let body = web::Form::<HashMap<String, String>>::from_request(&req, &mut payload)
+        let query = web::Query::<Vec<(String, String)>>::extract(&req)
             .await
             .map(|s| s.0)
             .unwrap_or_default();
-        Ok(Self { method, headers, query, body })
+        // JSON bodies are only used by client registration, all other endpoints use form encoded bodies
+        let (body, json) = if req.content_type() == "application/json" {
+            let json = web::Json::<serde_json::Value>::from_request(&req, &mut payload)
+                .await
+                .map(|s| s.0)
+                .ok();
+            (Vec::new(), json)
+        } else {
+            let body = web::Form::<Vec<(String, String)>>::from_request(&req, &mut payload)
+                .await
+                .map(|s| s.0)
+                .unwrap_or_default();
+            (body, None)
+        };
+        let peer_certificate = req.conn_data::<PeerCertificate>().map(|c| c.0.clone());
+        Ok(Self { method, headers, query, body, json, peer_certificate })
     }
 }
 
@@ -67,6 +106,16 @@ impl FromRequest for ActixOAuthRequest {
     }
 }
 
+/// Look up the last value of a parameter, as a repeated parameter overrides the earlier values.
+fn param(params: &[(String, String)], key: &str) -> Option<String> {
+    params.iter().rev().find(|(k, _)| k == key).map(|(_, v)| v.clone())
+}
+
+/// Look up all values of a parameter, in the order they were sent.
+fn param_values(params: &[(String, String)], key: &str) -> Vec<String> {
+    params.iter().filter(|(k, _)| k == key).map(|(_, v)| v.clone()).collect()
+}
+
 impl FrontendRequest for ActixOAuthRequest {
     fn request_method(&self) -> FrontendRequestMethod {
         self.method.clone()
@@ -77,11 +126,31 @@ impl FrontendRequest for ActixOAuthRequest {
     }
 
     fn query_param(&self, key: &str) -> Option<String> {
-        self.query.get(key).cloned()
+        param(&self.query, key)
     }
 
     fn body_param(&self, key: &str) -> Option<String> {
-        self.body.get(key).cloned()
+        param(&self.body, key)
+    }
+
+    fn query_param_values(&self, key: &str) -> Vec<String> {
+        param_values(&self.query, key)
+    }
+
+    fn body_param_values(&self, key: &str) -> Vec<String> {
+        param_values(&self.body, key)
+    }
+
+    fn body_params(&self) -> HashMap<String, String> {
+        self.body.iter().cloned().collect()
+    }
+
+    fn body_json(&self) -> Option<serde_json::Value> {
+        self.json.clone()
+    }
+
+    fn peer_certificate(&self) -> Option<Vec<u8>> {
+        self.peer_certificate.clone()
     }
 }
 
@@ -104,16 +173,117 @@ impl Responder for ActixOAuthResponse {
     type Body = BoxBody;
 
     fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        self.into_http_response()
+    }
+}
+
+impl ActixOAuthResponse {
+    fn into_http_response(self) -> HttpResponse {
         match self.req {
             FrontendResponse::Success { json } => HttpResponse::Ok()
                 .insert_header(CacheControl(vec![CacheDirective::NoStore]))
                 .json(json),
-            FrontendResponse::Error { error } => HttpResponse::BadRequest()
+            FrontendResponse::Created { json } => HttpResponse::Created()
                 .insert_header(CacheControl(vec![CacheDirective::NoStore]))
-                .json(error),
+                .json(json),
+            FrontendResponse::Jwt { jwt } => HttpResponse::Ok()
+                .insert_header(CacheControl(vec![CacheDirective::NoStore]))
+                .content_type("application/jwt")
+                .body(jwt),
+            FrontendResponse::Empty => HttpResponse::Ok()
+                .insert_header(CacheControl(vec![CacheDirective::NoStore]))
+                .finish(),
+            FrontendResponse::NoContent => HttpResponse::NoContent()
+                .insert_header(CacheControl(vec![CacheDirective::NoStore]))
+                .finish(),
+            FrontendResponse::Error { status, headers, error } => {
+                let status =
+                    StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+                let mut response = HttpResponse::build(status);
+                response.insert_header(CacheControl(vec![CacheDirective::NoStore]));
+                for header in headers {
+                    response.append_header(header);
+                }
+                response.json(error)
+            }
             FrontendResponse::Redirect { location } => {
                 HttpResponse::Found().append_header(("Location", location.to_string())).finish()
             }
         }
     }
 }
+
+/// Actix extractor that guards a protected resource, by validating the bearer access token of the request.
+///
+/// The token is validated by the [OAuthManager] stored as `web::Data<OAuthManager<U, E, Ex>>`,
+/// against the [BearerTokenValidator] stored as app data. When no validator is stored,
+/// any valid access token sent in the `Authorization` header is accepted.
+/// The request body is only read when the validator allows the token to be sent in the form-encoded body.
+///
+/// ```ignore
+/// App::new()
+///     .app_data(oauth.clone())
+///     .service(
+///         web::scope("/api")
+///             .app_data(BearerTokenValidator::new().require_scope("read"))
+///             .service(resource),
+///     )
+///
+/// #[get("/resource")]
+/// async fn resource(bearer: ActixBearerToken<u32, (), ()>) -> String {
+///     format!("Hello {}", bearer.token.client_id)
+/// }
+/// ```
+pub struct ActixBearerToken<U, E, Ex> {
+    /// The validated access token the request was made with.
+    pub token: IssuedToken<U>,
+    manager: PhantomData<fn() -> (E, Ex)>,
+}
+
+impl<U: 'static, E: 'static, Ex: 'static> FromRequest for ActixBearerToken<U, E, Ex> {
+    type Error = ActixOAuthError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        let manager = req.app_data::<web::Data<OAuthManager<U, E, Ex>>>().cloned();
+        let validator = req.app_data::<BearerTokenValidator>().cloned().unwrap_or_default();
+        // Leave the body to the handler, unless the token may be sent in it
+        let payload = if validator.allows_form_body() { payload.take() } else { Payload::None };
+
+        async move {
+            let Some(manager) = manager else {
+                return Err(ActixOAuthError(PublicOAuthError::ServerError));
+            };
+            let request = ActixOAuthRequest::new(req, payload).await.unwrap_or_else(|e| match e {});
+            let token = manager
+                .handle_protected_resource_request(request, &validator)
+                .await
+                .map_err(|e| ActixOAuthError(e.into()))?;
+            Ok(Self { token, manager: PhantomData })
+        }
+        .boxed_local()
+    }
+}
+
+/// Actix error wrapper for RAOS, returned when an [ActixBearerToken] is rejected.
+/// This implements [ResponseError], responding with the error as described in RFC 6750 section 3,
+/// including the `WWW-Authenticate` header.
+#[derive(Debug)]
+pub struct ActixOAuthError(pub PublicOAuthError);
+
+impl fmt::Display for ActixOAuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl ResponseError for ActixOAuthError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(self.0.status_code()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        ActixOAuthResponse { req: self.0.clone().into() }.into_http_response()
+    }
+}
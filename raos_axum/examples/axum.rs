@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    routing::{get, post},
+    Router,
+};
+
+use example_support::{
+    DumbTokenProvider, ExampleAuthorizationProvider, ExampleDeviceAuthorizationProvider,
+    ExamplePushedAuthorizationProvider, VecClient, VecClientProvider,
+};
+use raos::{
+    common::model::{Client, TokenEndpointAuthMethod},
+    device::DEVICE_CODE_GRANT_TYPE,
+    manager::OAuthManager,
+};
+use raos_axum::{oauth_router, AxumOAuthRequest, AxumOAuthResponse};
+
+type Manager = Arc<OAuthManager<u32, (), ()>>;
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let oauth = Arc::new(
+        OAuthManager::builder()
+            .client_provider(VecClientProvider(vec![VecClient {
+                client: Client {
+                    client_id: "test".to_string(),
+                    redirect_uris: vec!["https://oauthdebugger.com/debug".to_string()],
+                    confidential: false,
+                    grant_types: vec![
+                        "authorization_code".to_string(),
+                        "refresh_token".to_string(),
+                        DEVICE_CODE_GRANT_TYPE.to_string(),
+                    ],
+                    response_types: vec!["code".to_string()],
+                    token_endpoint_auth_method: TokenEndpointAuthMethod::None,
+                    default_scopes: vec!["bla".to_string()],
+                    access_token_lifetime: None,
+                    refresh_token_lifetime: None,
+                    display: Default::default(),
+                },
+                scopes: vec!["bla"],
+                secret: "bla".to_string(),
+            }]))
+            .authorization_provider(ExampleAuthorizationProvider::default())
+            .token_provider(DumbTokenProvider)
+            .device_authorization_provider(ExampleDeviceAuthorizationProvider::default())
+            .pushed_authorization_provider(ExamplePushedAuthorizationProvider::default())
+            .disallow_plain_code_challenge()
+            .set_authorization_server_identifier("http://127.0.0.1:8080".to_string())
+            .build(),
+    );
+
+    // The authorize and token endpoints are served by the router helper, the other endpoints are added by hand
+    let app = oauth_router(oauth.clone()).merge(
+        Router::new()
+            .route("/revoke", post(revoke))
+            .route("/introspect", post(introspect))
+            .route("/device_authorization", post(device_authorization))
+            .route("/device", get(device))
+            .route("/par", post(par))
+            .route("/.well-known/oauth-authorization-server", get(metadata))
+            .with_state(oauth),
+    );
+
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", 8080)).await?;
+    axum::serve(listener, app).await
+}
+
+async fn revoke(State(oauth): State<Manager>, req: AxumOAuthRequest) -> AxumOAuthResponse {
+    let result = oauth.handle_revocation_request(req).await;
+    if let Err(ref e) = result {
+        println!("Error: {e:#?}");
+    }
+    result.into()
+}
+
+async fn introspect(State(oauth): State<Manager>, req: AxumOAuthRequest) -> AxumOAuthResponse {
+    let result = oauth.handle_introspection_request(req).await;
+    if let Err(ref e) = result {
+        println!("Error: {e:#?}");
+    }
+    result.into()
+}
+
+async fn device_authorization(
+    State(oauth): State<Manager>,
+    req: AxumOAuthRequest,
+) -> AxumOAuthResponse {
+    let result = oauth.handle_device_authorization_request(req).await;
+    if let Err(ref e) = result {
+        println!("Error: {e:#?}");
+    }
+    result.into()
+}
+
+async fn device(State(oauth): State<Manager>, req: AxumOAuthRequest) -> AxumOAuthResponse {
+    let result = oauth.handle_device_verification_request(req, None).await;
+    if let Err(ref e) = result {
+        println!("Error: {e:#?}");
+    }
+    result.into()
+}
+
+async fn par(State(oauth): State<Manager>, req: AxumOAuthRequest) -> AxumOAuthResponse {
+    let result = oauth.handle_pushed_authorization_request(req).await;
+    if let Err(ref e) = result {
+        println!("Error: {e:#?}");
+    }
+    result.into()
+}
+
+async fn metadata(State(oauth): State<Manager>) -> AxumOAuthResponse {
+    oauth.authorization_server_metadata().into()
+}
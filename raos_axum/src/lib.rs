@@ -0,0 +1,241 @@
+#![forbid(unsafe_code)]
+#![warn(missing_docs, rust_2018_idioms, unreachable_pub)]
+
+//! # RAOS-axum
+//!
+//! ## In development
+//!
+//! **R**ust **A**sync **O**auth **S**erver
+//! Axum wrapper for RAOS.
+
+use std::{collections::HashMap, convert::Infallible, sync::Arc};
+
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, Request, State},
+    http::{
+        header::{CACHE_CONTROL, CONTENT_TYPE, LOCATION},
+        HeaderMap, HeaderValue, Method, StatusCode,
+    },
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Router,
+};
+use serde::Serialize;
+
+use raos::{
+    common::frontend::{
+        FrontendRequest, FrontendRequestMethod, FrontendResponse, FrontendResponseExt,
+    },
+    manager::OAuthManager,
+};
+
+/// Axum request wrapper for RAOS.
+/// This implements [FrontendRequest] for axum requests, also implements the trait required to function as an extractor via [FromRequest].
+pub struct AxumOAuthRequest {
+    method: FrontendRequestMethod,
+    headers: HashMap<String, String>,
+    query: Vec<(String, String)>,
+    body: Vec<(String, String)>,
+    json: Option<serde_json::Value>,
+}
+
+impl<S> FromRequest<S> for AxumOAuthRequest
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let method = match req.method() {
+            &Method::GET => FrontendRequestMethod::GET,
+            &Method::POST => FrontendRequestMethod::POST,
+            &Method::PUT => FrontendRequestMethod::PUT,
+            &Method::DELETE => FrontendRequestMethod::DELETE,
+            method => FrontendRequestMethod::OtherUnsupported(method.to_string()),
+        };
+        let headers = req
+            .headers()
+            .iter()
+            .filter_map(|(k, v)| Some((k.as_str().to_string(), v.to_str().ok()?.to_string())))
+            .collect();
+        let query = req
+            .uri()
+            .query()
+            .and_then(|query| serde_urlencoded::from_str(query).ok())
+            .unwrap_or_default();
+
+        // Form encoded bodies are used by most endpoints, JSON bodies only by client registration,
+        // anything else is treated as an empty body
+        let content_type = req
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let (body, json) = if content_type.starts_with("application/x-www-form-urlencoded") {
+            let body = Bytes::from_request(req, state)
+                .await
+                .ok()
+                .and_then(|bytes| serde_urlencoded::from_bytes(&bytes).ok())
+                .unwrap_or_default();
+            (body, None)
+        } else if content_type.starts_with("application/json") {
+            let json = Bytes::from_request(req, state)
+                .await
+                .ok()
+                .and_then(|bytes| serde_json::from_slice(&bytes).ok());
+            (Vec::new(), json)
+        } else {
+            (Vec::new(), None)
+        };
+
+        Ok(Self { method, headers, query, body, json })
+    }
+}
+
+/// Look up the last value of a parameter, as a repeated parameter overrides the earlier values.
+fn param(params: &[(String, String)], key: &str) -> Option<String> {
+    params.iter().rev().find(|(k, _)| k == key).map(|(_, v)| v.clone())
+}
+
+/// Look up all values of a parameter, in the order they were sent.
+fn param_values(params: &[(String, String)], key: &str) -> Vec<String> {
+    params.iter().filter(|(k, _)| k == key).map(|(_, v)| v.clone()).collect()
+}
+
+impl FrontendRequest for AxumOAuthRequest {
+    fn request_method(&self) -> FrontendRequestMethod {
+        self.method.clone()
+    }
+
+    fn header_param(&self, key: &str) -> Option<String> {
+        self.headers.get(key).cloned()
+    }
+
+    fn query_param(&self, key: &str) -> Option<String> {
+        param(&self.query, key)
+    }
+
+    fn body_param(&self, key: &str) -> Option<String> {
+        param(&self.body, key)
+    }
+
+    fn query_param_values(&self, key: &str) -> Vec<String> {
+        param_values(&self.query, key)
+    }
+
+    fn body_param_values(&self, key: &str) -> Vec<String> {
+        param_values(&self.body, key)
+    }
+
+    fn body_params(&self) -> HashMap<String, String> {
+        self.body.iter().cloned().collect()
+    }
+
+    fn body_json(&self) -> Option<serde_json::Value> {
+        self.json.clone()
+    }
+}
+
+/// Axum response wrapper for RAOS.
+/// This implements [IntoResponse] for axum responses and can be constructed from any type that implements [FrontendResponseExt].
+pub struct AxumOAuthResponse {
+    req: FrontendResponse,
+}
+
+impl<E> From<E> for AxumOAuthResponse
+where
+    E: FrontendResponseExt + Sized,
+{
+    fn from(value: E) -> Self {
+        Self { req: value.into_frontend_response() }
+    }
+}
+
+impl IntoResponse for AxumOAuthResponse {
+    fn into_response(self) -> Response {
+        let mut headers = HeaderMap::new();
+        headers.insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+
+        match self.req {
+            FrontendResponse::Success { json } => json_response(StatusCode::OK, headers, &json),
+            FrontendResponse::Created { json } => {
+                json_response(StatusCode::CREATED, headers, &json)
+            }
+            FrontendResponse::Jwt { jwt } => {
+                headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/jwt"));
+                (StatusCode::OK, headers, jwt).into_response()
+            }
+            FrontendResponse::Empty => (StatusCode::OK, headers).into_response(),
+            FrontendResponse::NoContent => (StatusCode::NO_CONTENT, headers).into_response(),
+            FrontendResponse::Error { status, headers: error_headers, error } => {
+                for (name, value) in error_headers {
+                    let Ok(value) = HeaderValue::from_str(&value) else {
+                        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+                    };
+                    headers.append(name, value);
+                }
+                let status =
+                    StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+                json_response(status, headers, &error)
+            }
+            FrontendResponse::Redirect { location } => {
+                let Ok(location) = HeaderValue::from_str(location.as_str()) else {
+                    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+                };
+                headers.insert(LOCATION, location);
+                (StatusCode::FOUND, headers).into_response()
+            }
+        }
+    }
+}
+
+fn json_response(status: StatusCode, mut headers: HeaderMap, body: &impl Serialize) -> Response {
+    let Ok(body) = serde_json::to_string(body) else {
+        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+    };
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    (status, headers, body).into_response()
+}
+
+/// Build a [Router] that serves the authorization endpoint on `/authorize` and the token endpoint on `/token`
+/// for the given [OAuthManager].
+///
+/// The returned router can be merged into, or nested in, the router of the application.
+/// Other endpoints, like revocation or introspection, can be added using [AxumOAuthRequest] and [AxumOAuthResponse].
+pub fn oauth_router<U, E, Ex>(manager: Arc<OAuthManager<U, E, Ex>>) -> Router
+where
+    U: Send + Sync + 'static,
+    E: Send + Sync + 'static,
+    Ex: Send + Sync + 'static,
+{
+    Router::new()
+        .route("/authorize", get(authorize::<U, E, Ex>))
+        .route("/token", post(token::<U, E, Ex>))
+        .with_state(manager)
+}
+
+async fn authorize<U, E, Ex>(
+    State(manager): State<Arc<OAuthManager<U, E, Ex>>>,
+    req: AxumOAuthRequest,
+) -> AxumOAuthResponse
+where
+    U: Send + Sync + 'static,
+    E: Send + Sync + 'static,
+    Ex: Send + Sync + 'static,
+{
+    manager.handle_authorization_request(req, None).await.into()
+}
+
+async fn token<U, E, Ex>(
+    State(manager): State<Arc<OAuthManager<U, E, Ex>>>,
+    req: AxumOAuthRequest,
+) -> AxumOAuthResponse
+where
+    U: Send + Sync + 'static,
+    E: Send + Sync + 'static,
+    Ex: Send + Sync + 'static,
+{
+    manager.handle_token_request(req).await.into()
+}